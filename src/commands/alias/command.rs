@@ -0,0 +1,176 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+use serenity::Error;
+
+use crate::commands::shared::embed_error_handling::{
+    create_embed_error, schedule_message_deletion,
+};
+use crate::commands::shared::logs::{get_server_name, send_log};
+use crate::commands::shared::models::LoggerDocument;
+use crate::commands::shared::player_alias::{
+    add_alias, list_aliases, remove_alias, search_aliases,
+};
+use crate::Data;
+
+/// Limite de caractères d'une description d'embed
+const EMBED_DESCRIPTION_LIMIT: usize = 4000;
+
+async fn log_alias(
+    ctx: &poise::ApplicationContext<'_, Data, Error>,
+    subcommand: &str,
+    success: bool,
+) -> Result<(), Error> {
+    send_log(LoggerDocument::new(
+        &ctx.author().name,
+        &format!("alias {}", subcommand),
+        &get_server_name(ctx).await?,
+        success,
+        chrono::Utc::now().timestamp(),
+    ))
+    .await
+}
+
+async fn autocomplete_alias<'a>(
+    _ctx: poise::ApplicationContext<'a, Data, Error>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    search_aliases(partial, 25)
+        .into_iter()
+        .map(|(alias, _)| alias)
+}
+
+/// 📂 Manage player aliases (bot owners only)
+#[poise::command(
+    slash_command,
+    owners_only,
+    subcommands("add", "remove", "list"),
+    subcommand_required
+)]
+pub async fn alias(_ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Add an alias to a lucksack player
+#[poise::command(slash_command, owners_only)]
+pub async fn add(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Lucksack player id"] lucksack_id: i64,
+    #[description = "Alias"] name: String,
+    #[description = "SWArena player id"] swarena_id: Option<i64>,
+) -> Result<(), Error> {
+    let result = add_alias(lucksack_id, &name, swarena_id).await;
+
+    match &result {
+        Ok(()) => {
+            ctx.say(format!(
+                "✅ `{}` is now an alias of lucksack player {}.",
+                name, lucksack_id
+            ))
+            .await?;
+        }
+        Err(e) => {
+            let reply = ctx.send(create_embed_error(&format!("❌ {}", e))).await?;
+            schedule_message_deletion(reply, ctx).await?;
+        }
+    }
+
+    log_alias(&ctx, "add", result.is_ok()).await
+}
+
+/// Remove an alias
+#[poise::command(slash_command, owners_only)]
+pub async fn remove(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Alias"]
+    #[autocomplete = "autocomplete_alias"]
+    name: String,
+) -> Result<(), Error> {
+    let result = remove_alias(&name).await;
+
+    match &result {
+        Ok(Some(lucksack_id)) => {
+            ctx.say(format!(
+                "🗑️ `{}` removed from lucksack player {}.",
+                name, lucksack_id
+            ))
+            .await?;
+        }
+        Ok(None) => {
+            let reply = ctx
+                .send(create_embed_error(&format!("❌ Unknown alias `{}`.", name)))
+                .await?;
+            schedule_message_deletion(reply, ctx).await?;
+        }
+        Err(e) => {
+            let reply = ctx.send(create_embed_error(&format!("❌ {}", e))).await?;
+            schedule_message_deletion(reply, ctx).await?;
+        }
+    }
+
+    log_alias(&ctx, "remove", matches!(result, Ok(Some(_)))).await
+}
+
+/// List aliases, optionally filtered by a fuzzy search
+#[poise::command(slash_command, owners_only)]
+pub async fn list(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Search"] query: Option<String>,
+) -> Result<(), Error> {
+    let lines: Vec<String> = match query.as_deref() {
+        Some(q) => search_aliases(q, 50)
+            .into_iter()
+            .map(|(alias, id)| format!("- **{}** → `{}`", alias, id))
+            .collect(),
+        None => {
+            let mut entries = list_aliases();
+            entries.sort_by_key(|e| e.en_names.first().map(|n| n.to_lowercase()));
+            entries
+                .iter()
+                .map(|e| {
+                    let swarena = e
+                        .swarena_id
+                        .map(|id| format!(" • swarena `{}`", id))
+                        .unwrap_or_default();
+                    format!(
+                        "- **{}** → `{}`{}",
+                        e.en_names.join("**, **"),
+                        e.lucksack_id,
+                        swarena
+                    )
+                })
+                .collect()
+        }
+    };
+
+    let total = lines.len();
+    let mut description = String::new();
+    for line in &lines {
+        if description.len() + line.len() + 1 > EMBED_DESCRIPTION_LIMIT {
+            description.push('…');
+            break;
+        }
+        description.push_str(line);
+        description.push('\n');
+    }
+    if description.is_empty() {
+        description.push_str("No alias found.");
+    }
+
+    let embed = CreateEmbed::default()
+        .title(format!("📇 Player aliases ({})", total))
+        .description(description)
+        .footer(CreateEmbedFooter::new("alias → lucksack id"))
+        .color(serenity::Colour::from_rgb(0, 180, 255));
+
+    let result = ctx
+        .send(CreateReply {
+            embeds: vec![embed],
+            ephemeral: Some(true),
+            ..Default::default()
+        })
+        .await;
+
+    log_alias(&ctx, "list", result.is_ok()).await?;
+    result.map(|_| ())
+}
@@ -0,0 +1,32 @@
+//! Cache asynchrone pour les équipes swcalc.cz (get_dungeon_stats)
+use crate::commands::best_pve_teams::models::DungeonTeamData;
+use crate::commands::best_pve_teams::utils::get_dungeon_stats;
+use anyhow::{anyhow, Result};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Clé : (dungeon_id, page)
+type DungeonPageKey = (u32, u32);
+
+static DUNGEON_TEAMS_CACHE: Lazy<Cache<DungeonPageKey, Arc<Vec<DungeonTeamData>>>> =
+    Lazy::new(|| {
+        Cache::builder()
+            .time_to_live(Duration::from_secs(3 * 3600))
+            .max_capacity(1_000)
+            .build()
+    });
+
+/// One batch of teams of a dungeon, cached for 3 hours. Errors are not cached.
+pub async fn get_dungeon_stats_cached(
+    dungeon_id: u32,
+    page: u32,
+) -> Result<Arc<Vec<DungeonTeamData>>> {
+    DUNGEON_TEAMS_CACHE
+        .try_get_with((dungeon_id, page), async move {
+            get_dungeon_stats(dungeon_id, page).await.map(Arc::new)
+        })
+        .await
+        .map_err(|e| anyhow!("Failed to fetch dungeon teams: {}", e))
+}
@@ -1,7 +1,11 @@
-use crate::commands::best_pve_teams::models::Dungeon;
+use crate::commands::best_pve_teams::cache::get_dungeon_stats_cached;
+use crate::commands::best_pve_teams::models::{DungeonTeamData, PveTeamSort, PveTeamsView};
 use crate::commands::best_pve_teams::utils::{
-    build_monster_name_map, create_pve_teams_embed, get_dungeon_stats,
+    build_core_id_map, build_monster_name_map, bundled_dungeon_catalog,
+    create_pve_pagination_buttons, create_pve_teams_embed, filter_teams_for_box, find_dungeon,
+    search_dungeons, sort_teams,
 };
+use crate::commands::player_box::utils::resolve_box;
 use crate::commands::player_stats::utils::get_mob_emoji_collection;
 use crate::commands::shared::embed_error_handling::{
     create_embed_error, schedule_message_deletion,
@@ -11,19 +15,167 @@ use crate::commands::shared::models::LoggerDocument;
 use crate::Data;
 use poise::serenity_prelude as serenity;
 use poise::CreateReply;
-use serenity::Error;
+use serenity::builder::EditInteractionResponse;
+use serenity::{Attachment, CreateInteractionResponse, CreateInteractionResponseMessage, Error};
+use std::collections::{HashMap, HashSet};
 
-/// 📂 Displays the current best PvE teams to use
+/// Équipes "à 1 monstre près" affichées avec un box
+const NEAR_TEAMS_SHOWN: usize = 3;
+
+/// Équipes affichées par page
+const TEAMS_PER_PAGE: usize = 5;
+
+/// Nombre max de lots swcalc.cz chargés par commande
+const MAX_API_PAGES: u32 = 10;
+
+/// Autocomplete sur le catalogue des donjons : tous les étages d'un donjon
+/// dès que son nom est tapé, puis étage ou catégorie
+pub async fn autocomplete_dungeon<'a>(
+    _ctx: poise::ApplicationContext<'a, Data, Error>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    search_dungeons(bundled_dungeon_catalog(), partial, 25).into_iter()
+}
+
+/// Équipes chargées depuis swcalc.cz, lot par lot
+struct TeamPages {
+    dungeon_id: u32,
+    view: PveTeamsView,
+    loaded: Vec<DungeonTeamData>,
+    next_api_page: u32,
+    exhausted: bool,
+}
+
+impl TeamPages {
+    fn new(dungeon_id: u32, view: PveTeamsView) -> Self {
+        TeamPages {
+            dungeon_id,
+            view,
+            loaded: Vec::new(),
+            next_api_page: 1,
+            exhausted: false,
+        }
+    }
+
+    async fn load_next_batch(&mut self) -> anyhow::Result<()> {
+        let batch = get_dungeon_stats_cached(self.dungeon_id, self.next_api_page).await?;
+        self.next_api_page += 1;
+
+        // Un lot vide ou déjà vu signifie qu'il n'y a plus rien à charger
+        let known: HashSet<u32> = self.loaded.iter().map(|t| t.id).collect();
+        let before = self.loaded.len();
+        self.loaded
+            .extend(batch.iter().filter(|t| !known.contains(&t.id)).cloned());
+        if self.loaded.len() == before || self.next_api_page > MAX_API_PAGES {
+            self.exhausted = true;
+        }
+
+        sort_teams(&mut self.loaded, self.view.sort);
+        Ok(())
+    }
+
+    /// Teams shown to the user, filtered by their box when one is given
+    fn view(
+        &self,
+        owned_ids: Option<&HashSet<u32>>,
+        core_ids: &HashMap<String, u32>,
+        monster_name_map: &HashMap<String, String>,
+    ) -> Vec<DungeonTeamData> {
+        let min_success = self.view.min_success_rate.unwrap_or(0.0) / 100.0;
+        let eligible: Vec<DungeonTeamData> = self
+            .loaded
+            .iter()
+            .filter(|t| t.success_rate >= min_success)
+            .cloned()
+            .collect();
+
+        let mut teams = match owned_ids {
+            Some(owned_ids) => filter_teams_for_box(
+                eligible,
+                owned_ids,
+                core_ids,
+                monster_name_map,
+                usize::MAX,
+                NEAR_TEAMS_SHOWN,
+            ),
+            None => eligible,
+        };
+
+        for team in teams.iter_mut() {
+            team.average_time_ms = team.mean_time_ms.round() as u32;
+            team.success_rate_pct = team.success_rate * 100.0;
+        }
+        teams
+    }
+
+    /// Loads batches until `page` is full and we know whether a next page exists.
+    /// Any sort other than the swcalc.cz score needs every batch to be ordered.
+    /// Returns the teams of the page and whether there is a next one.
+    async fn page(
+        &mut self,
+        page: usize,
+        owned_ids: Option<&HashSet<u32>>,
+        core_ids: &HashMap<String, u32>,
+        monster_name_map: &HashMap<String, String>,
+    ) -> anyhow::Result<(Vec<DungeonTeamData>, bool)> {
+        let needed = match self.view.sort {
+            PveTeamSort::Score => page * TEAMS_PER_PAGE + 1,
+            _ => usize::MAX,
+        };
+        let mut teams = self.view(owned_ids, core_ids, monster_name_map);
+        while teams.len() < needed && !self.exhausted {
+            self.load_next_batch().await?;
+            teams = self.view(owned_ids, core_ids, monster_name_map);
+        }
+
+        let has_next = teams.len() > page * TEAMS_PER_PAGE;
+        let page_teams = teams
+            .into_iter()
+            .skip((page - 1) * TEAMS_PER_PAGE)
+            .take(TEAMS_PER_PAGE)
+            .collect();
+        Ok((page_teams, has_next))
+    }
+}
+
+/// 📂 Displays the current best PvE teams to use, optionally from your own box
 ///
-/// Usage: `/best_pve_teams`
+/// Usage: `/best_pve_teams <dungeon> [sort] [min_success_rate] [file] [my_box]`
 #[poise::command(slash_command)]
 pub async fn best_pve_teams(
     ctx: poise::ApplicationContext<'_, Data, Error>,
-    #[description = "Select the dungeon"] dungeon: Dungeon,
+    #[description = "Dungeon and stage (or a swcalc.cz dungeon id)"]
+    #[autocomplete = "autocomplete_dungeon"]
+    dungeon: String,
+    #[description = "Team order (default: best score)"] sort: Option<PveTeamSort>,
+    #[description = "Hide teams below this success rate (%)"]
+    #[min = 0]
+    #[max = 100]
+    min_success_rate: Option<f64>,
+    #[description = "Account JSON: only teams you can field"] file: Option<Attachment>,
+    #[description = "Only teams your saved box can field (/box save)"] my_box: Option<bool>,
 ) -> Result<(), Error> {
     // Évite le timeout de 3 s
     ctx.defer().await?;
 
+    let Some(dungeon) = find_dungeon(bundled_dungeon_catalog(), &dungeon) else {
+        let err_msg = format!(
+            "Unknown dungeon `{}`. Pick one from the list or type a swcalc.cz dungeon id.",
+            dungeon
+        );
+        let reply = ctx.send(create_embed_error(&err_msg)).await?;
+        schedule_message_deletion(reply, ctx).await?;
+        send_log(LoggerDocument::new(
+            &ctx.author().name,
+            "best_pve_teams",
+            &get_server_name(&ctx).await?,
+            false,
+            chrono::Utc::now().timestamp(),
+        ))
+        .await?;
+        return Ok(());
+    };
+
     // Récupération de la collection d'emojis
     let collection = match get_mob_emoji_collection().await {
         Ok(c) => c,
@@ -44,12 +196,43 @@ pub async fn best_pve_teams(
         }
     };
 
-    let dungeon_name = dungeon.clone().label();
-    let dungeon_slug = dungeon.clone().slug();
-    let dungeon_id = dungeon.id();
+    // 📦 Box du joueur (optionnel)
+    let owned_ids: Option<HashSet<u32>> = if file.is_some() || my_box.unwrap_or(false) {
+        match resolve_box(file.as_ref(), ctx.author().id.get()).await {
+            Ok(resolved) => Some(resolved.monsters.iter().map(|m| m.unit_master_id).collect()),
+            Err(err_msg) => {
+                let reply = ctx.send(create_embed_error(&err_msg)).await?;
+                schedule_message_deletion(reply, ctx).await?;
+                send_log(LoggerDocument::new(
+                    &ctx.author().name,
+                    "best_pve_teams",
+                    &get_server_name(&ctx).await?,
+                    false,
+                    chrono::Utc::now().timestamp(),
+                ))
+                .await?;
+                return Ok(());
+            }
+        }
+    } else {
+        None
+    };
+
+    let monster_name_map = build_monster_name_map();
+    let core_ids = build_core_id_map();
+    let view = PveTeamsView {
+        from_box: owned_ids.is_some(),
+        sort: sort.unwrap_or_default(),
+        min_success_rate,
+    };
+    let mut pages = TeamPages::new(dungeon.id, view);
+    let mut page: usize = 1;
 
     // Récupération des données du donjon sélectionné
-    let dungeon_data = match get_dungeon_stats(dungeon_id).await {
+    let (teams, mut has_next) = match pages
+        .page(page, owned_ids.as_ref(), &core_ids, &monster_name_map)
+        .await
+    {
         Ok(data) => data,
         Err(e) => {
             let err_msg = format!("Impossible de récupérer les données : {}", e);
@@ -67,39 +250,24 @@ pub async fn best_pve_teams(
         }
     };
 
-    // Trier le tableau par rank_score décroissant
-    let top_n: usize = 5;
-    let mut sorted_data = dungeon_data.clone();
-    sorted_data.sort_by(|a, b| {
-        b.rank
-            .partial_cmp(&a.rank)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
-    let len = sorted_data.len();
-    let top = &mut sorted_data[..top_n.min(len)];
-
-    for team in top.iter_mut() {
-        team.average_time_ms = team.mean_time_ms.round() as u32;
-        team.success_rate_pct = team.success_rate * 100.0;
-    }
-
-    let monster_name_map = build_monster_name_map();
-
     let embed = create_pve_teams_embed(
-        dungeon_name,
-        dungeon_slug,
-        top,
+        &dungeon,
+        &view,
+        &teams,
+        1,
+        page,
         &collection,
         &monster_name_map,
     )
     .await;
 
-    let reply = CreateReply {
-        embeds: vec![embed],
-        ..Default::default()
-    };
-
-    ctx.send(reply).await?;
+    let reply = ctx
+        .send(CreateReply {
+            embeds: vec![embed],
+            components: Some(vec![create_pve_pagination_buttons(page, has_next, false)]),
+            ..Default::default()
+        })
+        .await?;
 
     // 📝 Logging
     send_log(LoggerDocument::new(
@@ -111,5 +279,78 @@ pub async fn best_pve_teams(
     ))
     .await?;
 
+    let message_id = reply.message().await?.id;
+    let channel_id = ctx.channel_id();
+    let user_id = ctx.author().id;
+
+    // Boucle de gestion des boutons Previous / Next
+    while let Some(interaction) =
+        serenity::ComponentInteractionCollector::new(&ctx.serenity_context.shard)
+            .channel_id(channel_id)
+            .message_id(message_id)
+            .filter(move |i| i.user.id == user_id)
+            .timeout(std::time::Duration::from_secs(600))
+            .await
+    {
+        match interaction.data.custom_id.as_str() {
+            "pve_previous_page" if page > 1 => page -= 1,
+            "pve_next_page" if has_next => page += 1,
+            _ => continue,
+        }
+
+        // Boutons désactivés pendant le chargement du lot suivant
+        interaction
+            .create_response(
+                &ctx.serenity_context,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .components(vec![create_pve_pagination_buttons(page, has_next, true)]),
+                ),
+            )
+            .await?;
+
+        let teams = match pages
+            .page(page, owned_ids.as_ref(), &core_ids, &monster_name_map)
+            .await
+        {
+            Ok((teams, next)) => {
+                has_next = next;
+                teams
+            }
+            Err(e) => {
+                interaction
+                    .edit_response(
+                        &ctx.serenity_context.http,
+                        EditInteractionResponse::new()
+                            .content(format!("❌ Error fetching dungeon teams: {}", e))
+                            .components(vec![])
+                            .embeds(vec![]),
+                    )
+                    .await?;
+                break;
+            }
+        };
+
+        let embed = create_pve_teams_embed(
+            &dungeon,
+            &view,
+            &teams,
+            (page - 1) * TEAMS_PER_PAGE + 1,
+            page,
+            &collection,
+            &monster_name_map,
+        )
+        .await;
+
+        interaction
+            .edit_response(
+                &ctx.serenity_context.http,
+                EditInteractionResponse::new()
+                    .embeds(vec![embed])
+                    .components(vec![create_pve_pagination_buttons(page, has_next, false)]),
+            )
+            .await?;
+    }
+
     Ok(())
 }
@@ -7,53 +7,50 @@ pub struct MonstersFile {
 
 #[derive(Debug, Deserialize)]
 pub struct MonsterElement {
+    pub com2us_id: u32,
     pub name: String,
     pub image_filename: String,
 }
 
-#[derive(Debug, Clone, poise::ChoiceParameter)]
-pub enum Dungeon {
-    #[name = "Giant's Keep"]
-    GiantsKeep,
-
-    #[name = "Dragon's Lair"]
-    DragonsLair,
-
-    #[name = "Necropolis"]
-    Necropolis,
-
-    #[name = "Steel Fortress"]
-    SteelFortress,
-
-    #[name = "Punisher's Crypt"]
-    PunishersCrypt,
-
-    #[name = "Spiritual Realm"]
-    SpiritualRealm,
-
-    #[name = "Karzhan - Forest of Roaring Beasts"]
-    KarzhanForest,
-
-    #[name = "Ellunia - Sanctuary of Dreaming Fairies"]
-    ElluniaSanctuary,
-
-    #[name = "Lumel - Cliff of Tough Beast Men"]
-    LumelCliff,
-
-    #[name = "Khalderun - Ruin of Silent Death"]
-    KhalderunRuin,
-
-    #[name = "Sacreon - Castle of Forgotten Heroes"]
-    SacreonCastle,
+#[derive(Debug, Deserialize)]
+pub struct DungeonCatalog {
+    pub dungeons: Vec<DungeonEntry>,
+}
 
-    #[name = "Inferath - Hellground of Raging Fires"]
-    InferathHellground,
+/// Dungeon known to swcalc.cz, one entry per stage/difficulty
+#[derive(Debug, Deserialize, Clone)]
+pub struct DungeonEntry {
+    pub id: u32,
+    pub name: String,
+    #[serde(default = "default_dungeon_category")]
+    pub category: String,
+    /// Floor or difficulty ("B12", "Hard", ...)
+    #[serde(default)]
+    pub stage: Option<String>,
+    /// Page on swcalc.cz/dungeons, absent for ids outside the catalog
+    #[serde(default)]
+    pub slug: Option<String>,
+}
 
-    #[name = "Fire Beast"]
-    FireBeast,
+/// Pas de tri par popularité : aucun champ de nombre de runs confirmé côté swcalc.cz
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, poise::ChoiceParameter)]
+pub enum PveTeamSort {
+    #[default]
+    #[name = "Best score"]
+    Score,
+    #[name = "Fastest"]
+    Fastest,
+    #[name = "Highest success rate"]
+    SuccessRate,
+}
 
-    #[name = "Dark Beast"]
-    DarkBeast,
+/// Options of a /best_pve_teams call that shape the displayed list
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PveTeamsView {
+    pub from_box: bool,
+    pub sort: PveTeamSort,
+    /// Taux de réussite minimum, en %
+    pub min_success_rate: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -78,63 +75,32 @@ pub struct DungeonTeamData {
     pub average_time_ms: u32, // int ms
     #[serde(default)]
     pub success_rate_pct: f64, // %
+    /// Membres absents du box du joueur (filtre par box)
+    #[serde(default)]
+    pub missing_members: Vec<String>,
 }
 
-impl Dungeon {
-    pub const fn id(self) -> u32 {
-        match self {
-            Dungeon::GiantsKeep => 8011,
-            Dungeon::DragonsLair => 9011,
-            Dungeon::Necropolis => 6011,
-            Dungeon::SteelFortress => 9511,
-            Dungeon::PunishersCrypt => 9512,
-            Dungeon::SpiritualRealm => 9513,
-            Dungeon::KarzhanForest => 2101,
-            Dungeon::ElluniaSanctuary => 1101,
-            Dungeon::LumelCliff => 3101,
-            Dungeon::KhalderunRuin => 4101,
-            Dungeon::SacreonCastle => 5101,
-            Dungeon::InferathHellground => 6101,
-            Dungeon::FireBeast => 2001,
-            Dungeon::DarkBeast => 5001,
-        }
-    }
+fn default_dungeon_category() -> String {
+    "Other".to_string()
+}
 
-    pub const fn label(self) -> &'static str {
-        match self {
-            Dungeon::GiantsKeep => "Giant's Keep",
-            Dungeon::DragonsLair => "Dragon's Lair",
-            Dungeon::Necropolis => "Necropolis",
-            Dungeon::SteelFortress => "Steel Fortress",
-            Dungeon::PunishersCrypt => "Punisher's Crypt",
-            Dungeon::SpiritualRealm => "Spiritual Realm",
-            Dungeon::KarzhanForest => "Karzhan - Forest of Roaring Beasts",
-            Dungeon::ElluniaSanctuary => "Ellunia - Sanctuary of Dreaming Fairies",
-            Dungeon::LumelCliff => "Lumel - Cliff of Tough Beast Men",
-            Dungeon::KhalderunRuin => "Khalderun - Ruin of Silent Death",
-            Dungeon::SacreonCastle => "Sacreon - Castle of Forgotten Heroes",
-            Dungeon::InferathHellground => "Inferath - Hellground of Raging Fires",
-            Dungeon::FireBeast => "Fire Beast",
-            Dungeon::DarkBeast => "Dark Beast",
+impl DungeonEntry {
+    /// "Giant's Keep (B12)", as shown in the autocomplete
+    pub fn label(&self) -> String {
+        match &self.stage {
+            Some(stage) => format!("{} ({})", self.name, stage),
+            None => self.name.clone(),
         }
     }
 
-    pub const fn slug(self) -> &'static str {
-        match self {
-            Dungeon::GiantsKeep => "giants-keep",
-            Dungeon::DragonsLair => "dragons-lair",
-            Dungeon::Necropolis => "necropolis",
-            Dungeon::SteelFortress => "steel-fortress",
-            Dungeon::PunishersCrypt => "punishers-crypt",
-            Dungeon::SpiritualRealm => "spiritual-realm",
-            Dungeon::KarzhanForest => "karzhan",
-            Dungeon::ElluniaSanctuary => "ellunia",
-            Dungeon::LumelCliff => "lumel",
-            Dungeon::KhalderunRuin => "khalderun",
-            Dungeon::SacreonCastle => "sacreon",
-            Dungeon::InferathHellground => "inferath",
-            Dungeon::FireBeast => "fire-beast",
-            Dungeon::DarkBeast => "dark-beast",
+    /// Entry for a raw swcalc.cz dungeon id missing from the catalog
+    pub fn from_id(id: u32) -> Self {
+        DungeonEntry {
+            id,
+            name: format!("Dungeon #{}", id),
+            category: default_dungeon_category(),
+            stage: None,
+            slug: None,
         }
     }
 }
@@ -1,15 +1,89 @@
 use anyhow::{Context, Result};
 use mongodb::bson::{doc, Document};
 use mongodb::Collection;
+use once_cell::sync::Lazy;
 use reqwest::Client;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fs;
 
 use poise::serenity_prelude as serenity;
+use poise::ChoiceParameter;
 use serenity::builder::{CreateEmbed, CreateEmbedFooter};
 
-use crate::commands::best_pve_teams::models::{ApiResponse, DungeonTeamData, MonstersFile};
+use crate::commands::best_pve_teams::models::{
+    ApiResponse, DungeonCatalog, DungeonEntry, DungeonTeamData, MonstersFile, PveTeamSort,
+    PveTeamsView,
+};
+use crate::commands::mob_stats::utils::remap_monster_id;
 
-pub async fn get_dungeon_stats(dungeon_id: u32) -> Result<Vec<DungeonTeamData>> {
+/// Catalogue des donjons (id swcalc.cz, nom, étage) proposé par l'autocomplete.
+/// Seuls des ids swcalc.cz confirmés y figurent
+pub const DUNGEON_CATALOG_PATH: &str = "assets/pve_dungeons.json";
+
+static BUNDLED_DUNGEON_CATALOG: Lazy<Vec<DungeonEntry>> =
+    Lazy::new(|| match load_dungeon_catalog(DUNGEON_CATALOG_PATH) {
+        Ok(dungeons) => dungeons,
+        Err(e) => {
+            eprintln!("Failed to load {}: {:?}", DUNGEON_CATALOG_PATH, e);
+            Vec::new()
+        }
+    });
+
+fn load_dungeon_catalog(path: &str) -> Result<Vec<DungeonEntry>> {
+    let raw = fs::read_to_string(path).with_context(|| format!("Impossible de lire {}", path))?;
+    let catalog: DungeonCatalog =
+        serde_json::from_str(&raw).with_context(|| format!("Impossible de parser {}", path))?;
+    Ok(catalog.dungeons)
+}
+
+pub fn bundled_dungeon_catalog() -> &'static [DungeonEntry] {
+    &BUNDLED_DUNGEON_CATALOG
+}
+
+/// Dungeon picked in the autocomplete (label) or typed as a swcalc.cz id.
+pub fn find_dungeon(catalog: &[DungeonEntry], input: &str) -> Option<DungeonEntry> {
+    let input = input.trim();
+    let lower = input.to_lowercase();
+
+    if let Some(entry) = catalog.iter().find(|d| d.label().to_lowercase() == lower) {
+        return Some(entry.clone());
+    }
+
+    // Id brut : catalogue d'abord, sinon donjon hors catalogue
+    let id: u32 = input.parse().ok()?;
+    Some(
+        catalog
+            .iter()
+            .find(|d| d.id == id)
+            .cloned()
+            .unwrap_or_else(|| DungeonEntry::from_id(id)),
+    )
+}
+
+/// Autocomplete entries: every stage of the dungeons whose name matches
+/// `partial`, then the entries matching on stage or category.
+pub fn search_dungeons(catalog: &[DungeonEntry], partial: &str, limit: usize) -> Vec<String> {
+    let lower = partial.trim().to_lowercase();
+
+    let by_name = catalog
+        .iter()
+        .filter(|d| d.name.to_lowercase().contains(&lower));
+    let by_other = catalog.iter().filter(|d| {
+        !d.name.to_lowercase().contains(&lower)
+            && (d.label().to_lowercase().contains(&lower)
+                || d.category.to_lowercase().contains(&lower))
+    });
+
+    by_name
+        .chain(by_other)
+        .map(DungeonEntry::label)
+        .take(limit)
+        .collect()
+}
+
+/// Fetches one batch of teams; `page` starts at 1.
+pub async fn get_dungeon_stats(dungeon_id: u32, page: u32) -> Result<Vec<DungeonTeamData>> {
     let client = Client::new();
 
     let resp = client
@@ -17,6 +91,7 @@ pub async fn get_dungeon_stats(dungeon_id: u32) -> Result<Vec<DungeonTeamData>>
         .query(&[
             ("dungeon_id", dungeon_id.to_string()),
             ("sort_by", "rank_score".to_string()),
+            ("page", page.to_string()),
         ])
         .header("Accept", "application/json")
         .header("User-Agent", "Mozilla/5.0")
@@ -35,20 +110,58 @@ pub async fn get_dungeon_stats(dungeon_id: u32) -> Result<Vec<DungeonTeamData>>
 }
 
 // Crée un embed pour afficher les meilleures équipes PvE
+// `first_team` : numéro de la première équipe de la page (1, 6, 11, ...)
+#[allow(clippy::too_many_arguments)]
 pub async fn create_pve_teams_embed(
-    dungeon_name: &str,
-    dungeon_slug: &str,
+    dungeon: &DungeonEntry,
+    view: &PveTeamsView,
     teams: &[DungeonTeamData],
+    first_team: usize,
+    page: usize,
     collection: &Collection<Document>,
     monster_name_map: &HashMap<String, String>,
 ) -> CreateEmbed {
     let thumbnail = "https://raw.githubusercontent.com/B4tiste/landing-page-bot/refs/heads/main/src/assets/images/old_bot_logo.gif";
+    let dungeon_name = dungeon.label();
 
     let mut embed = CreateEmbed::default()
-        .title(format!("🏆 Best PvE Teams - {}", dungeon_name))
+        .title(if view.from_box {
+            format!("🏆 Best PvE Teams - {} (your box)", dungeon_name)
+        } else {
+            format!("🏆 Best PvE Teams - {}", dungeon_name)
+        })
         .color(serenity::Colour::from_rgb(0, 255, 127))
         .thumbnail(thumbnail)
-        .footer(CreateEmbedFooter::new("Data is gathered from swcalc.cz"));
+        .footer(CreateEmbedFooter::new(format!(
+            "Page {} · Data is gathered from swcalc.cz",
+            page
+        )));
+
+    // Tri et filtre choisis, rappelés en tête de l'embed
+    let mut options = Vec::new();
+    if view.sort != PveTeamSort::Score {
+        options.push(format!("Sorted by **{}**", view.sort.name()));
+    }
+    if let Some(min) = view.min_success_rate {
+        options.push(format!("Success rate ≥ **{:.0}** %", min));
+    }
+    let mut description = options.join(" · ");
+
+    if teams.is_empty() {
+        if !description.is_empty() {
+            description.push('\n');
+        }
+        description.push_str(if view.from_box {
+            "No listed team can be fielded with your box, even with one unit missing."
+        } else if view.min_success_rate.is_some() {
+            "No listed team reaches this success rate."
+        } else {
+            "No team listed on swcalc.cz for this dungeon."
+        });
+    }
+    if !description.is_empty() {
+        embed = embed.description(description);
+    }
 
     for (i, team) in teams.iter().enumerate() {
         let mut monsters_line = String::new();
@@ -83,7 +196,7 @@ pub async fn create_pve_teams_embed(
 
         let avg_str = format_duration(team.average_time_ms);
 
-        let value = format!(
+        let mut value = format!(
             "**Monsters :** {}\n\
              Success rate and average time : **{:.2}** %, **{}**\n\
              Score : {:.2}\n\
@@ -95,14 +208,26 @@ pub async fn create_pve_teams_embed(
             team.id,
         );
 
-        embed = embed.field(format!("Team {}", i + 1), value, false);
+        if !team.missing_members.is_empty() {
+            value.push_str(&format!(
+                "\n⚠️ Missing {} unit : **{}**",
+                team.missing_members.len(),
+                team.missing_members.join(", ")
+            ));
+        }
+
+        embed = embed.field(format!("Team {}", first_team + i), value, false);
     }
 
+    let other_teams_url = match &dungeon.slug {
+        Some(slug) => format!("https://swcalc.cz/dungeons/{}", slug),
+        None => "https://swcalc.cz/dungeons".to_string(),
+    };
     embed = embed.field(
         "Other teams",
         format!(
-            "[Click here to check other teams for **{}**](https://swcalc.cz/dungeons/{})",
-            dungeon_name, dungeon_slug
+            "[Click here to check other teams for **{}**]({})",
+            dungeon_name, other_teams_url
         ),
         true,
     );
@@ -116,6 +241,23 @@ pub async fn create_pve_teams_embed(
     embed
 }
 
+pub fn create_pve_pagination_buttons(
+    page: usize,
+    has_next: bool,
+    disabled: bool,
+) -> serenity::CreateActionRow {
+    serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new("pve_previous_page")
+            .label("⬅️ Previous")
+            .style(serenity::ButtonStyle::Primary)
+            .disabled(disabled || page <= 1),
+        serenity::CreateButton::new("pve_next_page")
+            .label("➡️ Next")
+            .style(serenity::ButtonStyle::Primary)
+            .disabled(disabled || !has_next),
+    ])
+}
+
 // Convertit ms -> mm:ss.mmm (ex: 01:12.345)
 fn format_duration(ms: u32) -> String {
     let total_seconds = ms / 1000;
@@ -140,13 +282,16 @@ pub async fn get_emoji_from_img_id(
     Some(format!("<:{}:{}>", emoji_name, emoji_id))
 }
 
-/// Construit une map: "0003_0_1" => "Forest Keeper - Fire"
-pub fn build_monster_name_map() -> HashMap<String, String> {
+fn bundled_monsters() -> MonstersFile {
     // adapte le chemin à ton projet (ex: assets/monsters_elements.json)
     let raw = include_str!("../../../monsters_elements.json");
 
-    let parsed: MonstersFile =
-        serde_json::from_str(raw).expect("Failed to parse monsters_elements.json");
+    serde_json::from_str(raw).expect("Failed to parse monsters_elements.json")
+}
+
+/// Construit une map: "0003_0_1" => "Forest Keeper - Fire"
+pub fn build_monster_name_map() -> HashMap<String, String> {
+    let parsed = bundled_monsters();
 
     let mut map = HashMap::with_capacity(parsed.monsters.len());
 
@@ -160,6 +305,80 @@ pub fn build_monster_name_map() -> HashMap<String, String> {
     map
 }
 
+/// Construit une map: "0003_0_1" => com2us_id (après remap, comme les box)
+pub fn build_core_id_map() -> HashMap<String, u32> {
+    bundled_monsters()
+        .monsters
+        .into_iter()
+        .filter_map(|m| {
+            Some((
+                core_id_from_image_filename(&m.image_filename)?,
+                remap_monster_id(m.com2us_id as i32) as u32,
+            ))
+        })
+        .collect()
+}
+
+/// Sorts teams by the chosen criterion, ties broken by swcalc.cz score.
+pub fn sort_teams(teams: &mut [DungeonTeamData], sort: PveTeamSort) {
+    // Ordre total : les NaN passent toujours en dernier
+    fn nan_last(a: f64, b: f64, descending: bool) -> Ordering {
+        match (a.is_nan(), b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            _ if descending => b.total_cmp(&a),
+            _ => a.total_cmp(&b),
+        }
+    }
+
+    teams.sort_by(|a, b| {
+        let primary = match sort {
+            PveTeamSort::Score => Ordering::Equal,
+            PveTeamSort::Fastest => nan_last(a.mean_time_ms, b.mean_time_ms, false),
+            PveTeamSort::SuccessRate => nan_last(a.success_rate, b.success_rate, true),
+        };
+        primary.then_with(|| nan_last(a.rank, b.rank, true))
+    });
+}
+
+/// Best teams the box can field, then up to `near_n` teams missing a single
+/// unit (listed in `missing_members`). `teams` must already be sorted.
+pub fn filter_teams_for_box(
+    teams: Vec<DungeonTeamData>,
+    owned_ids: &HashSet<u32>,
+    core_ids: &HashMap<String, u32>,
+    monster_name_map: &HashMap<String, String>,
+    top_n: usize,
+    near_n: usize,
+) -> Vec<DungeonTeamData> {
+    let mut feasible = Vec::new();
+    let mut near = Vec::new();
+    for mut team in teams {
+        // Membre inconnu de monsters.json = considéré comme manquant
+        team.missing_members = team
+            .members
+            .iter()
+            .filter(|core| !core_ids.get(*core).is_some_and(|id| owned_ids.contains(id)))
+            .map(|core| {
+                monster_name_map
+                    .get(core)
+                    .cloned()
+                    .unwrap_or_else(|| core.clone())
+            })
+            .collect();
+
+        match team.missing_members.len() {
+            0 if feasible.len() < top_n => feasible.push(team),
+            1 if near.len() < near_n => near.push(team),
+            _ => {}
+        }
+    }
+
+    feasible.extend(near);
+    feasible
+}
+
 /// "unit_icon_0003_0_1.png" -> Some("0003_0_1")
 fn core_id_from_image_filename(filename: &str) -> Option<String> {
     // Tolérant (png/jpg/…)
@@ -167,3 +386,152 @@ fn core_id_from_image_filename(filename: &str) -> Option<String> {
     let core = base.split('.').next()?; // "0003_0_1"
     Some(core.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: u32, name: &str, category: &str, stage: Option<&str>) -> DungeonEntry {
+        DungeonEntry {
+            id,
+            name: name.to_string(),
+            category: category.to_string(),
+            stage: stage.map(str::to_string),
+            slug: None,
+        }
+    }
+
+    fn catalog() -> Vec<DungeonEntry> {
+        vec![
+            entry(1, "Giant's Keep", "Cairos Dungeon", Some("B10")),
+            entry(2, "Giant's Keep", "Cairos Dungeon", Some("B12")),
+            entry(3, "Dragon's Lair", "Cairos Dungeon", Some("B12")),
+            entry(4, "Fire Beast", "Rift Beast", None),
+        ]
+    }
+
+    #[test]
+    fn dungeon_name_lists_every_stage() {
+        assert_eq!(
+            search_dungeons(&catalog(), "giant", 25),
+            vec!["Giant's Keep (B10)", "Giant's Keep (B12)"]
+        );
+        assert_eq!(
+            search_dungeons(&catalog(), "b12", 25),
+            vec!["Giant's Keep (B12)", "Dragon's Lair (B12)"]
+        );
+        assert_eq!(search_dungeons(&catalog(), "", 2).len(), 2);
+    }
+
+    #[test]
+    fn finds_dungeon_by_label_or_id() {
+        let catalog = catalog();
+        assert_eq!(
+            find_dungeon(&catalog, "giant's keep (b12)").map(|d| d.id),
+            Some(2)
+        );
+        assert_eq!(find_dungeon(&catalog, "3").map(|d| d.id), Some(3));
+        assert_eq!(
+            find_dungeon(&catalog, "9999").map(|d| d.name),
+            Some("Dungeon #9999".to_string())
+        );
+        assert!(find_dungeon(&catalog, "Giant's Keep").is_none());
+    }
+
+    fn team(id: u32, rank: f64, success_rate: f64, mean_time_ms: f64) -> DungeonTeamData {
+        DungeonTeamData {
+            id,
+            members: Vec::new(),
+            rank,
+            success_rate,
+            mean_time_ms,
+            average_time_ms: 0,
+            success_rate_pct: 0.0,
+            missing_members: Vec::new(),
+        }
+    }
+
+    fn sorted_ids(mut teams: Vec<DungeonTeamData>, sort: PveTeamSort) -> Vec<u32> {
+        sort_teams(&mut teams, sort);
+        teams.iter().map(|t| t.id).collect()
+    }
+
+    #[test]
+    fn sorts_by_score() {
+        let teams = vec![
+            team(1, 50.0, 0.9, 60_000.0),
+            team(2, f64::NAN, 1.0, 30_000.0),
+            team(3, 80.0, 0.5, 90_000.0),
+        ];
+        assert_eq!(sorted_ids(teams, PveTeamSort::Score), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn sorts_by_time_then_score() {
+        let teams = vec![
+            team(1, 50.0, 0.9, 60_000.0),
+            team(2, 90.0, 0.9, f64::NAN),
+            team(3, 70.0, 0.5, 45_000.0),
+            team(4, 80.0, 0.7, 60_000.0),
+        ];
+        assert_eq!(sorted_ids(teams, PveTeamSort::Fastest), vec![3, 4, 1, 2]);
+    }
+
+    #[test]
+    fn sorts_by_success_rate_then_score() {
+        let teams = vec![
+            team(1, 50.0, 0.95, 60_000.0),
+            team(2, 90.0, f64::NAN, 30_000.0),
+            team(3, 70.0, 1.0, 45_000.0),
+            team(4, 80.0, 0.95, 60_000.0),
+        ];
+        assert_eq!(
+            sorted_ids(teams, PveTeamSort::SuccessRate),
+            vec![3, 4, 1, 2]
+        );
+    }
+
+    #[test]
+    fn splits_feasible_and_missing_one_teams() {
+        let with_members = |id: u32, members: &[&str]| DungeonTeamData {
+            members: members.iter().map(|m| m.to_string()).collect(),
+            ..team(id, 0.0, 1.0, 0.0)
+        };
+        let teams = vec![
+            with_members(1, &["a", "b"]),
+            with_members(2, &["a", "c"]),
+            with_members(3, &["c", "d"]),
+            with_members(4, &["b", "a"]),
+            with_members(5, &["a", "x"]),
+            with_members(6, &["a", "d"]),
+        ];
+        let core_ids: HashMap<String, u32> = [("a", 10), ("b", 20), ("c", 30), ("d", 40)]
+            .into_iter()
+            .map(|(core, id)| (core.to_string(), id))
+            .collect();
+        let names = HashMap::from([("c".to_string(), "Veromos".to_string())]);
+        let owned = HashSet::from([10, 20]);
+
+        let kept = filter_teams_for_box(teams, &owned, &core_ids, &names, 1, 2);
+
+        // 1 équipe faisable (limite top_n), puis 2 à un monstre près, 3 ignorée
+        let ids: Vec<u32> = kept.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![1, 2, 5]);
+        assert!(kept[0].missing_members.is_empty());
+        assert_eq!(kept[1].missing_members, vec!["Veromos"]);
+        // Monstre absent de monsters.json : manquant, affiché par son id
+        assert_eq!(kept[2].missing_members, vec!["x"]);
+    }
+
+    #[test]
+    fn bundled_catalog_loads() {
+        let dungeons = load_dungeon_catalog(DUNGEON_CATALOG_PATH).unwrap();
+        let giants = find_dungeon(&dungeons, "Giant's Keep").unwrap();
+        assert_eq!(giants.id, 8011);
+        assert_eq!(giants.slug.as_deref(), Some("giants-keep"));
+
+        // Ids uniques, sinon find_dungeon renverrait la mauvaise entrée
+        let ids: HashSet<u32> = dungeons.iter().map(|d| d.id).collect();
+        assert_eq!(ids.len(), dungeons.len());
+    }
+}
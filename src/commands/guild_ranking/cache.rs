@@ -0,0 +1,106 @@
+//! Snapshot des comptes liés (`user-links`), rafraîchi en tâche de fond
+//! pour que `/guild_ranking` ne fasse aucun appel lucksack.
+use anyhow::Result;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use mongodb::bson::doc;
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
+
+use crate::commands::player_stats::utils::{
+    get_lucksack_player_summary, get_lucksack_season_numbers,
+};
+use crate::commands::register::utils::get_user_links_collection;
+
+const REFRESH_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Clone)]
+pub struct RosterEntry {
+    pub discord_user_id: u64,
+    pub player_id: i64,
+    pub username: String,
+    pub country: String,
+    pub score: i32,
+    pub rank: i64,
+    pub bracket: i32,
+    /// Serveurs où l'utilisateur a fait `/register`
+    pub guild_ids: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RosterSnapshot {
+    /// Triés par score décroissant
+    pub entries: Vec<RosterEntry>,
+    pub season: i32,
+    pub refreshed_at: i64,
+}
+
+static ROSTER: Lazy<RwLock<RosterSnapshot>> = Lazy::new(|| RwLock::new(RosterSnapshot::default()));
+
+pub fn roster_snapshot() -> RosterSnapshot {
+    ROSTER.read().map(|r| r.clone()).unwrap_or_default()
+}
+
+/// Fetches the current summary of every linked account and replaces the
+/// snapshot. Accounts whose summary fails are skipped.
+pub async fn refresh_roster() -> Result<usize> {
+    let season = get_lucksack_season_numbers()
+        .await?
+        .first()
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("No lucksack season found"))?;
+
+    let links: Vec<(u64, i64, Vec<u64>)> = get_user_links_collection()
+        .await?
+        .find(doc! {})
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .filter_map(|d| {
+            let discord_user_id = d.get_i64("discord_user_id").ok()? as u64;
+            let player_id = d.get_i64("swrt_player_id").ok()?;
+            let guild_ids = d
+                .get_array("guild_ids")
+                .map(|ids| {
+                    ids.iter()
+                        .filter_map(|id| id.as_i64())
+                        .map(|id| id as u64)
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some((discord_user_id, player_id, guild_ids))
+        })
+        .collect();
+
+    let mut entries: Vec<RosterEntry> = stream::iter(links)
+        .map(|(discord_user_id, player_id, guild_ids)| async move {
+            let summary = get_lucksack_player_summary(player_id, season).await.ok()?;
+            Some(RosterEntry {
+                discord_user_id,
+                player_id,
+                username: summary.user_info.username,
+                country: summary.user_info.country,
+                score: summary.summary.current_score,
+                rank: summary.summary.current_rank,
+                bracket: summary.summary.current_rank_bracket,
+                guild_ids,
+            })
+        })
+        .buffer_unordered(REFRESH_CONCURRENCY)
+        .filter_map(futures::future::ready)
+        .collect()
+        .await;
+
+    entries.sort_by(|a, b| b.score.cmp(&a.score).then(a.rank.cmp(&b.rank)));
+    let count = entries.len();
+
+    if let Ok(mut roster) = ROSTER.write() {
+        *roster = RosterSnapshot {
+            entries,
+            season,
+            refreshed_at: chrono::Utc::now().timestamp(),
+        };
+    }
+
+    Ok(count)
+}
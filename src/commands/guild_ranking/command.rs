@@ -0,0 +1,139 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::Error;
+
+use crate::commands::guild_ranking::cache::roster_snapshot;
+use crate::commands::guild_ranking::utils::{
+    create_guild_ranking_buttons, create_guild_ranking_embed, filter_guild_members,
+    GUILD_RANKING_PAGE_SIZE,
+};
+use crate::commands::shared::embed_error_handling::{
+    create_embed_error, schedule_message_deletion,
+};
+use crate::commands::shared::logs::{get_server_name, send_log};
+use crate::commands::shared::models::LoggerDocument;
+use crate::Data;
+
+/// 📂 RTA ranking of the registered members of this server
+///
+/// Usage: /guild_ranking
+#[poise::command(slash_command, guild_only)]
+pub async fn guild_ranking(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let result = show_guild_ranking(&ctx).await;
+
+    send_log(LoggerDocument::new(
+        &ctx.author().name,
+        "guild_ranking",
+        &get_server_name(&ctx).await?,
+        result.is_ok(),
+        chrono::Utc::now().timestamp(),
+    ))
+    .await?;
+
+    result
+}
+
+async fn show_guild_ranking(ctx: &poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    let snapshot = roster_snapshot();
+    if snapshot.refreshed_at == 0 {
+        let reply = ctx
+            .send(create_embed_error(
+                "⏳ The ranking is still being computed, please try again in a few minutes.",
+            ))
+            .await?;
+        schedule_message_deletion(reply, *ctx).await?;
+        return Ok(());
+    }
+
+    let entries = filter_guild_members(guild_id, &snapshot);
+    let guild_name = get_server_name(ctx).await?;
+    let last_page = entries.len().div_ceil(GUILD_RANKING_PAGE_SIZE).max(1);
+    let mut page = 1usize;
+
+    let reply = ctx
+        .send(CreateReply {
+            embeds: vec![create_guild_ranking_embed(
+                &guild_name,
+                &entries,
+                &snapshot,
+                page,
+            )],
+            components: if last_page > 1 {
+                Some(vec![create_guild_ranking_buttons(
+                    page,
+                    entries.len(),
+                    false,
+                )])
+            } else {
+                None
+            },
+            ..Default::default()
+        })
+        .await?;
+
+    if last_page <= 1 {
+        return Ok(());
+    }
+
+    let message_id = reply.message().await?.id;
+    let channel_id = ctx.channel_id();
+    let user_id = ctx.author().id;
+
+    while let Some(interaction) =
+        serenity::ComponentInteractionCollector::new(&ctx.serenity_context.shard)
+            .channel_id(channel_id)
+            .message_id(message_id)
+            .filter(move |i| i.user.id == user_id)
+            .timeout(std::time::Duration::from_secs(600))
+            .await
+    {
+        match interaction.data.custom_id.as_str() {
+            "guild_ranking_previous_page" if page > 1 => page -= 1,
+            "guild_ranking_next_page" if page < last_page => page += 1,
+            _ => continue,
+        }
+
+        interaction
+            .create_response(
+                &ctx.serenity_context,
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .add_embed(create_guild_ranking_embed(
+                            &guild_name,
+                            &entries,
+                            &snapshot,
+                            page,
+                        ))
+                        .components(vec![create_guild_ranking_buttons(
+                            page,
+                            entries.len(),
+                            false,
+                        )]),
+                ),
+            )
+            .await?;
+    }
+
+    // Disable buttons after timeout
+    let _ = reply
+        .edit(
+            poise::Context::Application(*ctx),
+            CreateReply {
+                components: Some(vec![create_guild_ranking_buttons(
+                    page,
+                    entries.len(),
+                    true,
+                )]),
+                ..Default::default()
+            },
+        )
+        .await;
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use poise::serenity_prelude as serenity;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+
+use crate::commands::guild_ranking::cache::{RosterEntry, RosterSnapshot};
+use crate::commands::player_stats::utils::get_rank_emojis_for_bracket;
+use crate::commands::shared::player_alias::player_alias;
+
+pub const GUILD_RANKING_PAGE_SIZE: usize = 15;
+
+/// Keeps the roster entries registered from `guild_id`, without any Discord call.
+pub fn filter_guild_members(
+    guild_id: serenity::GuildId,
+    snapshot: &RosterSnapshot,
+) -> Vec<RosterEntry> {
+    snapshot
+        .entries
+        .iter()
+        .filter(|entry| entry.guild_ids.contains(&guild_id.get()))
+        .cloned()
+        .collect()
+}
+
+pub fn create_guild_ranking_embed(
+    guild_name: &str,
+    entries: &[RosterEntry],
+    snapshot: &RosterSnapshot,
+    page: usize,
+) -> CreateEmbed {
+    let last_page = entries.len().div_ceil(GUILD_RANKING_PAGE_SIZE).max(1);
+    let start = (page - 1) * GUILD_RANKING_PAGE_SIZE;
+
+    let description = if entries.is_empty() {
+        "No registered member yet. Link your account with `/register <account name>`.".to_string()
+    } else {
+        entries
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(GUILD_RANKING_PAGE_SIZE)
+            .map(|(i, e)| {
+                let alias_str = player_alias(e.player_id)
+                    .map(|alias| format!(" aka **{}**", alias))
+                    .unwrap_or_default();
+                format!(
+                    "{}. :flag_{}: <@{}> - `{}`{} - **{}** (#{}) {}",
+                    i + 1,
+                    e.country.to_lowercase(),
+                    e.discord_user_id,
+                    e.username,
+                    alias_str,
+                    e.score,
+                    e.rank,
+                    get_rank_emojis_for_bracket(e.bracket)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    CreateEmbed::default()
+        .title(format!(
+            "🏰 {} - Season {} RTA Ranking",
+            guild_name, snapshot.season
+        ))
+        .description(description)
+        .field("Registered members", entries.len().to_string(), true)
+        .field("Page", format!("{}/{}", page, last_page), true)
+        .field(
+            "Last update",
+            format!("<t:{}:R>", snapshot.refreshed_at),
+            true,
+        )
+        .footer(CreateEmbedFooter::new(
+            "Data is gathered from lucksack.gg • Use /register to appear here",
+        ))
+        .color(serenity::Colour::from_rgb(0, 255, 0))
+}
+
+pub fn create_guild_ranking_buttons(
+    page: usize,
+    total: usize,
+    disabled: bool,
+) -> serenity::CreateActionRow {
+    let last_page = total.div_ceil(GUILD_RANKING_PAGE_SIZE).max(1);
+
+    let previous_button = serenity::CreateButton::new("guild_ranking_previous_page")
+        .label("⬅️ Previous")
+        .style(serenity::ButtonStyle::Primary)
+        .disabled(disabled || page <= 1);
+
+    let next_button = serenity::CreateButton::new("guild_ranking_next_page")
+        .label("➡️ Next")
+        .style(serenity::ButtonStyle::Primary)
+        .disabled(disabled || page >= last_page);
+
+    serenity::CreateActionRow::Buttons(vec![previous_button, next_button])
+}
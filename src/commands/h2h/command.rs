@@ -0,0 +1,199 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::builder::{EditAttachments, EditMessage};
+use serenity::Error;
+
+use crate::commands::h2h::utils::{
+    create_h2h_embed, create_loading_h2h_embed, filter_head_to_head,
+};
+use crate::commands::player_analysis::utils::analyze_matches;
+use crate::commands::player_stats::command::resolve_player_id;
+use crate::commands::player_stats::utils::{
+    create_lucksack_replay_image, get_all_lucksack_player_matches, get_lucksack_player_summary,
+    get_lucksack_season_numbers,
+};
+use crate::commands::shared::embed_error_handling::{
+    create_embed_error, schedule_message_deletion,
+};
+use crate::commands::shared::logs::{get_server_name, send_log};
+use crate::commands::shared::models::{LoggerDocument, ReplayDisplay};
+use crate::Data;
+
+/// Limite de matchs parcourus dans l'historique du joueur le moins actif
+const MAX_SCANNED_MATCHES: usize = 1000;
+
+/// 📂 Head-to-head record between two players this season
+///
+/// Usage: /h2h
+#[poise::command(slash_command)]
+pub async fn h2h(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Player name, alias or @mention"] player: String,
+    #[description = "Opponent name, alias or @mention"] opponent: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let Some(first) = resolve_player_id(&ctx, &player, "h2h").await? else {
+        return Ok(());
+    };
+    let Some(second) = resolve_player_id(&ctx, &opponent, "h2h").await? else {
+        return Ok(());
+    };
+
+    // Un seul message reste affiché si les deux joueurs sont passés par le menu
+    let reply_handle = match (first.reply_handle, second.reply_handle) {
+        (Some(a), Some(b)) => {
+            let _ = a.delete(poise::Context::Application(ctx)).await;
+            Some(b)
+        }
+        (a, b) => b.or(a),
+    };
+
+    let result = show_h2h(&ctx, first.player_id, second.player_id, reply_handle).await;
+
+    send_log(LoggerDocument::new(
+        &ctx.author().name,
+        "h2h",
+        &get_server_name(&ctx).await?,
+        result.is_ok(),
+        chrono::Utc::now().timestamp(),
+    ))
+    .await?;
+
+    result
+}
+
+async fn show_h2h<'a>(
+    ctx: &'a poise::ApplicationContext<'a, Data, Error>,
+    player_id: i64,
+    opponent_id: i64,
+    existing_reply: Option<poise::ReplyHandle<'a>>,
+) -> Result<(), Error> {
+    if player_id == opponent_id {
+        let reply = ctx
+            .send(create_embed_error("❌ Please pick two different players."))
+            .await?;
+        schedule_message_deletion(reply, *ctx).await?;
+        return Ok(());
+    }
+
+    let season = match get_lucksack_season_numbers().await {
+        Ok(seasons) if !seasons.is_empty() => seasons[0],
+        Ok(_) => {
+            let reply = ctx
+                .send(create_embed_error("❌ No valid season number found."))
+                .await?;
+            schedule_message_deletion(reply, *ctx).await?;
+            return Ok(());
+        }
+        Err(e) => {
+            let msg = format!("❌ Failed to fetch seasons: {}", e);
+            let reply = ctx.send(create_embed_error(&msg)).await?;
+            schedule_message_deletion(reply, *ctx).await?;
+            return Ok(());
+        }
+    };
+
+    let (player_res, opponent_res) = tokio::join!(
+        get_lucksack_player_summary(player_id, season),
+        get_lucksack_player_summary(opponent_id, season),
+    );
+    let to_error = |e: anyhow::Error| {
+        Error::from(std::io::Error::other(format!(
+            "Error retrieving player summary: {}",
+            e
+        )))
+    };
+    let player = player_res.map_err(to_error)?;
+    let opponent = opponent_res.map_err(to_error)?;
+
+    let loading = CreateReply {
+        content: Some(String::new()),
+        embeds: vec![create_loading_h2h_embed(
+            &player.user_info.username,
+            &opponent.user_info.username,
+        )],
+        components: Some(vec![]),
+        ..Default::default()
+    };
+
+    let reply_handle = match existing_reply {
+        Some(handle) => {
+            handle
+                .edit(poise::Context::Application(*ctx), loading)
+                .await?;
+            handle
+        }
+        None => ctx.send(loading).await?,
+    };
+
+    // On parcourt l'historique le plus court, puis on remet les matchs du point de vue du joueur
+    let player_total = player.summary.total_matches.max(0) as usize;
+    let opponent_total = opponent.summary.total_matches.max(0) as usize;
+    let (scanned_id, scanned_total, other_name, swap) = if opponent_total < player_total {
+        (
+            opponent_id,
+            opponent_total,
+            &player.user_info.username,
+            true,
+        )
+    } else {
+        (player_id, player_total, &opponent.user_info.username, false)
+    };
+
+    let matches =
+        get_all_lucksack_player_matches(scanned_id, season, scanned_total, MAX_SCANNED_MATCHES)
+            .await
+            .map_err(|e| {
+                Error::from(std::io::Error::other(format!(
+                    "Error retrieving matches: {}",
+                    e
+                )))
+            })?;
+
+    let encounters = filter_head_to_head(matches, other_name, swap);
+    let analysis = analyze_matches(&encounters);
+
+    let last_encounter = encounters.first().map(|m| {
+        chrono::DateTime::parse_from_rfc3339(&m.battle_time)
+            .map(|dt| dt.format("%d/%m/%Y").to_string())
+            .unwrap_or_else(|_| m.battle_time.clone())
+    });
+
+    let attachment = if encounters.is_empty() {
+        None
+    } else {
+        match create_lucksack_replay_image(&encounters, ReplayDisplay::Grid).await {
+            Ok(path) => serenity::CreateAttachment::path(path).await.ok(),
+            Err(_) => None,
+        }
+    };
+
+    let embed = create_h2h_embed(
+        &player,
+        &opponent,
+        &analysis,
+        last_encounter.as_deref(),
+        season,
+        attachment.as_ref().map(|a| a.filename.as_str()),
+    )
+    .await;
+
+    let mut attachments = EditAttachments::new();
+    if let Some(attachment) = attachment {
+        attachments = attachments.add(attachment);
+    }
+
+    let mut message = reply_handle.message().await?.into_owned();
+    message
+        .edit(
+            &ctx.serenity_context.http,
+            EditMessage::new()
+                .content("")
+                .embeds(vec![embed])
+                .attachments(attachments),
+        )
+        .await?;
+
+    Ok(())
+}
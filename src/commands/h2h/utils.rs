@@ -0,0 +1,155 @@
+use poise::serenity_prelude as serenity;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+
+use crate::commands::player_analysis::models::{MatchupAnalysis, MonsterRecord};
+use crate::commands::player_analysis::utils::{format_counts, format_pick_record, format_records};
+use crate::commands::player_stats::utils::{
+    get_mob_emoji_collection, LucksackMatch, LucksackPlayerSummary,
+};
+use crate::commands::shared::player_alias::player_alias;
+
+/// Keeps the matches played against `opponent_name` (lucksack only gives the
+/// opponent's in-game name). With `swap`, the matches were fetched from the
+/// opponent's history and are flipped back to the player's side.
+pub fn filter_head_to_head(
+    matches: Vec<LucksackMatch>,
+    opponent_name: &str,
+    swap: bool,
+) -> Vec<LucksackMatch> {
+    matches
+        .into_iter()
+        .filter(|m| m.opponent_username.eq_ignore_ascii_case(opponent_name))
+        .map(|m| if swap { m.swapped() } else { m })
+        .collect()
+}
+
+fn display_name(summary: &LucksackPlayerSummary) -> String {
+    let info = &summary.user_info;
+    match player_alias(info.player_id) {
+        Some(alias) => format!("{} (aka. {})", info.username, alias),
+        None => info.username.clone(),
+    }
+}
+
+/// Monstres adverses vus du côté de l'adversaire : on inverse victoires / défaites
+fn opponent_side(records: &[MonsterRecord]) -> Vec<MonsterRecord> {
+    records
+        .iter()
+        .map(|r| MonsterRecord {
+            monster_id: r.monster_id,
+            played: r.played,
+            wins: r.played - r.wins,
+        })
+        .collect()
+}
+
+pub async fn create_h2h_embed(
+    player: &LucksackPlayerSummary,
+    opponent: &LucksackPlayerSummary,
+    analysis: &MatchupAnalysis,
+    last_encounter: Option<&str>,
+    season: i32,
+    replay_attachment: Option<&str>,
+) -> CreateEmbed {
+    let collection = get_mob_emoji_collection().await.ok();
+    let collection = collection.as_ref();
+
+    let player_name = display_name(player);
+    let opponent_name = display_name(opponent);
+
+    let mut embed = CreateEmbed::default()
+        .title(format!(
+            "⚔️ {} vs {} - Season {}",
+            player.user_info.username, opponent.user_info.username, season
+        ))
+        .color(serenity::Colour::from_rgb(0, 180, 255))
+        .footer(CreateEmbedFooter::new(
+            "Data is gathered from lucksack.gg • Matches are found by in-game name",
+        ));
+
+    if analysis.total.played == 0 {
+        return embed.description(format!(
+            "No match found between **{}** and **{}** this season.",
+            player_name, opponent_name
+        ));
+    }
+
+    let wins = analysis.total.wins;
+    let losses = analysis.total.played - wins;
+    embed = embed.description(format!(
+        "**{}** {} - {} **{}**\n{} encounter(s){}",
+        player_name,
+        wins,
+        losses,
+        opponent_name,
+        analysis.total.played,
+        last_encounter
+            .map(|t| format!(" • last one on {}", t))
+            .unwrap_or_default()
+    ));
+
+    let opponent_monsters = opponent_side(&analysis.opponent_monsters);
+    let player_top: Vec<&MonsterRecord> = analysis.own_monsters.iter().take(6).collect();
+    let opponent_top: Vec<&MonsterRecord> = opponent_monsters.iter().take(6).collect();
+
+    embed = embed
+        .field(
+            format!("{} - First Pick", player.user_info.username),
+            format_pick_record(&analysis.first_pick),
+            true,
+        )
+        .field(
+            format!("{} - Second Pick", player.user_info.username),
+            format_pick_record(&analysis.second_pick),
+            true,
+        )
+        .field("\u{200B}", "\u{200B}", true)
+        .field(
+            format!("🛡️ {}'s Monsters", player.user_info.username),
+            format_records(collection, &player_top).await,
+            true,
+        )
+        .field(
+            format!("🛡️ {}'s Monsters", opponent.user_info.username),
+            format_records(collection, &opponent_top).await,
+            true,
+        )
+        .field(
+            format!("🚫 Banned by {}", player.user_info.username),
+            format_counts(
+                collection,
+                &analysis.player_bans[..analysis.player_bans.len().min(5)],
+            )
+            .await,
+            false,
+        )
+        .field(
+            format!("🚫 Banned by {}", opponent.user_info.username),
+            format_counts(
+                collection,
+                &analysis.banned_by_opponents[..analysis.banned_by_opponents.len().min(5)],
+            )
+            .await,
+            false,
+        );
+
+    if let Some(name) = replay_attachment {
+        embed = embed.image(format!("attachment://{}", name));
+    }
+
+    embed
+}
+
+pub fn create_loading_h2h_embed(player_name: &str, opponent_name: &str) -> CreateEmbed {
+    CreateEmbed::default()
+        .title(format!("⚔️ {} vs {}", player_name, opponent_name))
+        .color(serenity::Colour::from_rgb(255, 165, 0))
+        .field(
+            "Status",
+            "<a:loading:1358029412716515418> Looking for encounters...",
+            false,
+        )
+        .footer(CreateEmbedFooter::new(
+            "Please wait while we fetch the season data...",
+        ))
+}
@@ -16,22 +16,31 @@ use serenity::builder::CreateEmbedFooter;
 /// Usage: `/help`
 #[poise::command(slash_command)]
 pub async fn help(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    // One line per command in the description: Discord caps embeds at 25 fields
+    let command_lines: Vec<String> = ctx
+        .framework()
+        .options()
+        .commands
+        .iter()
+        .map(|command| {
+            let description = command
+                .description
+                .clone()
+                .unwrap_or_else(|| "No description available".to_string());
+            format!("**/{}** — {}", command.name, description)
+        })
+        .collect();
+
     // Create the embed that lists the commands
     let mut embed = serenity::CreateEmbed::default()
         .title("Commands")
         .color(serenity::Colour::from_rgb(0, 255, 255))
-        .description("Created by **b4tiste** & **shaakz**");
+        .description(format!(
+            "Created by **b4tiste** & **shaakz**\n\n{}",
+            command_lines.join("\n")
+        ));
     // .thumbnail(thumbnail)
 
-    // Add each command's name and description as an embed field
-    for command in &ctx.framework().options().commands {
-        let description = command
-            .description
-            .clone()
-            .unwrap_or_else(|| "No description available".to_string());
-        embed = embed.field(command.name.clone(), description, true);
-    }
-
     // Additional fields for credits and source code links
     embed = embed.field(
         "Source code & Project Road Map",
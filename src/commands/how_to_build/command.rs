@@ -11,9 +11,10 @@ use crate::commands::shared::logs::send_log;
 use crate::commands::shared::models::LoggerDocument;
 use crate::{Data, LUCKSACK_MONSTER_MAP};
 
+use crate::commands::how_to_build::models::{LucksackBuildResponse, RankComparison};
 use crate::commands::how_to_build::utils::{
-    build_how_to_build_embed, create_lucksack_rank_buttons, fetch_lucksack_build,
-    get_latest_lucksack_season,
+    build_how_to_build_embed, build_rank_comparison_embed, create_lucksack_rank_buttons,
+    fetch_lucksack_builds, get_latest_lucksack_season, COMPARE_VIEW,
 };
 
 const LUCKSACK_IMG_BASE_URL: &str = "https://static.lucksack.gg/images/monsters/";
@@ -50,13 +51,29 @@ pub async fn autocomplete_lucksack_monster<'a>(
     prefix_matches.into_iter().chain(contains_matches).take(10)
 }
 
+/// Un seul rank : vue classique, plusieurs : comparaison côte à côte
+fn build_view_embed(
+    monster_name: &str,
+    season: i32,
+    builds: &[(i32, LucksackBuildResponse)],
+    image_url: Option<String>,
+) -> serenity::CreateEmbed {
+    match builds {
+        [(rank, build)] => build_how_to_build_embed(monster_name, season, *rank, build, image_url),
+        _ => build_rank_comparison_embed(monster_name, season, builds, image_url),
+    }
+}
+
 /// 📂 Shows RTA runes and artifacts data for a given monster
+///
+/// Usage: `/how_to_build <monster_name> [compare]`
 #[poise::command(slash_command)]
 pub async fn how_to_build(
     ctx: poise::ApplicationContext<'_, Data, Error>,
     #[autocomplete = "autocomplete_lucksack_monster"]
     #[description = "Name of the monster"]
     monster_name: String,
+    #[description = "Compare builds across ranks side by side"] compare: Option<RankComparison>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -119,7 +136,9 @@ pub async fn how_to_build(
     // G1-G3 = 102
     // P2-P3 = 103
     // P1 = 11
-    let mut current_rank: i32 = 102;
+    // COMPARE_VIEW = ranks de `comparison` côte à côte
+    let comparison = compare.unwrap_or_default();
+    let mut current_rank: i32 = if compare.is_some() { COMPARE_VIEW } else { 102 };
 
     // IMPORTANT: si collab_id existe, on l'utilise en priorité
     let mut effective_monster_id: i32 = collab_id.unwrap_or(monster_id);
@@ -131,13 +150,23 @@ pub async fn how_to_build(
         }
     }
 
+    let ranks_for = |rank: i32| -> Vec<i32> {
+        if rank == COMPARE_VIEW {
+            comparison.ranks().to_vec()
+        } else {
+            vec![rank]
+        }
+    };
+
     // fetch initial (collab prioritaire), fallback sur monster_id si collab échoue
-    let build = match fetch_lucksack_build(effective_monster_id, season, current_rank).await {
+    let builds = match fetch_lucksack_builds(effective_monster_id, season, &ranks_for(current_rank))
+        .await
+    {
         Ok(data) => data,
         Err(e1) => {
             // fallback seulement si on était en collab
             if collab_id.is_some() && effective_monster_id != monster_id {
-                match fetch_lucksack_build(monster_id, season, current_rank).await {
+                match fetch_lucksack_builds(monster_id, season, &ranks_for(current_rank)).await {
                     Ok(data) => {
                         effective_monster_id = monster_id;
                         image_url = Some(format!("{}{}", LUCKSACK_IMG_BASE_URL, image));
@@ -179,13 +208,7 @@ pub async fn how_to_build(
         }
     };
 
-    let embed = build_how_to_build_embed(
-        &monster_name,
-        season,
-        current_rank,
-        &build,
-        image_url.clone(),
-    );
+    let embed = build_view_embed(&monster_name, season, &builds, image_url.clone());
 
     let reply = ctx
         .send(CreateReply {
@@ -212,6 +235,7 @@ pub async fn how_to_build(
             "rank_g1g2g3" => 102,
             "rank_p2p3" => 103,
             "rank_p1" => 11,
+            "rank_compare" => COMPARE_VIEW,
             _ => continue,
         };
 
@@ -231,55 +255,57 @@ pub async fn how_to_build(
             )
             .await?;
 
-        let build = match fetch_lucksack_build(effective_monster_id, season, current_rank).await {
-            Ok(data) => data,
-            Err(e) => {
-                // ✅ fallback si on était sur collab_id
-                if collab_id.is_some() && effective_monster_id != monster_id {
-                    match fetch_lucksack_build(monster_id, season, current_rank).await {
-                        Ok(data) => {
-                            effective_monster_id = monster_id;
-                            image_url = Some(format!("{}{}", LUCKSACK_IMG_BASE_URL, image));
-                            data
-                        }
-                        Err(_) => {
-                            interaction
-                                .edit_response(
-                                    &ctx.serenity_context.http,
-                                    EditInteractionResponse::new()
-                                        .content(format!("❌ Error fetching data 3: {}", e))
-                                        .components(vec![create_lucksack_rank_buttons(
-                                            current_rank,
-                                            false,
-                                        )])
-                                        .embeds(vec![]),
-                                )
-                                .await?;
-                            continue;
+        let builds =
+            match fetch_lucksack_builds(effective_monster_id, season, &ranks_for(current_rank))
+                .await
+            {
+                Ok(data) => data,
+                Err(e) => {
+                    // ✅ fallback si on était sur collab_id
+                    if collab_id.is_some() && effective_monster_id != monster_id {
+                        match fetch_lucksack_builds(monster_id, season, &ranks_for(current_rank))
+                            .await
+                        {
+                            Ok(data) => {
+                                effective_monster_id = monster_id;
+                                image_url = Some(format!("{}{}", LUCKSACK_IMG_BASE_URL, image));
+                                data
+                            }
+                            Err(_) => {
+                                interaction
+                                    .edit_response(
+                                        &ctx.serenity_context.http,
+                                        EditInteractionResponse::new()
+                                            .content(format!("❌ Error fetching data 3: {}", e))
+                                            .components(vec![create_lucksack_rank_buttons(
+                                                current_rank,
+                                                false,
+                                            )])
+                                            .embeds(vec![]),
+                                    )
+                                    .await?;
+                                continue;
+                            }
                         }
+                    } else {
+                        interaction
+                            .edit_response(
+                                &ctx.serenity_context.http,
+                                EditInteractionResponse::new()
+                                    .content(format!("❌ Error fetching data 4: {}", e))
+                                    .components(vec![create_lucksack_rank_buttons(
+                                        current_rank,
+                                        false,
+                                    )])
+                                    .embeds(vec![]),
+                            )
+                            .await?;
+                        continue;
                     }
-                } else {
-                    interaction
-                        .edit_response(
-                            &ctx.serenity_context.http,
-                            EditInteractionResponse::new()
-                                .content(format!("❌ Error fetching data 4: {}", e))
-                                .components(vec![create_lucksack_rank_buttons(current_rank, false)])
-                                .embeds(vec![]),
-                        )
-                        .await?;
-                    continue;
                 }
-            }
-        };
+            };
 
-        let embed = build_how_to_build_embed(
-            &monster_name,
-            season,
-            current_rank,
-            &build,
-            image_url.clone(),
-        );
+        let embed = build_view_embed(&monster_name, season, &builds, image_url.clone());
 
         interaction
             .edit_response(
@@ -37,3 +37,29 @@ pub struct LucksackArtifactStat {
     pub effect_id: i32,
     pub pickrate: f32,
 }
+
+/// Ranks compared side by side, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, poise::ChoiceParameter)]
+pub enum RankComparison {
+    #[default]
+    #[name = "P1 vs G1-G3 vs G3"]
+    P1G1G3,
+    #[name = "P1 vs P2-P3 vs G3"]
+    P1P2G3,
+    #[name = "P2-P3 vs G1-G3"]
+    P2G1,
+    #[name = "P1 vs G3"]
+    P1G3,
+}
+
+impl RankComparison {
+    /// Lucksack rank ids (see `rank_label`)
+    pub fn ranks(self) -> &'static [i32] {
+        match self {
+            RankComparison::P1G1G3 => &[11, 102, 16],
+            RankComparison::P1P2G3 => &[11, 103, 16],
+            RankComparison::P2G1 => &[103, 102],
+            RankComparison::P1G3 => &[11, 16],
+        }
+    }
+}
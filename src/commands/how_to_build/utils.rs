@@ -2,7 +2,10 @@ use poise::serenity_prelude as serenity;
 use reqwest::Client;
 use serenity::builder::{CreateEmbed, CreateEmbedFooter};
 
-use crate::commands::how_to_build::models::{LucksackBuildResponse, LucksackSeason};
+use crate::commands::how_to_build::models::{
+    LucksackArtifactStat, LucksackBuildResponse, LucksackRuneSet, LucksackSeason, LucksackSlotStats,
+};
+use crate::commands::shared::game_data::{ArtifactEffect, RuneSet, Stat};
 use crate::{GUARDIAN_EMOJI_ID, PUNISHER_EMOJI_ID};
 
 pub async fn get_latest_lucksack_season() -> Result<i32, String> {
@@ -64,114 +67,38 @@ pub async fn fetch_lucksack_build(
         .map_err(|_| "Failed to parse JSON".to_string())
 }
 
+/// Fetches the builds of several ranks concurrently, in the order of `ranks`.
+pub async fn fetch_lucksack_builds(
+    monster_id: i32,
+    season: i32,
+    ranks: &[i32],
+) -> Result<Vec<(i32, LucksackBuildResponse)>, String> {
+    let builds = futures::future::try_join_all(
+        ranks
+            .iter()
+            .map(|&rank| fetch_lucksack_build(monster_id, season, rank)),
+    )
+    .await?;
+
+    Ok(ranks.iter().copied().zip(builds).collect())
+}
+
 // ---------------------------
 // Mapping constants
 // ---------------------------
 fn rune_set_name(id: i32) -> &'static str {
-    match id {
-        1 => "Energy",
-        2 => "Fatal",
-        3 => "Blade",
-        4 => "Rage",
-        5 => "Swift",
-        6 => "Focus",
-        7 => "Guard",
-        8 => "Endure",
-        9 => "Violent",
-        10 => "Will",
-        11 => "Nemesis",
-        12 => "Shield",
-        13 => "Revenge",
-        14 => "Despair",
-        15 => "Vampire",
-        16 => "Destroy",
-        17 => "Fight",
-        18 => "Determination",
-        19 => "Enhance",
-        20 => "Accuracy",
-        21 => "Tolerance",
-        22 => "Intangible",
-        23 => "Seal",
-        _ => "Unknown",
-    }
+    RuneSet::from_lucksack_id(id).map_or("Unknown", RuneSet::name)
 }
 
 fn stat_short(id: i32) -> &'static str {
-    match id {
-        1 => "HP",
-        2 => "HP%",
-        3 => "ATK",
-        4 => "ATK%",
-        5 => "DEF",
-        6 => "DEF%",
-        8 => "SPD",
-        9 => "CR",
-        10 => "CD",
-        11 => "RES",
-        12 => "ACC",
-        _ => "?",
-    }
+    u32::try_from(id)
+        .ok()
+        .and_then(Stat::from_id)
+        .map_or("?", Stat::short_name)
 }
 
 fn artifact_effect_name(id: i32) -> &'static str {
-    match id {
-        // Elemental slot artifacts (200-226)
-        200 => "ATK+ Proportional to Lost HP",
-        201 => "DEF+ Proportional to Lost HP",
-        202 => "SPD+ Proportional to Lost HP",
-        203 => "SPD Under Inability",
-        204 => "ATK Increasing Effect",
-        205 => "DEF Increasing Effect",
-        206 => "SPD Increasing Effect",
-        207 => "CRIT Rate Increasing Effect",
-        208 => "Damage by Counterattack",
-        209 => "Damage by Co-op Attack",
-        210 => "Bomb Damage",
-        211 => "Reflect DMG",
-        212 => "Crushing Hit DMG",
-        213 => "Damage Received Under Inability",
-        214 => "CRIT DMG Received",
-        215 => "Life Drain",
-        216 => "HP when Revived",
-        217 => "ATK Bar when Revived",
-        218 => "Additional DMG by HP%",
-        219 => "Additional DMG by ATK%",
-        220 => "Additional DMG by DEF%",
-        221 => "Additional DMG by SPD",
-        222 => "CRIT DMG+ (Enemy HP High)",
-        223 => "CRIT DMG+ (Enemy HP Low)",
-        224 => "Single-target CRIT DMG",
-        225 => "Counter/Co-op CRIT DMG",
-        226 => "ATK/DEF Increasing Effect",
-
-        // Archetype slot artifacts - Element damage (300-309)
-        300 => "DMG Dealt on Fire",
-        301 => "DMG Dealt on Water",
-        302 => "DMG Dealt on Wind",
-        303 => "DMG Dealt on Light",
-        304 => "DMG Dealt on Dark",
-        305 => "DMG Received from Fire",
-        306 => "DMG Received from Water",
-        307 => "DMG Received from Wind",
-        308 => "DMG Received from Light",
-        309 => "DMG Received from Dark",
-
-        // Archetype slot artifacts - Skill effects (400-411)
-        400 => "[Skill 1] CRIT DMG",
-        401 => "[Skill 2] CRIT DMG",
-        402 => "[Skill 3] CRIT DMG",
-        403 => "[Skill 4] CRIT DMG",
-        404 => "[Skill 1] Recovery",
-        405 => "[Skill 2] Recovery",
-        406 => "[Skill 3] Recovery",
-        407 => "[Skill 1] Accuracy",
-        408 => "[Skill 2] Accuracy",
-        409 => "[Skill 3] Accuracy",
-        410 => "[Skill 3/4] CRIT DMG",
-        411 => "First Attack CRIT DMG",
-
-        _ => "Unknown",
-    }
+    ArtifactEffect::from_id(id).map_or("Unknown", ArtifactEffect::name)
 }
 
 fn rank_label(rank: i32) -> &'static str {
@@ -217,6 +144,28 @@ fn popularity_stars(pickrate: f32, max_pickrate: f32) -> String {
 // ---------------------------
 // Formatters
 // ---------------------------
+fn rune_set_combo_name(s: &LucksackRuneSet) -> String {
+    let primary = rune_set_name(s.primary_set);
+    let secondary = s.secondary_set.map(rune_set_name);
+    let tertiary = s.tertiary_set.map(rune_set_name);
+
+    match (secondary, tertiary) {
+        (Some(b), Some(c)) => format!("{} + {} + {}", primary, b, c),
+        (Some(b), None) => format!("{} + {}", primary, b),
+        (None, Some(c)) => format!("{} + {}", primary, c),
+        (None, None) => primary.to_string(),
+    }
+}
+
+fn slot_combo_name(s: &LucksackSlotStats) -> String {
+    format!(
+        "{} / {} / {}",
+        stat_short(s.slot_two),
+        stat_short(s.slot_four),
+        stat_short(s.slot_six)
+    )
+}
+
 fn format_top_rune_sets(build: &LucksackBuildResponse, top_n: usize) -> String {
     if build.rune_sets.is_empty() {
         return "No rune set data.".to_string();
@@ -232,21 +181,12 @@ fn format_top_rune_sets(build: &LucksackBuildResponse, top_n: usize) -> String {
     // Use the most-picked set as the 5★ reference
     let max_pickrate = sets.first().map(|x| x.pickrate).unwrap_or(0.0);
 
-    sets.into_iter()
-        .take(top_n)
+    let top: Vec<LucksackRuneSet> = sets.into_iter().take(top_n).collect();
+
+    top.iter()
         .enumerate()
         .map(|(i, s)| {
-            let primary = rune_set_name(s.primary_set);
-            let secondary = s.secondary_set.map(rune_set_name);
-            let tertiary = s.tertiary_set.map(rune_set_name);
-
-            let name = match (secondary, tertiary) {
-                (Some(b), Some(c)) => format!("{} + {} + {}", primary, b, c),
-                (Some(b), None) => format!("{} + {}", primary, b),
-                (None, Some(c)) => format!("{} + {}", primary, c),
-                (None, None) => primary.to_string(),
-            };
-
+            let name = rune_set_combo_name(s);
             let stars = popularity_stars(s.pickrate, max_pickrate);
 
             format!(
@@ -261,6 +201,55 @@ fn format_top_rune_sets(build: &LucksackBuildResponse, top_n: usize) -> String {
         .join("\n")
 }
 
+/// Bonus des sets cités dans le top, pour les nouveaux joueurs. Champ séparé
+/// limité à 1024 caractères : les dernières lignes sont coupées si besoin.
+fn format_rune_set_bonuses(build: &LucksackBuildResponse, top_n: usize) -> Option<String> {
+    const FIELD_LIMIT: usize = 1024;
+
+    let mut sets = build.rune_sets.clone();
+    sets.sort_by(|a, b| {
+        b.pickrate
+            .partial_cmp(&a.pickrate)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut listed: Vec<RuneSet> = Vec::new();
+    for set in sets
+        .iter()
+        .take(top_n)
+        .flat_map(|s| [Some(s.primary_set), s.secondary_set, s.tertiary_set])
+        .flatten()
+        .filter_map(RuneSet::from_lucksack_id)
+    {
+        if !listed.contains(&set) {
+            listed.push(set);
+        }
+    }
+
+    let mut result = String::new();
+    for line in listed.iter().map(|set| match set.pieces() {
+        1 => format!("{} *{} : {}*", set.emoji(), set.name(), set.bonus()),
+        pieces => format!(
+            "{} *{} ({}-set) : {}*",
+            set.emoji(),
+            set.name(),
+            pieces,
+            set.bonus()
+        ),
+    }) {
+        // +1 pour le \n
+        if result.len() + line.len() + 1 > FIELD_LIMIT {
+            break;
+        }
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        result.push_str(&line);
+    }
+
+    (!result.is_empty()).then_some(result)
+}
+
 // malus si WR < 50% (score utilisé pour trier)
 fn adjusted_slot_score(pickrate: f32, winrate: f32) -> f32 {
     let base = pickrate * winrate;
@@ -299,11 +288,9 @@ fn format_top_slots(build: &LucksackBuildResponse, top_n: usize) -> String {
             let stars = popularity_stars(s.pickrate, max_pickrate);
 
             format!(
-                "{}. **{} / {} / {}** : {} / {}",
+                "{}. **{}** : {} / {}",
                 i + 1,
-                stat_short(s.slot_two),
-                stat_short(s.slot_four),
-                stat_short(s.slot_six),
+                slot_combo_name(&s),
                 fmt_pct(s.winrate),
                 stars
             )
@@ -312,10 +299,7 @@ fn format_top_slots(build: &LucksackBuildResponse, top_n: usize) -> String {
         .join("\n")
 }
 
-fn format_top_artifacts(
-    items: &[crate::commands::how_to_build::models::LucksackArtifactStat],
-    top_n: usize,
-) -> String {
+fn format_top_artifacts(items: &[LucksackArtifactStat], top_n: usize) -> String {
     if items.is_empty() {
         return "No artifact data.".to_string();
     }
@@ -335,9 +319,11 @@ fn format_top_artifacts(
         .enumerate()
         .map(|(i, a)| {
             let stars = popularity_stars(a.pickrate, max_pickrate);
+            let emoji = ArtifactEffect::from_id(a.effect_id).map_or("", ArtifactEffect::emoji);
             format!(
-                "{}. {} : {}",
+                "{}. {} {} : {}",
                 i + 1,
+                emoji,
                 artifact_effect_name(a.effect_id),
                 stars
             )
@@ -371,7 +357,13 @@ pub fn build_how_to_build_embed(
         ))
         .description("See more detailed stats directly on [lucksack.gg](https://lucksack.gg/)\nData format : Win Rate / Popularity")
         .color(serenity::Colour::from_rgb(120, 153, 255))
-        .field("Top Rune Sets", top_sets, false)
+        .field("Top Rune Sets", top_sets, false);
+
+    if let Some(bonuses) = format_rune_set_bonuses(build, 5) {
+        embed = embed.field("Rune Set Bonuses", bonuses, false);
+    }
+
+    embed = embed
         .field("Top 2/4/6 Slots", top_slots, false)
         .field("Top Element Artifacts", top_artifact_type, false)
         .field("Top Type Artifacts", top_artifact_arch, false)
@@ -384,9 +376,209 @@ pub fn build_how_to_build_embed(
     embed
 }
 
+// ---------------------------
+// Rank comparison
+// ---------------------------
+
+/// Écarts (en points) à partir desquels une variation est mise en gras
+const PICKRATE_SHIFT_PTS: f32 = 5.0;
+const WINRATE_SHIFT_PTS: f32 = 2.0;
+
+/// (libellé, pickrate, winrate si disponible)
+type ComparedStat = (String, f32, Option<f32>);
+
+fn format_delta(delta_pts: f32, threshold: f32) -> String {
+    let arrow = if delta_pts > 0.0 {
+        "▲"
+    } else if delta_pts < 0.0 {
+        "▼"
+    } else {
+        "="
+    };
+    let text = format!("{} {:+.1}", arrow, delta_pts);
+    if delta_pts.abs() >= threshold {
+        format!("**{}**", text)
+    } else {
+        text
+    }
+}
+
+fn most_picked(stats: &[ComparedStat]) -> &str {
+    stats
+        .iter()
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(label, _, _)| label.as_str())
+        .unwrap_or("-")
+}
+
+/// One block per item (most picked across all ranks first): its stats in each
+/// rank, then the shift between the lowest and the highest rank.
+fn format_rank_comparison(ranks: &[i32], stats: &[Vec<ComparedStat>], top_n: usize) -> String {
+    let mut labels: Vec<(&str, f32)> = Vec::new();
+    for (label, pickrate, _) in stats.iter().flatten() {
+        match labels.iter_mut().find(|(l, _)| *l == label) {
+            Some(entry) => entry.1 = entry.1.max(*pickrate),
+            None => labels.push((label, *pickrate)),
+        }
+    }
+    if labels.is_empty() {
+        return "No data.".to_string();
+    }
+    labels.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    labels
+        .into_iter()
+        .take(top_n)
+        .map(|(label, _)| {
+            let per_rank: Vec<Option<&ComparedStat>> = stats
+                .iter()
+                .map(|rank_stats| rank_stats.iter().find(|(l, _, _)| l == label))
+                .collect();
+
+            let values = ranks
+                .iter()
+                .zip(&per_rank)
+                .map(|(rank, stat)| match stat {
+                    Some((_, pickrate, Some(winrate))) => format!(
+                        "{} `{}` · {}",
+                        rank_label(*rank),
+                        fmt_pct(*pickrate),
+                        fmt_pct(*winrate)
+                    ),
+                    Some((_, pickrate, None)) => {
+                        format!("{} `{}`", rank_label(*rank), fmt_pct(*pickrate))
+                    }
+                    None => format!("{} `-`", rank_label(*rank)),
+                })
+                .collect::<Vec<_>>()
+                .join(" → ");
+
+            // Absent d'un rank = pas joué (pickrate 0)
+            let first = per_rank.first().copied().flatten();
+            let last = per_rank.last().copied().flatten();
+            let pick_delta = (last.map_or(0.0, |s| s.1) - first.map_or(0.0, |s| s.1)) * 100.0;
+            let mut deltas = format!("Pick {}", format_delta(pick_delta, PICKRATE_SHIFT_PTS));
+            if let (Some((_, _, Some(low))), Some((_, _, Some(high)))) = (first, last) {
+                deltas.push_str(&format!(
+                    " · WR {}",
+                    format_delta((high - low) * 100.0, WINRATE_SHIFT_PTS)
+                ));
+            }
+
+            format!("**{}**\n{}\n{}", label, values, deltas)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn artifact_stats(items: &[LucksackArtifactStat]) -> Vec<ComparedStat> {
+    items
+        .iter()
+        .map(|a| {
+            (
+                artifact_effect_name(a.effect_id).to_string(),
+                a.pickrate,
+                None,
+            )
+        })
+        .collect()
+}
+
+pub fn build_rank_comparison_embed(
+    monster_name: &str,
+    season: i32,
+    builds: &[(i32, LucksackBuildResponse)],
+    image_url: Option<String>,
+) -> CreateEmbed {
+    let ranks: Vec<i32> = builds.iter().map(|(rank, _)| *rank).collect();
+
+    let sets: Vec<Vec<ComparedStat>> = builds
+        .iter()
+        .map(|(_, b)| {
+            b.rune_sets
+                .iter()
+                .map(|s| (rune_set_combo_name(s), s.pickrate, Some(s.winrate)))
+                .collect()
+        })
+        .collect();
+    let slots: Vec<Vec<ComparedStat>> = builds
+        .iter()
+        .map(|(_, b)| {
+            b.slot_stats
+                .iter()
+                .map(|s| (slot_combo_name(s), s.pickrate, Some(s.winrate)))
+                .collect()
+        })
+        .collect();
+    let artifact_type: Vec<Vec<ComparedStat>> = builds
+        .iter()
+        .map(|(_, b)| artifact_stats(&b.artifact_type))
+        .collect();
+    let artifact_arch: Vec<Vec<ComparedStat>> = builds
+        .iter()
+        .map(|(_, b)| artifact_stats(&b.artifact_arch))
+        .collect();
+
+    let shift = |stats: &[Vec<ComparedStat>]| {
+        ranks
+            .iter()
+            .zip(stats)
+            .map(|(rank, s)| format!("{} **{}**", rank_label(*rank), most_picked(s)))
+            .collect::<Vec<_>>()
+            .join(" → ")
+    };
+
+    let description = format!(
+        "See more detailed stats directly on [lucksack.gg](https://lucksack.gg/)\nData format : rank `Pick Rate` · Win Rate, then the shift from the lowest to the highest rank (in points, bold when significant)\n\nMost picked sets : {}\nMost picked slots : {}",
+        shift(&sets),
+        shift(&slots)
+    );
+
+    let mut embed = serenity::CreateEmbed::default()
+        .title(format!(
+            "How to build - {} - Season {} - {}",
+            monster_name.split(" - ").next().unwrap_or(monster_name),
+            season,
+            ranks
+                .iter()
+                .map(|r| rank_label(*r))
+                .collect::<Vec<_>>()
+                .join(" vs ")
+        ))
+        .description(description)
+        .color(serenity::Colour::from_rgb(120, 153, 255))
+        .field("Rune Sets", format_rank_comparison(&ranks, &sets, 4), false)
+        .field(
+            "2/4/6 Slots",
+            format_rank_comparison(&ranks, &slots, 4),
+            false,
+        )
+        .field(
+            "Element Artifacts",
+            format_rank_comparison(&ranks, &artifact_type, 3),
+            false,
+        )
+        .field(
+            "Type Artifacts",
+            format_rank_comparison(&ranks, &artifact_arch, 3),
+            false,
+        )
+        .footer(CreateEmbedFooter::new("Data is gathered from lucksack.gg"));
+
+    if let Some(url) = image_url {
+        embed = embed.thumbnail(url);
+    }
+
+    embed
+}
+
 // ---------------------------
 // Buttons (rank lucksack)
 // ---------------------------
+
+/// Valeur de `selected_rank` pour la vue comparative
+pub const COMPARE_VIEW: i32 = 0;
+
 pub fn create_lucksack_rank_buttons(
     selected_rank: i32,
     disabled: bool,
@@ -439,5 +631,10 @@ pub fn create_lucksack_rank_buttons(
                 name: Some("guardian".to_string()),
             })
             .style(style_for(16)),
+        serenity::CreateButton::new("rank_compare")
+            .label("Compare")
+            .disabled(disabled)
+            .emoji(serenity::ReactionType::Unicode("⚖️".to_string()))
+            .style(style_for(COMPARE_VIEW)),
     ])
 }
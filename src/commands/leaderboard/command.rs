@@ -5,13 +5,23 @@ use serenity::{
     Error,
 };
 
-use crate::commands::leaderboard::utils::{get_leaderboard_data, LeaderboardPlayer};
-use crate::commands::player_stats::command::show_player_stats;
-use crate::commands::player_stats::utils::get_lucksack_season_numbers;
+use crate::commands::leaderboard::snapshots::{
+    biggest_climbers, format_movement, get_reference_snapshot, movements, LeaderboardSnapshot,
+    Movement, SnapshotPeriod,
+};
+use crate::commands::leaderboard::utils::{
+    filter_by_country, get_leaderboard_data, get_leaderboard_top, leaderboard_to_csv,
+    LeaderboardPlayer, LEADERBOARD_SCAN_LIMIT,
+};
+use crate::commands::player_stats::command::{resolve_player_id, show_player_stats};
+use crate::commands::player_stats::utils::{
+    get_lucksack_player_summary, get_lucksack_season_numbers,
+};
+use crate::commands::register::utils::get_user_link;
 use crate::commands::shared::logs::get_server_name;
 use crate::commands::shared::logs::send_log;
-use crate::commands::shared::models::LoggerDocument;
-use crate::commands::shared::player_alias::PLAYER_ALIAS_MAP;
+use crate::commands::shared::models::{LoggerDocument, ReplayDisplay};
+use crate::commands::shared::player_alias::player_alias;
 use crate::Data;
 
 /// 📂 Displays the RTA leaderboard
@@ -19,12 +29,18 @@ use crate::Data;
 pub async fn get_rta_leaderboard(
     ctx: poise::ApplicationContext<'_, Data, Error>,
     #[description = "Page number to start from"] page: Option<i32>,
+    #[description = "Country code filter, e.g. FR (top 1000 only)"] country: Option<String>,
+    #[description = "Open the page containing this player (name, alias or @mention)"]
+    player_name: Option<String>,
+    #[description = "Export the top N players as CSV (max 1000)"] export_top: Option<u32>,
+    #[description = "Rank and score movement period (default: since yesterday)"] compare: Option<
+        SnapshotPeriod,
+    >,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
     let user_id = ctx.author().id;
     let mut page = page.unwrap_or(1).max(1);
-    const PAGE_SIZE: i32 = 10;
 
     let seasons = get_lucksack_season_numbers().await.map_err(|e| {
         Error::from(std::io::Error::other(format!(
@@ -37,23 +53,96 @@ pub async fn get_rta_leaderboard(
         .copied()
         .ok_or_else(|| Error::from(std::io::Error::other("No valid season found.")))?;
 
-    let leaderboard = get_leaderboard_data(season, page, PAGE_SIZE)
+    let country = country
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty());
+
+    // Le filtre pays travaille sur le top mis en cache, la pagination est alors locale
+    let filtered: Option<Vec<LeaderboardPlayer>> = match &country {
+        Some(c) => {
+            let top = get_leaderboard_top(season)
+                .await
+                .map_err(|e| Error::from(std::io::Error::other(format!("API error: {}", e))))?;
+            Some(filter_by_country(&top, c))
+        }
+        None => None,
+    };
+
+    let period = compare.unwrap_or_default();
+    let reference = get_reference_snapshot(season, period)
         .await
-        .map_err(|e| Error::from(std::io::Error::other(format!("API error: {}", e))))?;
-
-    let mut players = leaderboard.data;
-    let total_count = leaderboard.count;
-
-    let response = ctx
-        .send(CreateReply {
-            embeds: vec![build_leaderboard_embed(&players, page, total_count)],
-            components: Some(vec![
-                create_pagination_buttons(page, total_count, PAGE_SIZE),
-                create_player_select_menu(&players),
-            ]),
-            ..Default::default()
-        })
-        .await?;
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to load leaderboard snapshot: {e:?}");
+            None
+        });
+
+    let view = LeaderboardView {
+        country: country.as_deref(),
+        reference: reference.as_ref(),
+        period,
+    };
+
+    let mut existing_reply = None;
+    if let Some(name) = player_name {
+        let Some(resolved) = resolve_player_id(&ctx, &name, "get_leaderboard").await? else {
+            return Ok(());
+        };
+        existing_reply = resolved.reply_handle;
+
+        match page_of_player(season, resolved.player_id, filtered.as_deref()).await {
+            Some(p) => page = p,
+            None => {
+                ctx.say(format!(
+                    "❌ `{}` is not in this leaderboard, showing page {} instead.",
+                    name, page
+                ))
+                .await?;
+            }
+        }
+    }
+
+    let (mut players, mut total_count) = load_page(season, page, filtered.as_deref()).await?;
+
+    let csv_attachment = match export_top {
+        Some(n) => {
+            let n = (n as usize).clamp(1, LEADERBOARD_SCAN_LIMIT);
+            let source = match &filtered {
+                Some(f) => f.clone(),
+                None => get_leaderboard_top(season)
+                    .await
+                    .map_err(|e| Error::from(std::io::Error::other(format!("API error: {}", e))))?
+                    .to_vec(),
+            };
+            let top: Vec<LeaderboardPlayer> = source.into_iter().take(n).collect();
+            let filename = match &country {
+                Some(c) => format!("leaderboard-s{}-{}-top{}.csv", season, c.to_lowercase(), n),
+                None => format!("leaderboard-s{}-top{}.csv", season, n),
+            };
+            Some(serenity::CreateAttachment::bytes(
+                leaderboard_to_csv(&top).into_bytes(),
+                filename,
+            ))
+        }
+        None => None,
+    };
+
+    let initial_reply = CreateReply {
+        content: Some(String::new()),
+        embeds: vec![build_leaderboard_embed(&players, page, total_count, &view)],
+        components: Some(leaderboard_components(&players, page, total_count, false)),
+        attachments: csv_attachment.into_iter().collect(),
+        ..Default::default()
+    };
+
+    let response = match existing_reply {
+        Some(handle) => {
+            handle
+                .edit(poise::Context::Application(ctx), initial_reply)
+                .await?;
+            handle
+        }
+        None => ctx.send(initial_reply).await?,
+    };
 
     let message_id = response.message().await?.id;
     let channel_id = ctx.channel_id();
@@ -70,6 +159,89 @@ pub async fn get_rta_leaderboard(
             "previous_page" if page > 1 => page -= 1,
             "next_page" => page += 1,
 
+            "leaderboard_my_position" => {
+                let linked_player = get_user_link(interaction.user.id.get())
+                    .await
+                    .ok()
+                    .flatten()
+                    .and_then(|doc| doc.get_i64("swrt_player_id").ok());
+
+                let target_page = match linked_player {
+                    Some(player_id) => page_of_player(season, player_id, filtered.as_deref()).await,
+                    None => None,
+                };
+
+                let Some(target_page) = target_page else {
+                    let content = if linked_player.is_none() {
+                        "❌ No linked account yet. Use `/register <account name>` first."
+                    } else {
+                        "❌ Your account is not in this leaderboard."
+                    };
+                    interaction
+                        .create_response(
+                            &ctx.serenity_context,
+                            serenity::CreateInteractionResponse::Message(
+                                serenity::CreateInteractionResponseMessage::new()
+                                    .content(content)
+                                    .ephemeral(true),
+                            ),
+                        )
+                        .await?;
+                    continue;
+                };
+
+                page = target_page;
+            }
+
+            "leaderboard_climbers" => {
+                let Some(reference) = reference.as_ref() else {
+                    interaction
+                        .create_response(
+                            &ctx.serenity_context,
+                            serenity::CreateInteractionResponse::Message(
+                                serenity::CreateInteractionResponseMessage::new()
+                                    .content("❌ No leaderboard snapshot is available yet for this period.")
+                                    .ephemeral(true),
+                            ),
+                        )
+                        .await?;
+                    continue;
+                };
+
+                let top = match &filtered {
+                    Some(f) => f.clone(),
+                    None => match get_leaderboard_top(season).await {
+                        Ok(top) => top.to_vec(),
+                        Err(e) => {
+                            ctx.say(format!("Failed to load the leaderboard: {}", e))
+                                .await?;
+                            break;
+                        }
+                    },
+                };
+
+                interaction
+                    .create_response(
+                        &ctx.serenity_context,
+                        serenity::CreateInteractionResponse::UpdateMessage(
+                            serenity::CreateInteractionResponseMessage::new()
+                                .add_embed(build_climbers_embed(
+                                    &biggest_climbers(&top, reference, PAGE_SIZE as usize),
+                                    &view,
+                                ))
+                                .components(vec![create_pagination_buttons(
+                                    page,
+                                    total_count,
+                                    PAGE_SIZE,
+                                    false,
+                                )]),
+                        ),
+                    )
+                    .await?;
+
+                continue;
+            }
+
             "leaderboard_player_select" => {
                 let selected_id =
                     if let serenity::ComponentInteractionDataKind::StringSelect { values } =
@@ -116,16 +288,25 @@ pub async fn get_rta_leaderboard(
                         &ctx.serenity_context,
                         serenity::CreateInteractionResponse::UpdateMessage(
                             serenity::CreateInteractionResponseMessage::new()
-                                .add_embed(build_leaderboard_embed(&players, page, total_count))
-                                .components(vec![
-                                    create_pagination_buttons(page, total_count, PAGE_SIZE),
-                                    create_player_select_menu(&players),
-                                ]),
+                                .add_embed(build_leaderboard_embed(
+                                    &players,
+                                    page,
+                                    total_count,
+                                    &view,
+                                ))
+                                .components(leaderboard_components(
+                                    &players,
+                                    page,
+                                    total_count,
+                                    false,
+                                )),
                         ),
                     )
                     .await?;
 
-                if let Err(e) = show_player_stats(&ctx, player_id, None).await {
+                if let Err(e) =
+                    show_player_stats(&ctx, player_id, None, ReplayDisplay::default()).await
+                {
                     ctx.say(format!("❌ Failed to load player stats: {}", e))
                         .await?;
                 }
@@ -136,7 +317,7 @@ pub async fn get_rta_leaderboard(
             _ => continue,
         }
 
-        let leaderboard = match get_leaderboard_data(season, page, PAGE_SIZE).await {
+        (players, total_count) = match load_page(season, page, filtered.as_deref()).await {
             Ok(p) => p,
             Err(e) => {
                 ctx.say(format!("Failed to load page {}: {}", page, e))
@@ -145,18 +326,13 @@ pub async fn get_rta_leaderboard(
             }
         };
 
-        players = leaderboard.data;
-
         interaction
             .create_response(
                 &ctx.serenity_context,
                 serenity::CreateInteractionResponse::UpdateMessage(
                     serenity::CreateInteractionResponseMessage::new()
-                        .add_embed(build_leaderboard_embed(&players, page, total_count))
-                        .components(vec![
-                            create_pagination_buttons(page, total_count, PAGE_SIZE),
-                            create_player_select_menu(&players),
-                        ]),
+                        .add_embed(build_leaderboard_embed(&players, page, total_count, &view))
+                        .components(leaderboard_components(&players, page, total_count, false)),
                 ),
             )
             .await?;
@@ -167,17 +343,13 @@ pub async fn get_rta_leaderboard(
         .edit(
             poise::Context::Application(ctx),
             CreateReply {
-                embeds: vec![build_leaderboard_embed(&players, page, total_count)],
-                components: Some(vec![serenity::CreateActionRow::Buttons(vec![
-                    serenity::CreateButton::new("previous_page")
-                        .label("⬅️ Previous")
-                        .style(serenity::ButtonStyle::Primary)
-                        .disabled(true),
-                    serenity::CreateButton::new("next_page")
-                        .label("➡️ Next")
-                        .style(serenity::ButtonStyle::Primary)
-                        .disabled(true),
-                ])]),
+                embeds: vec![build_leaderboard_embed(&players, page, total_count, &view)],
+                components: Some(vec![create_pagination_buttons(
+                    page,
+                    total_count,
+                    PAGE_SIZE,
+                    true,
+                )]),
                 ..Default::default()
             },
         )
@@ -195,32 +367,131 @@ pub async fn get_rta_leaderboard(
     Ok(())
 }
 
+const PAGE_SIZE: i32 = 10;
+
+/// Page courante : API lucksack, ou découpage local quand un filtre pays est actif
+async fn load_page(
+    season: i32,
+    page: i32,
+    filtered: Option<&[LeaderboardPlayer]>,
+) -> Result<(Vec<LeaderboardPlayer>, i64), Error> {
+    match filtered {
+        Some(list) => {
+            let start = ((page - 1) * PAGE_SIZE) as usize;
+            let players = list
+                .iter()
+                .skip(start)
+                .take(PAGE_SIZE as usize)
+                .cloned()
+                .collect();
+            Ok((players, list.len() as i64))
+        }
+        None => {
+            let leaderboard = get_leaderboard_data(season, page, PAGE_SIZE)
+                .await
+                .map_err(|e| Error::from(std::io::Error::other(format!("API error: {}", e))))?;
+            Ok((leaderboard.data, leaderboard.count))
+        }
+    }
+}
+
+/// Page containing `player_id`, from its current rank (or its position in the
+/// filtered list).
+async fn page_of_player(
+    season: i32,
+    player_id: i64,
+    filtered: Option<&[LeaderboardPlayer]>,
+) -> Option<i32> {
+    let position = match filtered {
+        Some(list) => list.iter().position(|p| p.player_id == player_id)? as i64,
+        None => {
+            let summary = get_lucksack_player_summary(player_id, season).await.ok()?;
+            if summary.summary.current_rank <= 0 {
+                return None;
+            }
+            summary.summary.current_rank - 1
+        }
+    };
+
+    Some((position / PAGE_SIZE as i64) as i32 + 1)
+}
+
+fn leaderboard_components(
+    players: &[LeaderboardPlayer],
+    page: i32,
+    total_count: i64,
+    disabled: bool,
+) -> Vec<serenity::CreateActionRow> {
+    let mut rows = vec![create_pagination_buttons(
+        page,
+        total_count,
+        PAGE_SIZE,
+        disabled,
+    )];
+    // Discord refuse un menu sans option (page vide)
+    if !players.is_empty() {
+        rows.push(create_player_select_menu(players));
+    }
+    rows
+}
+
+/// Filtre pays et instantané de référence communs à toutes les pages
+struct LeaderboardView<'a> {
+    country: Option<&'a str>,
+    reference: Option<&'a LeaderboardSnapshot>,
+    period: SnapshotPeriod,
+}
+
 fn build_leaderboard_embed(
     players: &[LeaderboardPlayer],
     page: i32,
     total_count: i64,
+    view: &LeaderboardView,
 ) -> serenity::CreateEmbed {
     let mut description = String::new();
+    let player_movements = view.reference.map(|r| movements(players, r));
 
-    for player in players {
-        let alias_str = PLAYER_ALIAS_MAP
-            .get(&player.player_id)
+    for (i, player) in players.iter().enumerate() {
+        let alias_str = player_alias(player.player_id)
             .map(|alias| format!(" aka **{}**", alias))
             .unwrap_or_default();
 
+        let movement_str = player_movements
+            .as_ref()
+            .map(|m| format!(" {}", format_movement(&m[i])))
+            .unwrap_or_default();
+
         description.push_str(&format!(
-            "{}. :flag_{}: {} - `{}`{}\n",
+            "{}. :flag_{}: {} - `{}`{}{}\n",
             player.rank,
             player.country.to_lowercase(),
             player.current_score,
             player.username,
             alias_str,
+            movement_str,
         ));
     }
 
-    CreateEmbed::default()
-        .title(format!("Leaderboard - Page {}", page))
-        .description(description)
+    if description.is_empty() {
+        description.push_str("No player on this page.");
+    }
+
+    let title = match view.country {
+        Some(c) => format!("Leaderboard :flag_{}: - Page {}", c.to_lowercase(), page),
+        None => format!("Leaderboard - Page {}", page),
+    };
+
+    let mut embed = CreateEmbed::default().title(title).description(description);
+    if view.country.is_some() {
+        embed = embed.field(
+            "🌍 Country filter",
+            format!("Within the global top {}", LEADERBOARD_SCAN_LIMIT),
+            true,
+        );
+    }
+    embed = embed.field("📈 Movement", movement_note(view), true);
+
+    embed
         .field("Players", total_count.to_string(), true)
         .field(
             "💡 Tip",
@@ -236,24 +507,89 @@ fn build_leaderboard_embed(
         .color(serenity::Colour::from_rgb(0, 255, 0))
 }
 
+fn movement_note(view: &LeaderboardView) -> String {
+    match view.reference {
+        Some(r) => format!("Since {} ({})", view.period.label(), r.date),
+        None => format!("No snapshot from {} yet", view.period.label()),
+    }
+}
+
+fn build_climbers_embed(climbers: &[Movement], view: &LeaderboardView) -> serenity::CreateEmbed {
+    let mut description = String::new();
+
+    for (i, m) in climbers.iter().enumerate() {
+        let alias_str = player_alias(m.player.player_id)
+            .map(|alias| format!(" aka **{}**", alias))
+            .unwrap_or_default();
+
+        description.push_str(&format!(
+            "{}. :flag_{}: `{}`{} - #{} → **#{}** {}\n",
+            i + 1,
+            m.player.country.to_lowercase(),
+            m.player.username,
+            alias_str,
+            m.previous_rank.unwrap_or_default(),
+            m.player.rank,
+            format_movement(m),
+        ));
+    }
+
+    if description.is_empty() {
+        description.push_str("Nobody climbed during this period.");
+    }
+
+    let title = match view.country {
+        Some(c) => format!("🚀 Biggest climbers :flag_{}:", c.to_lowercase()),
+        None => "🚀 Biggest climbers".to_string(),
+    };
+
+    CreateEmbed::default()
+        .title(title)
+        .description(description)
+        .field("📈 Movement", movement_note(view), true)
+        .field(
+            "💡 Tip",
+            "Use the page buttons to go back to the leaderboard.",
+            false,
+        )
+        .footer(CreateEmbedFooter::new("Data is gathered from lucksack.gg"))
+        .color(serenity::Colour::from_rgb(0, 255, 0))
+}
+
 fn create_pagination_buttons(
     page: i32,
     total_count: i64,
     page_size: i32,
+    disabled: bool,
 ) -> serenity::CreateActionRow {
     let last_page = ((total_count + page_size as i64 - 1) / page_size as i64).max(1) as i32;
 
     let previous_button = serenity::CreateButton::new("previous_page")
         .label("⬅️ Previous")
         .style(serenity::ButtonStyle::Primary)
-        .disabled(page <= 1);
+        .disabled(disabled || page <= 1);
 
     let next_button = serenity::CreateButton::new("next_page")
         .label("➡️ Next")
         .style(serenity::ButtonStyle::Primary)
-        .disabled(page >= last_page);
-
-    serenity::CreateActionRow::Buttons(vec![previous_button, next_button])
+        .disabled(disabled || page >= last_page);
+
+    let my_position_button = serenity::CreateButton::new("leaderboard_my_position")
+        .label("📍 My position")
+        .style(serenity::ButtonStyle::Secondary)
+        .disabled(disabled);
+
+    let climbers_button = serenity::CreateButton::new("leaderboard_climbers")
+        .label("🚀 Climbers")
+        .style(serenity::ButtonStyle::Secondary)
+        .disabled(disabled);
+
+    serenity::CreateActionRow::Buttons(vec![
+        previous_button,
+        next_button,
+        my_position_button,
+        climbers_button,
+    ])
 }
 
 fn create_player_select_menu(players: &[LeaderboardPlayer]) -> serenity::CreateActionRow {
@@ -266,7 +602,7 @@ fn create_player_select_menu(players: &[LeaderboardPlayer]) -> serenity::CreateA
                 serenity::ReactionType::Unicode(country_code_to_flag_emoji(&player.country))
             };
 
-            let label = if let Some(alias) = PLAYER_ALIAS_MAP.get(&player.player_id) {
+            let label = if let Some(alias) = player_alias(player.player_id) {
                 format!("{} aka {}", player.username, alias)
             } else {
                 player.username.clone()
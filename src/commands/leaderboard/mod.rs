@@ -1,2 +1,3 @@
 pub mod command;
+pub mod snapshots;
 pub mod utils;
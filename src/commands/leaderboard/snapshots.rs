@@ -0,0 +1,264 @@
+//! Instantanés quotidiens du top du leaderboard (collection
+//! `leaderboard-snapshots`), pour afficher les variations de rang / score.
+use anyhow::Result;
+use mongodb::bson::doc;
+use mongodb::options::FindOneOptions;
+use mongodb::Collection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::commands::leaderboard::utils::{get_leaderboard_top, LeaderboardPlayer};
+use crate::commands::player_stats::utils::get_lucksack_season_numbers;
+use crate::commands::shared::clients::mongo_client;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotRow {
+    pub player_id: i64,
+    pub username: String,
+    pub country: String,
+    pub rank: i64,
+    pub score: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardSnapshot {
+    pub season: i32,
+    /// Jour UTC, "YYYY-MM-DD"
+    pub date: String,
+    pub taken_at: i64,
+    pub players: Vec<SnapshotRow>,
+}
+
+impl LeaderboardSnapshot {
+    pub fn by_player(&self) -> HashMap<i64, &SnapshotRow> {
+        self.players.iter().map(|r| (r.player_id, r)).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum SnapshotPeriod {
+    #[default]
+    #[name = "Since yesterday"]
+    Yesterday,
+    #[name = "Since last week"]
+    LastWeek,
+}
+
+impl SnapshotPeriod {
+    pub fn days(self) -> i64 {
+        match self {
+            SnapshotPeriod::Yesterday => 1,
+            SnapshotPeriod::LastWeek => 7,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SnapshotPeriod::Yesterday => "yesterday",
+            SnapshotPeriod::LastWeek => "last week",
+        }
+    }
+}
+
+/// Mouvement d'un joueur entre l'instantané de référence et maintenant
+#[derive(Debug, Clone)]
+pub struct Movement {
+    pub player: LeaderboardPlayer,
+    /// `None` si le joueur n'était pas dans l'instantané
+    pub previous_rank: Option<i64>,
+    pub previous_score: Option<i64>,
+    /// Rang actuel couvert par l'instantané : absent = vraiment nouveau
+    pub within_snapshot: bool,
+}
+
+impl Movement {
+    /// Positif = places gagnées
+    pub fn rank_delta(&self) -> Option<i64> {
+        self.previous_rank.map(|r| r - self.player.rank)
+    }
+
+    pub fn score_delta(&self) -> Option<i64> {
+        self.previous_score.map(|s| self.player.current_score - s)
+    }
+}
+
+fn snapshots_collection() -> Result<Collection<LeaderboardSnapshot>> {
+    Ok(mongo_client()?
+        .database("bot-swbox-db")
+        .collection::<LeaderboardSnapshot>("leaderboard-snapshots"))
+}
+
+fn utc_date(days_ago: i64) -> String {
+    (chrono::Utc::now() - chrono::Duration::days(days_ago))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Stores today's snapshot of the top players, once per UTC day. Returns
+/// `false` when today's snapshot already exists.
+pub async fn take_daily_snapshot() -> Result<bool> {
+    let season = get_lucksack_season_numbers()
+        .await?
+        .first()
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("No lucksack season found"))?;
+
+    let col = snapshots_collection()?;
+    let today = utc_date(0);
+    if col
+        .find_one(doc! { "season": season, "date": &today })
+        .await?
+        .is_some()
+    {
+        return Ok(false);
+    }
+
+    let top = get_leaderboard_top(season).await?;
+    let players = top
+        .iter()
+        .map(|p| SnapshotRow {
+            player_id: p.player_id,
+            username: p.username.clone(),
+            country: p.country.clone(),
+            rank: p.rank,
+            score: p.current_score,
+        })
+        .collect();
+
+    col.insert_one(LeaderboardSnapshot {
+        season,
+        date: today,
+        taken_at: chrono::Utc::now().timestamp(),
+        players,
+    })
+    .await?;
+
+    Ok(true)
+}
+
+/// Latest snapshot of the season taken at least `period` ago.
+pub async fn get_reference_snapshot(
+    season: i32,
+    period: SnapshotPeriod,
+) -> Result<Option<LeaderboardSnapshot>> {
+    let col = snapshots_collection()?;
+    let snapshot = col
+        .find_one(doc! { "season": season, "date": { "$lte": utc_date(period.days()) } })
+        .with_options(FindOneOptions::builder().sort(doc! { "date": -1 }).build())
+        .await?;
+    Ok(snapshot)
+}
+
+pub fn movements(players: &[LeaderboardPlayer], reference: &LeaderboardSnapshot) -> Vec<Movement> {
+    let previous = reference.by_player();
+    // L'instantané ne garde que le top : au-delà, l'absence ne veut rien dire
+    let last_rank = reference.players.iter().map(|r| r.rank).max().unwrap_or(0);
+    players
+        .iter()
+        .map(|p| {
+            let row = previous.get(&p.player_id);
+            Movement {
+                player: p.clone(),
+                previous_rank: row.map(|r| r.rank),
+                previous_score: row.map(|r| r.score),
+                within_snapshot: p.rank <= last_rank,
+            }
+        })
+        .collect()
+}
+
+/// Players of `top` who gained the most places since the snapshot.
+pub fn biggest_climbers(
+    top: &[LeaderboardPlayer],
+    reference: &LeaderboardSnapshot,
+    limit: usize,
+) -> Vec<Movement> {
+    let mut climbers: Vec<Movement> = movements(top, reference)
+        .into_iter()
+        .filter(|m| m.rank_delta().is_some_and(|d| d > 0))
+        .collect();
+    climbers.sort_by(|a, b| {
+        b.rank_delta()
+            .cmp(&a.rank_delta())
+            .then(b.score_delta().cmp(&a.score_delta()))
+    });
+    climbers.truncate(limit);
+    climbers
+}
+
+/// "🔺3 (+25)", "🔻2 (-12)", "▪️", "🆕" for players missing from the snapshot
+/// while ranked within its range, or "–" below that range (unknown).
+pub fn format_movement(m: &Movement) -> String {
+    let (Some(rank_delta), Some(score_delta)) = (m.rank_delta(), m.score_delta()) else {
+        return if m.within_snapshot { "🆕" } else { "–" }.to_string();
+    };
+
+    let rank = match rank_delta {
+        d if d > 0 => format!("🔺{}", d),
+        d if d < 0 => format!("🔻{}", -d),
+        _ => "▪️".to_string(),
+    };
+
+    if score_delta == 0 {
+        rank
+    } else {
+        format!("{} ({:+})", rank, score_delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(player_id: i64, rank: i64, current_score: i64) -> LeaderboardPlayer {
+        LeaderboardPlayer {
+            player_id,
+            username: format!("player{player_id}"),
+            country: "FR".to_string(),
+            current_score,
+            rank,
+        }
+    }
+
+    fn snapshot(rows: &[(i64, i64, i64)]) -> LeaderboardSnapshot {
+        LeaderboardSnapshot {
+            season: 1,
+            date: "2026-10-18".to_string(),
+            taken_at: 0,
+            players: rows
+                .iter()
+                .map(|&(player_id, rank, score)| SnapshotRow {
+                    player_id,
+                    username: format!("player{player_id}"),
+                    country: "FR".to_string(),
+                    rank,
+                    score,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn formats_moves_within_the_snapshot() {
+        let reference = snapshot(&[(1, 1, 3000), (2, 2, 2990), (3, 3, 2980)]);
+        let players = [player(2, 1, 3010), player(1, 2, 3000), player(4, 3, 2985)];
+        let formatted: Vec<String> = movements(&players, &reference)
+            .iter()
+            .map(format_movement)
+            .collect();
+        assert_eq!(formatted, vec!["🔺1 (+20)", "🔻1", "🆕"]);
+    }
+
+    #[test]
+    fn players_below_the_snapshot_range_are_not_new() {
+        let reference = snapshot(&[(1, 1, 3000), (2, 2, 2990)]);
+        let players = [player(5, 1500, 1800), player(2, 1501, 1799)];
+        let formatted: Vec<String> = movements(&players, &reference)
+            .iter()
+            .map(format_movement)
+            .collect();
+        assert_eq!(formatted[0], "–");
+        // Un joueur connu garde sa variation, même hors de la plage
+        assert_eq!(formatted[1], "🔻1499 (-1191)");
+    }
+}
@@ -1,7 +1,13 @@
 use anyhow::{anyhow, Result};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
 use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
 
+use crate::commands::player_names::snapshots::spawn_record_player_names;
 use crate::commands::shared::clients::http_client;
+use crate::commands::shared::player_alias::player_alias;
 
 #[derive(Debug, Deserialize)]
 pub struct LeaderboardResponse {
@@ -9,7 +15,7 @@ pub struct LeaderboardResponse {
     pub data: Vec<LeaderboardPlayer>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct LeaderboardPlayer {
     pub player_id: i64,
     pub username: String,
@@ -43,7 +49,92 @@ pub async fn get_leaderboard_data(
         return Err(anyhow!("Error status {}", res.status()));
     }
 
-    res.json::<LeaderboardResponse>()
+    let leaderboard = res
+        .json::<LeaderboardResponse>()
+        .await
+        .map_err(|e| anyhow!("Failed to parse leaderboard JSON: {}", e))?;
+
+    spawn_record_player_names(
+        leaderboard
+            .data
+            .iter()
+            .map(|p| (p.player_id, p.username.clone()))
+            .collect(),
+    );
+
+    Ok(leaderboard)
+}
+
+/// Taille du top parcouru pour les filtres par pays et l'export CSV
+pub const LEADERBOARD_SCAN_LIMIT: usize = 1000;
+const LEADERBOARD_SCAN_PAGE_SIZE: i32 = 100;
+
+/// Clé : season
+static LEADERBOARD_TOP_CACHE: Lazy<Cache<i32, Arc<Vec<LeaderboardPlayer>>>> = Lazy::new(|| {
+    Cache::builder()
+        .time_to_live(Duration::from_secs(600))
+        .max_capacity(4)
+        .build()
+});
+
+/// Top `LEADERBOARD_SCAN_LIMIT` players of the season, cached for 10 minutes.
+pub async fn get_leaderboard_top(season: i32) -> Result<Arc<Vec<LeaderboardPlayer>>> {
+    LEADERBOARD_TOP_CACHE
+        .try_get_with(season, async move {
+            let pages = (LEADERBOARD_SCAN_LIMIT as i32) / LEADERBOARD_SCAN_PAGE_SIZE;
+            let mut players = Vec::with_capacity(LEADERBOARD_SCAN_LIMIT);
+
+            for page in 1..=pages {
+                let res = get_leaderboard_data(season, page, LEADERBOARD_SCAN_PAGE_SIZE).await?;
+                let is_last = res.data.len() < LEADERBOARD_SCAN_PAGE_SIZE as usize;
+                players.extend(res.data);
+                if is_last {
+                    break;
+                }
+            }
+
+            Ok::<_, anyhow::Error>(Arc::new(players))
+        })
         .await
-        .map_err(|e| anyhow!("Failed to parse leaderboard JSON: {}", e))
+        .map_err(|e| anyhow!("Failed to fetch leaderboard top: {}", e))
+}
+
+pub fn filter_by_country(players: &[LeaderboardPlayer], country: &str) -> Vec<LeaderboardPlayer> {
+    players
+        .iter()
+        .filter(|p| p.country.eq_ignore_ascii_case(country))
+        .cloned()
+        .collect()
+}
+
+/// `rank,player_id,username,country,score,alias`
+pub fn leaderboard_to_csv(players: &[LeaderboardPlayer]) -> String {
+    fn escape(field: &str) -> String {
+        // Un pseudo commençant par = + - @ serait interprété comme une formule par le tableur
+        let field = if field.starts_with(['=', '+', '-', '@']) {
+            format!("'{}", field)
+        } else {
+            field.to_string()
+        };
+        if field.contains([',', '"', '\n']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field
+        }
+    }
+
+    let mut csv = String::from("rank,player_id,username,country,score,alias\n");
+    for p in players {
+        let alias = player_alias(p.player_id).unwrap_or_default();
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            p.rank,
+            p.player_id,
+            escape(&p.username),
+            escape(&p.country),
+            p.current_score,
+            escape(&alias)
+        ));
+    }
+    csv
 }
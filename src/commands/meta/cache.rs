@@ -0,0 +1,38 @@
+//! Cache des images de tierlist, par (niveau, createDate)
+use anyhow::Result;
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::commands::meta::utils::create_tierlist_image;
+use crate::commands::rta_core::models::TierListData;
+use crate::commands::shared::render::encode_png;
+
+/// Clé : (level, createDate)
+type TierListKey = (i32, String);
+
+static TIERLIST_IMAGE_CACHE: Lazy<Cache<TierListKey, Arc<Vec<u8>>>> = Lazy::new(|| {
+    Cache::builder()
+        .time_to_live(Duration::from_secs(48 * 3600))
+        .max_capacity(16)
+        .build()
+});
+
+/// PNG of the tier list, rendered once per (level, createDate).
+pub async fn get_tierlist_image(data: &TierListData) -> Result<Arc<Vec<u8>>> {
+    let key = (i32::from(data.level), data.date.clone().unwrap_or_default());
+    if let Some(bytes) = TIERLIST_IMAGE_CACHE.get(&key).await {
+        return Ok(bytes);
+    }
+
+    let (image, complete) = create_tierlist_image(data).await?;
+    let bytes = Arc::new(encode_png(image).await?);
+
+    // Pas de cache tant que des portraits manquent : ils arrivent en arrière-plan
+    if complete {
+        TIERLIST_IMAGE_CACHE.insert(key, bytes.clone()).await;
+    }
+
+    Ok(bytes)
+}
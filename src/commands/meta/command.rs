@@ -1,29 +1,64 @@
+use crate::commands::meta::cache::get_tierlist_image;
 use crate::commands::meta::utils::{
-    build_tier_line, create_loading_meta_embed, create_meta_embed, create_meta_level_buttons,
+    create_loading_meta_embed, create_meta_embed, create_meta_level_buttons, create_tierlist_image,
+    format_missing_units, missing_meta_units,
 };
-use crate::commands::player_stats::utils::get_mob_emoji_collection;
-use crate::commands::rta_core::utils::get_tierlist_data;
+use crate::commands::player_box::utils::resolve_box;
+use crate::commands::rta_core::models::{Monster, TierListData};
+use crate::commands::rta_core::utils::{filter_monster, get_tierlist_data};
 use crate::commands::shared::embed_error_handling::{
     create_embed_error, schedule_message_deletion,
 };
 use crate::commands::shared::logs::{get_server_name, send_log};
 use crate::commands::shared::models::LoggerDocument;
+use crate::commands::shared::render::encode_png;
 use crate::{Data, API_TOKEN, GUARDIAN_EMOJI_ID, PUNISHER_EMOJI_ID};
 
 use poise::serenity_prelude as serenity;
 use poise::CreateReply;
 use serenity::{
-    builder::EditInteractionResponse, CreateInteractionResponse, CreateInteractionResponseMessage,
-    Error,
+    builder::{EditAttachments, EditInteractionResponse},
+    Attachment, CreateAttachment, CreateEmbed, CreateInteractionResponse,
+    CreateInteractionResponseMessage, Error,
 };
 
 use std::time::Duration;
 
-/// 📂 Displays the current meta as a TierList
+/// Embed de la tierlist avec son image en pièce jointe. Avec un box, la
+/// tierlist est filtrée (image non mise en cache) et les manquants listés.
+async fn build_meta_message(
+    data: &TierListData,
+    box_monsters: Option<&[Monster]>,
+) -> anyhow::Result<(CreateEmbed, CreateAttachment)> {
+    let filename = format!("meta-{}.png", data.level);
+    let date = data.date.clone().unwrap_or_default();
+
+    let Some(monsters) = box_monsters else {
+        let image = get_tierlist_image(data).await?;
+        return Ok((
+            create_meta_embed(data.level.into(), &date, &filename, None),
+            CreateAttachment::bytes(image.as_slice(), filename),
+        ));
+    };
+
+    let (image, _) = create_tierlist_image(&filter_monster(data, monsters)).await?;
+    let missing = format_missing_units(&missing_meta_units(data, monsters));
+
+    Ok((
+        create_meta_embed(data.level.into(), &date, &filename, Some(&missing)),
+        CreateAttachment::bytes(encode_png(image).await?, filename),
+    ))
+}
+
+/// 📂 Displays the current meta as a TierList, optionally filtered to your box
 ///
-/// Usage: `/meta`
+/// Usage: `/get_meta [file] [my_box]`
 #[poise::command(slash_command)]
-pub async fn get_meta(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+pub async fn get_meta(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Account JSON: only show the monsters you own"] file: Option<Attachment>,
+    #[description = "Only show the monsters of your saved box (/box save)"] my_box: Option<bool>,
+) -> Result<(), Error> {
     // Évite le timeout de 3 s
     ctx.defer().await?;
 
@@ -39,29 +74,31 @@ pub async fn get_meta(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result
         })?
     };
 
-    // Niveau d'API par défaut (1 = G1-G2)
-    let mut current_level = 1;
-
-    // 😃 Récupération de la collection d'emojis
-    let collection = match get_mob_emoji_collection().await {
-        Ok(c) => c,
-        Err(_) => {
-            let err_msg =
-                "Impossible de récupérer les emojis des monstres (DB error). Réessaie plus tard.";
-            let reply = ctx.send(create_embed_error(err_msg)).await?;
-            schedule_message_deletion(reply, ctx).await?;
-            send_log(LoggerDocument::new(
-                &ctx.author().name,
-                "get_meta",
-                &get_server_name(&ctx).await?,
-                false,
-                chrono::Utc::now().timestamp(),
-            ))
-            .await?;
-            return Ok(());
+    // 📦 Box du joueur (optionnel)
+    let box_monsters = if file.is_some() || my_box.unwrap_or(false) {
+        match resolve_box(file.as_ref(), user_id.get()).await {
+            Ok(resolved) => Some(resolved.monsters),
+            Err(err_msg) => {
+                let reply = ctx.send(create_embed_error(&err_msg)).await?;
+                schedule_message_deletion(reply, ctx).await?;
+                send_log(LoggerDocument::new(
+                    &ctx.author().name,
+                    "get_meta",
+                    &get_server_name(&ctx).await?,
+                    false,
+                    chrono::Utc::now().timestamp(),
+                ))
+                .await?;
+                return Ok(());
+            }
         }
+    } else {
+        None
     };
 
+    // Niveau d'API par défaut (1 = G1-G2)
+    let mut current_level = 1;
+
     // 📥 Récupération de la tierlist initiale
     let tierlist_data = match get_tierlist_data(current_level, &token).await {
         Ok(data) => data,
@@ -81,32 +118,34 @@ pub async fn get_meta(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result
         }
     };
 
-    // 🔁 Construire les lignes d'emojis pour chaque tier
-    let sss_line = build_tier_line(&tierlist_data.sss_monster, &collection).await;
-    let ss_line = build_tier_line(&tierlist_data.ss_monster, &collection).await;
-    let s_line = build_tier_line(&tierlist_data.s_monster, &collection).await;
-    let a_line = build_tier_line(&tierlist_data.a_monster, &collection).await;
-    let b_line = build_tier_line(&tierlist_data.b_monster, &collection).await;
-    let date = tierlist_data.date.clone().unwrap_or_default();
-
     // Emojis custom pour les boutons (mêmes que /get_replays)
     let guardian_id: u64 = GUARDIAN_EMOJI_ID.lock().unwrap().parse().unwrap();
     let punisher_id: u64 = PUNISHER_EMOJI_ID.lock().unwrap().parse().unwrap();
 
-    // 🧩 Construction de l'embed via utils
-    let embed = create_meta_embed(
-        tierlist_data.level.into(),
-        &sss_line,
-        &ss_line,
-        &s_line,
-        &a_line,
-        &b_line,
-        &date,
-    );
+    // 🧩 Rendu de l'image de la tierlist
+    let (embed, attachment) =
+        match build_meta_message(&tierlist_data, box_monsters.as_deref()).await {
+            Ok(message) => message,
+            Err(e) => {
+                let err_msg = format!("Impossible de générer l'image de la tierlist : {}", e);
+                let reply = ctx.send(create_embed_error(&err_msg)).await?;
+                schedule_message_deletion(reply, ctx).await?;
+                send_log(LoggerDocument::new(
+                    &ctx.author().name,
+                    "get_meta",
+                    &get_server_name(&ctx).await?,
+                    false,
+                    chrono::Utc::now().timestamp(),
+                ))
+                .await?;
+                return Ok(());
+            }
+        };
 
     let reply = ctx
         .send(CreateReply {
             embeds: vec![embed],
+            attachments: vec![attachment],
             components: Some(vec![create_meta_level_buttons(
                 guardian_id,
                 punisher_id,
@@ -179,23 +218,25 @@ pub async fn get_meta(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result
             }
         };
 
-        // Recalcul des lignes d'emojis
-        let new_sss_line = build_tier_line(&new_tierlist_data.sss_monster, &collection).await;
-        let new_ss_line = build_tier_line(&new_tierlist_data.ss_monster, &collection).await;
-        let new_s_line = build_tier_line(&new_tierlist_data.s_monster, &collection).await;
-        let new_a_line = build_tier_line(&new_tierlist_data.a_monster, &collection).await;
-        let new_b_line = build_tier_line(&new_tierlist_data.b_monster, &collection).await;
-        let new_date = new_tierlist_data.date.clone().unwrap_or_default();
-
-        let final_embed = create_meta_embed(
-            new_tierlist_data.level.into(),
-            &new_sss_line,
-            &new_ss_line,
-            &new_s_line,
-            &new_a_line,
-            &new_b_line,
-            &new_date,
-        );
+        let (final_embed, new_attachment) =
+            match build_meta_message(&new_tierlist_data, box_monsters.as_deref()).await {
+                Ok(message) => message,
+                Err(e) => {
+                    interaction
+                        .edit_response(
+                            &ctx.serenity_context.http,
+                            EditInteractionResponse::new()
+                                .content(format!(
+                                    "❌ Impossible de générer l'image de la tierlist : {}",
+                                    e
+                                ))
+                                .components(vec![])
+                                .embeds(vec![]),
+                        )
+                        .await?;
+                    continue;
+                }
+            };
 
         interaction
             .edit_response(
@@ -207,7 +248,8 @@ pub async fn get_meta(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result
                         punisher_id,
                         current_level,
                         false, // Boutons réactivés
-                    )]),
+                    )])
+                    .attachments(EditAttachments::new().add(new_attachment)),
             )
             .await?;
     }
@@ -1,37 +1,204 @@
+use ab_glyph::PxScale;
+use anyhow::Result;
+use image::{GenericImage, ImageBuffer, Rgba, RgbaImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
 use poise::serenity_prelude as serenity;
 use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+use std::collections::HashSet;
 
-use mongodb::bson::Document;
-use mongodb::Collection;
-
-use crate::commands::rta_core::models::MonsterStat;
-use crate::commands::rta_core::utils::get_emoji_from_id;
-
-/// Construit une ligne d'emojis pour un tier donné
-pub async fn build_tier_line(
-    monsters: &[MonsterStat],
-    collection: &Collection<Document>,
-) -> String {
-    let mut line = String::new();
-
-    for m in monsters {
-        let emoji = get_emoji_from_id(collection, m.monster_id)
-            .await
-            .unwrap_or_default();
-
-        if !emoji.is_empty() {
-            if !line.is_empty() {
-                line.push(' ');
-            }
-            line.push_str(&emoji);
+use crate::commands::player_stats::utils::get_comid_to_image_map;
+use crate::commands::replays::utils::monster_name_by_id;
+use crate::commands::rta_core::models::{Monster, MonsterStat, TierListData};
+use crate::commands::shared::portraits::{get_portrait, has_portrait, placeholder_portrait};
+use crate::commands::shared::render::{banner_font, draw_bold_text_mut, text_width};
+
+const TIER_PORTRAIT: u32 = 80;
+const TIER_GAP: u32 = 6;
+const TIER_LABEL_WIDTH: u32 = 100;
+const TIER_PER_LINE: u32 = 10;
+const TIER_HEADER_HEIGHT: u32 = 60;
+
+/// (nom, couleur) des tiers, du meilleur au moins bon
+const TIERS: [(&str, Rgba<u8>); 6] = [
+    ("SSS", Rgba([255, 127, 127, 255])),
+    ("SS", Rgba([255, 191, 127, 255])),
+    ("S", Rgba([255, 223, 127, 255])),
+    ("A", Rgba([255, 255, 127, 255])),
+    ("B", Rgba([191, 255, 127, 255])),
+    ("C", Rgba([127, 255, 127, 255])),
+];
+
+/// Nombre de monstres manquants affichés dans l'embed
+pub const MISSING_UNITS_SHOWN: usize = 15;
+
+/// Monstres de chaque tier, dans l'ordre de `TIERS`
+fn tier_rows(data: &TierListData) -> [&[MonsterStat]; 6] {
+    [
+        &data.sss_monster,
+        &data.ss_monster,
+        &data.s_monster,
+        &data.a_monster,
+        &data.b_monster,
+        &data.c_monster,
+    ]
+}
+
+fn format_pick_count(picks: u32) -> String {
+    if picks >= 1000 {
+        format!("{:.1}k", picks as f64 / 1000.0)
+    } else {
+        picks.to_string()
+    }
+}
+
+/// Draws the tier list, one row per tier with the pick count over each
+/// portrait. The flag is `false` when a portrait was not on disk yet and
+/// the placeholder was drawn instead.
+pub async fn create_tierlist_image(data: &TierListData) -> Result<(RgbaImage, bool)> {
+    let tiers = tier_rows(data);
+
+    let cell = TIER_PORTRAIT + TIER_GAP;
+    let row_height = |count: usize| {
+        let lines = (count as u32).div_ceil(TIER_PER_LINE).max(1);
+        lines * cell + TIER_GAP
+    };
+
+    let width = TIER_LABEL_WIDTH + TIER_PER_LINE * cell + TIER_GAP;
+    let height = TIER_HEADER_HEIGHT + tiers.iter().map(|t| row_height(t.len())).sum::<u32>();
+
+    let mut canvas: RgbaImage = ImageBuffer::from_pixel(width, height, Rgba([32, 34, 37, 255]));
+    let font = banner_font();
+    let white = Rgba([255, 255, 255, 255]);
+    let black = Rgba([0, 0, 0, 255]);
+
+    draw_bold_text_mut(
+        &mut canvas,
+        white,
+        12,
+        14,
+        PxScale::from(30.0),
+        font,
+        &format!(
+            "RTA Meta Tier List - {} - {}",
+            level_to_label(data.level.into()),
+            data.date.clone().unwrap_or_default()
+        ),
+    );
+
+    let img_map = get_comid_to_image_map();
+    let mut complete = true;
+    let label_scale = PxScale::from(36.0);
+    let pick_scale = PxScale::from(18.0);
+    let mut y = TIER_HEADER_HEIGHT;
+
+    for ((name, color), monsters) in TIERS.iter().zip(tiers) {
+        let h = row_height(monsters.len());
+
+        draw_filled_rect_mut(
+            &mut canvas,
+            Rect::at(0, y as i32).of_size(TIER_LABEL_WIDTH, h - 2),
+            *color,
+        );
+        let label_x = (TIER_LABEL_WIDTH as f32 - text_width(font, label_scale, name)) / 2.0;
+        draw_bold_text_mut(
+            &mut canvas,
+            black,
+            label_x as i32,
+            (y + (h - 36) / 2) as i32,
+            label_scale,
+            font,
+            name,
+        );
+
+        for (i, m) in monsters.iter().enumerate() {
+            let x = TIER_LABEL_WIDTH + TIER_GAP + (i as u32 % TIER_PER_LINE) * cell;
+            let py = y + TIER_GAP + (i as u32 / TIER_PER_LINE) * cell;
+
+            let portrait = match img_map.get(&(m.monster_id as i32)) {
+                Some(filename) => {
+                    complete &= has_portrait(filename);
+                    get_portrait(filename).await
+                }
+                None => placeholder_portrait().clone(),
+            };
+            let icon = portrait.resize_exact(
+                TIER_PORTRAIT,
+                TIER_PORTRAIT,
+                image::imageops::FilterType::Triangle,
+            );
+            canvas.copy_from(&icon.to_rgba8(), x, py)?;
+
+            // Nombre de picks en bas du portrait
+            let picks = format_pick_count(m.pick_total);
+            let picks_w = text_width(font, pick_scale, &picks) as u32 + 8;
+            draw_filled_rect_mut(
+                &mut canvas,
+                Rect::at(
+                    (x + TIER_PORTRAIT - picks_w) as i32,
+                    (py + TIER_PORTRAIT - 20) as i32,
+                )
+                .of_size(picks_w, 20),
+                Rgba([20, 20, 20, 255]),
+            );
+            draw_bold_text_mut(
+                &mut canvas,
+                white,
+                (x + TIER_PORTRAIT - picks_w + 4) as i32,
+                (py + TIER_PORTRAIT - 20) as i32,
+                pick_scale,
+                font,
+                &picks,
+            );
         }
+
+        y += h;
     }
 
-    if line.is_empty() {
-        ":no_entry_sign:".to_string()
-    } else {
-        line
+    Ok((canvas, complete))
+}
+
+/// Tier list monsters missing from the box, best tier first then by picks.
+pub fn missing_meta_units<'a>(
+    data: &'a TierListData,
+    monsters: &[Monster],
+) -> Vec<(&'static str, &'a MonsterStat)> {
+    let owned: HashSet<u32> = monsters.iter().map(|m| m.unit_master_id).collect();
+
+    let mut missing = Vec::new();
+    for ((name, _), tier) in TIERS.iter().zip(tier_rows(data)) {
+        let mut tier_missing: Vec<&MonsterStat> = tier
+            .iter()
+            .filter(|m| !owned.contains(&m.monster_id))
+            .collect();
+        tier_missing.sort_by_key(|m| std::cmp::Reverse(m.pick_total));
+        missing.extend(tier_missing.into_iter().map(|m| (*name, m)));
     }
+    missing
+}
+
+pub fn format_missing_units(missing: &[(&str, &MonsterStat)]) -> String {
+    if missing.is_empty() {
+        return "None, you own every monster of the tier list 🎉".to_string();
+    }
+
+    let mut lines: Vec<String> = missing
+        .iter()
+        .take(MISSING_UNITS_SHOWN)
+        .map(|(tier, m)| {
+            format!(
+                "**{}** {} ({} picks)",
+                tier,
+                monster_name_by_id(m.monster_id),
+                format_pick_count(m.pick_total)
+            )
+        })
+        .collect();
+
+    if missing.len() > MISSING_UNITS_SHOWN {
+        lines.push(format!("…and {} more", missing.len() - MISSING_UNITS_SHOWN));
+    }
+    lines.join("\n")
 }
 
 fn level_to_label(level: i32) -> &'static str {
@@ -43,35 +210,39 @@ fn level_to_label(level: i32) -> &'static str {
     }
 }
 
-/// Embed principal de la méta
+/// Embed principal de la méta, l'image de la tierlist est en pièce jointe.
+/// `missing_units` : liste déjà formatée quand la tierlist est filtrée sur un box.
 pub fn create_meta_embed(
     api_level: i32,
-    sss_line: &str,
-    ss_line: &str,
-    s_line: &str,
-    a_line: &str,
-    b_line: &str,
     date: &str,
+    image_filename: &str,
+    missing_units: Option<&str>,
 ) -> CreateEmbed {
     let thumbnail = "https://raw.githubusercontent.com/B4tiste/landing-page-bot/refs/heads/main/src/assets/images/old_bot_logo.gif";
     let level_label = level_to_label(api_level);
 
-    CreateEmbed::default()
-        .title("📊 RTA Meta Tier List")
+    let mut embed = CreateEmbed::default()
+        .title(if missing_units.is_some() {
+            "📊 RTA Meta Tier List (your box)"
+        } else {
+            "📊 RTA Meta Tier List"
+        })
         .color(serenity::Colour::from_rgb(255, 255, 255))
         .thumbnail(thumbnail)
         .description(format!(
-            "Displaying the current meta for rank **{}**.\n\nLast updated: **{}**",
+            "Displaying the current meta for rank **{}**.\n\nLast updated: **{}**\n*Number on each monster: picks*",
             level_label, date
         ))
-        .field("SSS", sss_line, false)
-        .field("SS", ss_line, false)
-        .field("S", s_line, false)
-        .field("A", a_line, false)
-        .field("B", b_line, false)
+        .image(format!("attachment://{}", image_filename))
         .footer(CreateEmbedFooter::new(
             "Data is gathered from m.swranking.com",
-        ))
+        ));
+
+    if let Some(missing_units) = missing_units {
+        embed = embed.field("Meta units you're missing", missing_units, false);
+    }
+
+    embed
 }
 
 /// Embed de chargement quand on change de niveau
@@ -1,11 +1,17 @@
 // pub mod duo_stats;
+pub mod alias;
 pub mod best_pve_teams;
+pub mod guild_ranking;
+pub mod h2h;
 pub mod help;
 pub mod how_to_build;
 pub mod leaderboard;
 pub mod meta;
 pub mod mob_stats;
+pub mod my_target;
 pub mod mystats;
+pub mod player_analysis;
+pub mod player_box;
 pub mod player_names;
 pub mod player_stats;
 pub mod ranks;
@@ -18,3 +24,4 @@ pub mod suggestion;
 pub mod support;
 pub mod unregister;
 pub mod upload_json;
+pub mod watch;
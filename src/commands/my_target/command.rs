@@ -0,0 +1,183 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::Error;
+
+use crate::commands::my_target::models::{RankTarget, TargetRank};
+use crate::commands::my_target::utils::{
+    create_target_chart, create_target_embed, get_rank_target, score_history, set_rank_target,
+    SCORE_HISTORY_MATCHES,
+};
+use crate::commands::player_stats::utils::{
+    get_all_lucksack_player_matches, get_lucksack_player_summary, get_lucksack_season_numbers,
+};
+use crate::commands::ranks::history::{estimate_end_of_season, get_season_history};
+use crate::commands::ranks::prediction::{
+    fetch_prediction_cutoffs, prediction_source, PredictionSource,
+};
+use crate::commands::ranks::utils::upcoming_season_end;
+use crate::commands::ranks::utils::{fetch_live_cutoffs, CutoffValues};
+use crate::commands::register::utils::get_user_link;
+use crate::commands::shared::embed_error_handling::{
+    create_embed_error, schedule_message_deletion,
+};
+use crate::commands::shared::logs::{get_server_name, send_log};
+use crate::commands::shared::models::LoggerDocument;
+use crate::Data;
+
+fn db_error(e: anyhow::Error) -> Error {
+    Error::from(std::io::Error::other(format!("DB error: {e}")))
+}
+
+/// Sans objectif, le graphique suit le prochain seuil au-dessus du joueur
+fn next_rank_above(score: i32, live: CutoffValues) -> TargetRank {
+    const RANKS: [TargetRank; 5] = [
+        TargetRank::P2,
+        TargetRank::P3,
+        TargetRank::G1,
+        TargetRank::G2,
+        TargetRank::G3,
+    ];
+    RANKS
+        .into_iter()
+        .find(|r| score < live.as_array()[r.index()])
+        .unwrap_or(TargetRank::G3)
+}
+
+/// 📂 Shows your gap to each rank cutoff and sets your target rank (register first)
+///
+/// Usage: `/my_target [target] [reminders]`
+#[poise::command(slash_command)]
+pub async fn my_target(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Rank you are aiming for"] target: Option<TargetRank>,
+    #[description = "DM me when I fall below my target cutoff"] reminders: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let result: Result<(), Error> = async {
+        let discord_user_id = ctx.author().id.get();
+
+        let Some(link) = get_user_link(discord_user_id).await.map_err(db_error)? else {
+            ctx.say("❌ No linked account yet. Use `/register <account name>` first.")
+                .await?;
+            return Ok(());
+        };
+        let player_id = link
+            .get_i64("swrt_player_id")
+            .map_err(|_| Error::from(std::io::Error::other("Invalid stored player_id in DB")))?;
+
+        let mut rank_target = get_rank_target(discord_user_id)
+            .await
+            .map_err(db_error)?;
+
+        if target.is_some() || reminders.is_some() {
+            let Some(chosen) = target.or(rank_target.as_ref().map(|t| t.target)) else {
+                let reply = ctx
+                    .send(create_embed_error(
+                        "❌ Choose a target rank first, e.g. `/my_target target:G1 reminders:True`.",
+                    ))
+                    .await?;
+                schedule_message_deletion(reply, ctx).await?;
+                return Ok(());
+            };
+
+            let updated = RankTarget {
+                discord_user_id: discord_user_id as i64,
+                target: chosen,
+                reminders: reminders
+                    .or(rank_target.as_ref().map(|t| t.reminders))
+                    .unwrap_or(false),
+                below_notified: false,
+                updated_at: chrono::Utc::now().timestamp(),
+            };
+            set_rank_target(updated.clone()).await.map_err(db_error)?;
+            rank_target = Some(updated);
+        }
+
+        let live = match fetch_live_cutoffs().await {
+            Ok(live) => live,
+            Err(_) => {
+                let reply = ctx
+                    .send(create_embed_error("Unable to retrieve ELO information."))
+                    .await?;
+                schedule_message_deletion(reply, ctx).await?;
+                return Ok(());
+            }
+        };
+
+        let season = get_lucksack_season_numbers()
+            .await
+            .ok()
+            .and_then(|s| s.first().copied())
+            .ok_or_else(|| Error::from(std::io::Error::other("No valid season number found")))?;
+
+        let summary = get_lucksack_player_summary(player_id, season)
+            .await
+            .map_err(|e| {
+                Error::from(std::io::Error::other(format!(
+                    "Error retrieving player summary: {}",
+                    e
+                )))
+            })?;
+
+        // Projection : prédiction swrta.top si activée, sinon l'estimation SWbox
+        let history = get_season_history(season).await.unwrap_or_default();
+        let prediction_url = format!("<{}>", prediction_source().source_url());
+        let projected = match fetch_prediction_cutoffs().await {
+            Some(p) => Some((p, prediction_url.as_str())),
+            None => estimate_end_of_season(&history, upcoming_season_end())
+                .map(|e| (e, "SWbox end-of-season estimate")),
+        };
+
+        let mut embed = create_target_embed(&summary, live, projected, rank_target.as_ref());
+
+        let matches = get_all_lucksack_player_matches(
+            player_id,
+            season,
+            summary.summary.total_matches.max(0) as usize,
+            SCORE_HISTORY_MATCHES,
+        )
+        .await
+        .unwrap_or_default();
+        let scores = score_history(&matches);
+
+        let mut attachments = vec![];
+        if scores.len() >= 2 {
+            let chart_rank = rank_target
+                .as_ref()
+                .map(|t| t.target)
+                .unwrap_or_else(|| next_rank_above(summary.summary.current_score, live));
+            let file_name = "my-target.png";
+            match create_target_chart(&summary.user_info.username, &scores, &history, chart_rank)
+                .await
+            {
+                Ok(bytes) => {
+                    attachments.push(serenity::CreateAttachment::bytes(bytes, file_name));
+                    embed = embed.image(format!("attachment://{file_name}"));
+                }
+                Err(e) => eprintln!("Failed to render target chart: {e:?}"),
+            }
+        }
+
+        ctx.send(CreateReply {
+            embeds: vec![embed],
+            attachments,
+            ..Default::default()
+        })
+        .await?;
+
+        Ok(())
+    }
+    .await;
+
+    send_log(LoggerDocument::new(
+        &ctx.author().name,
+        "my_target",
+        &get_server_name(&ctx).await?,
+        result.is_ok(),
+        chrono::Utc::now().timestamp(),
+    ))
+    .await?;
+
+    result
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// Rang visé, dans l'ordre de `CutoffValues` (P2 → G3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter)]
+pub enum TargetRank {
+    P2,
+    P3,
+    G1,
+    G2,
+    G3,
+}
+
+impl TargetRank {
+    /// Index in `CutoffValues::as_array()`
+    pub fn index(self) -> usize {
+        match self {
+            TargetRank::P2 => 0,
+            TargetRank::P3 => 1,
+            TargetRank::G1 => 2,
+            TargetRank::G2 => 3,
+            TargetRank::G3 => 4,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TargetRank::P2 => "P2",
+            TargetRank::P3 => "P3",
+            TargetRank::G1 => "G1",
+            TargetRank::G2 => "G2",
+            TargetRank::G3 => "G3",
+        }
+    }
+}
+
+/// Objectif de rang d'un utilisateur enregistré (collection `rank-targets`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankTarget {
+    pub discord_user_id: i64,
+    pub target: TargetRank,
+    /// Rappel en MP quand le joueur passe sous le seuil visé
+    pub reminders: bool,
+    /// Évite de renvoyer un rappel tant que le joueur reste sous le seuil
+    pub below_notified: bool,
+    pub updated_at: i64,
+}
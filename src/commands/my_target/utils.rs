@@ -0,0 +1,291 @@
+use anyhow::Result;
+use futures::stream::TryStreamExt;
+use image::Rgba;
+use mongodb::bson::doc;
+use mongodb::Collection;
+use poise::serenity_prelude as serenity;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter, CreateMessage};
+use serenity::Context as SerenityContext;
+
+use crate::commands::my_target::models::{RankTarget, TargetRank};
+use crate::commands::player_stats::utils::{
+    get_lucksack_player_summary, get_lucksack_season_numbers, LucksackMatch, LucksackPlayerSummary,
+};
+use crate::commands::ranks::history::{cutoff_points, CutoffRecord, CUTOFF_COLORS};
+use crate::commands::ranks::utils::{fetch_live_cutoffs, CutoffValues};
+use crate::commands::register::utils::get_user_link;
+use crate::commands::shared::chart::{render_line_chart, ChartSeries};
+use crate::commands::shared::clients::mongo_client;
+use crate::commands::shared::render::encode_png;
+
+/// Nombre de matchs récents utilisés pour la courbe du joueur
+pub const SCORE_HISTORY_MATCHES: usize = 300;
+
+/// Délai entre deux appels lucksack pendant la vérification des rappels
+const REMINDER_DELAY_MS: u64 = 1500;
+
+fn rank_targets_collection() -> Result<Collection<RankTarget>> {
+    Ok(mongo_client()?
+        .database("bot-swbox-db")
+        .collection::<RankTarget>("rank-targets"))
+}
+
+/* ------------------ Target storage ------------------ */
+
+pub async fn get_rank_target(discord_user_id: u64) -> Result<Option<RankTarget>> {
+    let col = rank_targets_collection()?;
+    Ok(col
+        .find_one(doc! { "discord_user_id": discord_user_id as i64 })
+        .await?)
+}
+
+/// Replaces the user's target. The reminder state starts over.
+pub async fn set_rank_target(target: RankTarget) -> Result<()> {
+    let col = rank_targets_collection()?;
+    col.delete_many(doc! { "discord_user_id": target.discord_user_id })
+        .await?;
+    col.insert_one(target).await?;
+    Ok(())
+}
+
+async fn set_below_notified(discord_user_id: i64, below_notified: bool) -> Result<()> {
+    let col = rank_targets_collection()?;
+    col.update_one(
+        doc! { "discord_user_id": discord_user_id },
+        doc! { "$set": { "below_notified": below_notified } },
+    )
+    .await?;
+    Ok(())
+}
+
+/* ------------------ Embed & chart ------------------ */
+
+fn format_gap(score: i32, cutoff: i32) -> String {
+    let gap = score - cutoff;
+    if gap >= 0 {
+        format!("✅ +{}", gap)
+    } else {
+        format!("❌ {}", gap)
+    }
+}
+
+/// `projected` is the projected cutoffs with the name of their source.
+pub fn create_target_embed(
+    summary: &LucksackPlayerSummary,
+    live: CutoffValues,
+    projected: Option<(CutoffValues, &str)>,
+    target: Option<&RankTarget>,
+) -> CreateEmbed {
+    let info = &summary.user_info;
+    let score = summary.summary.current_score;
+    let live_values = live.as_array();
+    let projected_values = projected.map(|(p, _)| p.as_array());
+
+    let mut description = format!(
+        "Current score : **{}** (rank #{})\n\n",
+        score, summary.summary.current_rank
+    );
+
+    for (i, (emotes, cutoff)) in live.to_emote_pairs().into_iter().enumerate() {
+        let marker = if target.is_some_and(|t| t.target.index() == i) {
+            "🎯 "
+        } else {
+            ""
+        };
+        description.push_str(&format!(
+            "{marker}{emotes} : {cutoff} → {}",
+            format_gap(score, live_values[i])
+        ));
+        if let Some(projected) = projected_values {
+            description.push_str(&format!(
+                " · proj. `{}` ({})",
+                projected[i],
+                format_gap(score, projected[i])
+            ));
+        }
+        description.push('\n');
+    }
+
+    if let Some((_, source)) = projected {
+        description.push_str(&format!("\n*Projection : {source}*\n"));
+    }
+
+    let reminders = match target {
+        Some(t) if t.reminders => format!(
+            "🔔 DM when you fall below the **{}** cutoff",
+            t.target.label()
+        ),
+        Some(t) => format!(
+            "🔕 Off (target **{}**), enable with `reminders: True`",
+            t.target.label()
+        ),
+        None => "No target yet, set one with `/my_target target:<rank>`".to_string(),
+    };
+
+    CreateEmbed::default()
+        .title(format!(
+            ":flag_{}: {} - Rank targets",
+            info.country.to_lowercase(),
+            info.username
+        ))
+        .url(format!("https://lucksack.gg/player/{}", info.player_id))
+        .thumbnail(info.image.clone())
+        .color(serenity::Colour::from_rgb(0, 0, 255))
+        .description(description)
+        .field("Reminders", reminders, false)
+        .footer(CreateEmbedFooter::new(
+            "Data is gathered from m.swranking.com and lucksack.gg",
+        ))
+}
+
+/// Player score after each match, oldest first.
+pub fn score_history(matches: &[LucksackMatch]) -> Vec<(i64, i32)> {
+    let mut points: Vec<(i64, i32)> = matches
+        .iter()
+        .filter_map(|m| {
+            chrono::DateTime::parse_from_rfc3339(&m.battle_time)
+                .ok()
+                .map(|d| (d.timestamp(), m.my_score))
+        })
+        .collect();
+    points.sort_by_key(|(t, _)| *t);
+    points
+}
+
+/// Player score against the cutoff of `rank`, over the period covered by the matches.
+pub async fn create_target_chart(
+    username: &str,
+    scores: &[(i64, i32)],
+    history: &[CutoffRecord],
+    rank: TargetRank,
+) -> Result<Vec<u8>> {
+    let since = scores.first().map(|(t, _)| *t).unwrap_or_default();
+    let cutoff: Vec<(i64, i32)> = cutoff_points(history, rank.index())
+        .into_iter()
+        .filter(|(t, _)| *t >= since)
+        .collect();
+
+    let series = [
+        ChartSeries {
+            label: Some(username.to_string()),
+            color: Rgba([255, 255, 255, 255]),
+            points: scores.to_vec(),
+            dashed: false,
+        },
+        ChartSeries {
+            label: Some(format!("{} cutoff", rank.label())),
+            color: CUTOFF_COLORS[rank.index()],
+            points: cutoff,
+            dashed: false,
+        },
+    ];
+
+    encode_png(render_line_chart(
+        &format!("Score vs {} cutoff", rank.label()),
+        &series,
+    )?)
+    .await
+}
+
+/* ------------------ DM reminders ------------------ */
+
+fn create_reminder_embed(
+    summary: &LucksackPlayerSummary,
+    target: TargetRank,
+    cutoff: i32,
+) -> CreateEmbed {
+    let score = summary.summary.current_score;
+    CreateEmbed::default()
+        .title(format!("⚠️ You fell below the {} cutoff", target.label()))
+        .color(serenity::Colour::from_rgb(240, 71, 71))
+        .description(format!(
+            "**{}** : {} points, the {} cutoff is **{}** ({} points to go).\n\nUse `/my_target` for details, or `/my_target reminders: False` to stop these messages.",
+            summary.user_info.username,
+            score,
+            target.label(),
+            cutoff,
+            cutoff - score
+        ))
+        .footer(CreateEmbedFooter::new(
+            "Data is gathered from m.swranking.com and lucksack.gg",
+        ))
+}
+
+/// Sends one DM per drop below the target cutoff; the reminder is re-armed
+/// once the player is back above it.
+pub async fn check_target_reminders(serenity_ctx: &SerenityContext) -> Result<()> {
+    let col = rank_targets_collection()?;
+    let targets: Vec<RankTarget> = col
+        .find(doc! { "reminders": true })
+        .await?
+        .try_collect()
+        .await?;
+    if targets.is_empty() {
+        return Ok(());
+    }
+
+    let live = fetch_live_cutoffs()
+        .await
+        .map_err(anyhow::Error::msg)?
+        .as_array();
+    let season = get_lucksack_season_numbers()
+        .await?
+        .first()
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("No lucksack season found"))?;
+
+    for target in targets {
+        // Une erreur sur un utilisateur ne doit pas bloquer les rappels des suivants
+        let link = match get_user_link(target.discord_user_id as u64).await {
+            Ok(link) => link,
+            Err(e) => {
+                eprintln!(
+                    "Rank targets: failed to read the link of {}: {e:?}",
+                    target.discord_user_id
+                );
+                continue;
+            }
+        };
+        let Some(player_id) = link.and_then(|d| d.get_i64("swrt_player_id").ok()) else {
+            continue;
+        };
+
+        let summary = match get_lucksack_player_summary(player_id, season).await {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Rank targets: failed to fetch player {player_id}: {e:?}");
+                continue;
+            }
+        };
+
+        let cutoff = live[target.target.index()];
+        let below = summary.summary.current_score < cutoff;
+
+        if below && !target.below_notified {
+            // Pas de panic si l'utilisateur bloque les MP
+            let _ = serenity::UserId::new(target.discord_user_id as u64)
+                .direct_message(
+                    &serenity_ctx.http,
+                    CreateMessage::new().embed(create_reminder_embed(
+                        &summary,
+                        target.target,
+                        cutoff,
+                    )),
+                )
+                .await;
+        }
+
+        if below != target.below_notified {
+            if let Err(e) = set_below_notified(target.discord_user_id, below).await {
+                eprintln!(
+                    "Rank targets: failed to update {}: {e:?}",
+                    target.discord_user_id
+                );
+            }
+        }
+
+        tokio::time::sleep(std::time::Duration::from_millis(REMINDER_DELAY_MS)).await;
+    }
+
+    Ok(())
+}
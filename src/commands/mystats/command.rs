@@ -3,7 +3,7 @@ use poise::serenity_prelude::Error;
 use crate::commands::player_stats::command::show_player_stats;
 use crate::commands::register::utils::get_user_link;
 use crate::commands::shared::logs::{get_server_name, send_log};
-use crate::commands::shared::models::LoggerDocument;
+use crate::commands::shared::models::{LoggerDocument, ReplayDisplay};
 use crate::Data;
 
 /// 📂 Displays your linked account stats (register first)
@@ -28,7 +28,7 @@ pub async fn mystats(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<
             .get_i64("swrt_player_id")
             .map_err(|_| Error::from(std::io::Error::other("Invalid stored player_id in DB")))?;
 
-        show_player_stats(&ctx, player_id, None).await
+        show_player_stats(&ctx, player_id, None, ReplayDisplay::default()).await
     }
     .await;
 
@@ -0,0 +1,161 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::builder::{EditAttachments, EditMessage};
+use serenity::Error;
+
+use crate::commands::player_analysis::models::MonsterRecord;
+use crate::commands::player_analysis::utils::{
+    analyze_matches, create_loading_analysis_embed, create_player_analysis_embed,
+    create_winrate_chart,
+};
+use crate::commands::player_stats::command::resolve_player_id;
+use crate::commands::player_stats::utils::{
+    get_all_lucksack_player_matches, get_lucksack_player_summary, get_lucksack_season_numbers,
+};
+use crate::commands::shared::embed_error_handling::{
+    create_embed_error, schedule_message_deletion,
+};
+use crate::commands::shared::logs::{get_server_name, send_log};
+use crate::commands::shared::models::LoggerDocument;
+use crate::Data;
+
+/// Limite de matchs analysés (lucksack pagine par 50)
+const MAX_ANALYZED_MATCHES: usize = 1000;
+
+/// 📂 Season matchup analysis of a player (win rates per monster, picks, bans)
+///
+/// Usage: /get_player_analysis
+#[poise::command(slash_command)]
+pub async fn get_player_analysis(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Player name, alias or @mention"] player_name: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let resolved = match resolve_player_id(&ctx, &player_name, "get_player_analysis").await? {
+        Some(r) => r,
+        None => return Ok(()),
+    };
+
+    let result = show_player_analysis(&ctx, resolved.player_id, resolved.reply_handle).await;
+
+    send_log(LoggerDocument::new(
+        &ctx.author().name,
+        "get_player_analysis",
+        &get_server_name(&ctx).await?,
+        result.is_ok(),
+        chrono::Utc::now().timestamp(),
+    ))
+    .await?;
+
+    result
+}
+
+async fn show_player_analysis<'a>(
+    ctx: &'a poise::ApplicationContext<'a, Data, Error>,
+    player_id: i64,
+    existing_reply: Option<poise::ReplyHandle<'a>>,
+) -> Result<(), Error> {
+    let season = match get_lucksack_season_numbers().await {
+        Ok(seasons) if !seasons.is_empty() => seasons[0],
+        Ok(_) => {
+            let reply = ctx
+                .send(create_embed_error("❌ No valid season number found."))
+                .await?;
+            schedule_message_deletion(reply, *ctx).await?;
+            return Ok(());
+        }
+        Err(e) => {
+            let msg = format!("❌ Failed to fetch seasons: {}", e);
+            let reply = ctx.send(create_embed_error(&msg)).await?;
+            schedule_message_deletion(reply, *ctx).await?;
+            return Ok(());
+        }
+    };
+
+    let summary = get_lucksack_player_summary(player_id, season)
+        .await
+        .map_err(|e| {
+            Error::from(std::io::Error::other(format!(
+                "Error retrieving player summary: {}",
+                e
+            )))
+        })?;
+
+    let total_matches = (summary.summary.total_matches.max(0) as usize).min(MAX_ANALYZED_MATCHES);
+    let loading = CreateReply {
+        content: Some(String::new()),
+        embeds: vec![create_loading_analysis_embed(
+            &summary.user_info.username,
+            total_matches,
+        )],
+        components: Some(vec![]),
+        ..Default::default()
+    };
+
+    let reply_handle = match existing_reply {
+        Some(handle) => {
+            handle
+                .edit(poise::Context::Application(*ctx), loading)
+                .await?;
+            handle
+        }
+        None => ctx.send(loading).await?,
+    };
+
+    let matches =
+        get_all_lucksack_player_matches(player_id, season, total_matches, MAX_ANALYZED_MATCHES)
+            .await
+            .map_err(|e| {
+                Error::from(std::io::Error::other(format!(
+                    "Error retrieving matches: {}",
+                    e
+                )))
+            })?;
+
+    let analysis = analyze_matches(&matches);
+
+    let top_monsters: Vec<&MonsterRecord> = analysis.own_monsters.iter().take(10).collect();
+    let attachment = if top_monsters.is_empty() {
+        None
+    } else {
+        create_winrate_chart(
+            &top_monsters,
+            &format!("{} - Win rate by monster", summary.user_info.username),
+        )
+        .await
+        .ok()
+        .map(|bytes| {
+            serenity::CreateAttachment::bytes(
+                bytes,
+                format!("analysis-{}-{}.png", player_id, season),
+            )
+        })
+    };
+
+    let embed = create_player_analysis_embed(
+        &summary,
+        &analysis,
+        season,
+        attachment.as_ref().map(|a| a.filename.as_str()),
+    )
+    .await;
+
+    let mut attachments = EditAttachments::new();
+    if let Some(attachment) = attachment {
+        attachments = attachments.add(attachment);
+    }
+
+    let mut message = reply_handle.message().await?.into_owned();
+    message
+        .edit(
+            &ctx.serenity_context.http,
+            EditMessage::new()
+                .content("")
+                .embeds(vec![embed])
+                .attachments(attachments),
+        )
+        .await?;
+
+    Ok(())
+}
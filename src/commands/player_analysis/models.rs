@@ -0,0 +1,49 @@
+/// Bilan d'un monstre (com2us_id) sur les matchs analysés
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MonsterRecord {
+    pub monster_id: i64,
+    pub played: u32,
+    pub wins: u32,
+}
+
+impl MonsterRecord {
+    pub fn win_rate(&self) -> f64 {
+        if self.played == 0 {
+            0.0
+        } else {
+            self.wins as f64 * 100.0 / self.played as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PickRecord {
+    pub played: u32,
+    pub wins: u32,
+}
+
+impl PickRecord {
+    pub fn win_rate(&self) -> f64 {
+        if self.played == 0 {
+            0.0
+        } else {
+            self.wins as f64 * 100.0 / self.played as f64
+        }
+    }
+}
+
+/// Statistiques de matchup d'un joueur sur une saison.
+/// `opponents[..].wins` = victoires du joueur face à ce monstre.
+#[derive(Debug, Clone, Default)]
+pub struct MatchupAnalysis {
+    pub total: PickRecord,
+    pub first_pick: PickRecord,
+    pub second_pick: PickRecord,
+    /// Triés par nombre de picks décroissant
+    pub own_monsters: Vec<MonsterRecord>,
+    pub opponent_monsters: Vec<MonsterRecord>,
+    /// Monstres du joueur bannis par l'adversaire : (monster_id, count)
+    pub banned_by_opponents: Vec<(i64, u32)>,
+    /// Monstres adverses bannis par le joueur : (monster_id, count)
+    pub player_bans: Vec<(i64, u32)>,
+}
@@ -0,0 +1,329 @@
+use ab_glyph::PxScale;
+use anyhow::Result;
+use image::{GenericImage, ImageBuffer, Rgba, RgbaImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
+use mongodb::Collection;
+use poise::serenity_prelude as serenity;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+use std::collections::HashMap;
+
+use crate::commands::player_analysis::models::{MatchupAnalysis, MonsterRecord, PickRecord};
+use crate::commands::player_stats::utils::{
+    get_comid_to_image_map, get_lucksack_monster_emoji, get_mob_emoji_collection, LucksackMatch,
+    LucksackPlayerSummary,
+};
+use crate::commands::shared::player_alias::player_alias;
+use crate::commands::shared::portraits::{get_portrait, placeholder_portrait};
+use crate::commands::shared::render::{banner_font, draw_bold_text_mut, encode_png};
+
+/// Nombre de matchs minimum pour classer un monstre adverse dans les matchups difficiles
+const MIN_GAMES_FOR_MATCHUP: u32 = 5;
+
+/* ------------------ Analysis ------------------ */
+
+pub fn analyze_matches(matches: &[LucksackMatch]) -> MatchupAnalysis {
+    let mut analysis = MatchupAnalysis::default();
+    let mut own: HashMap<i64, MonsterRecord> = HashMap::new();
+    let mut opponents: HashMap<i64, MonsterRecord> = HashMap::new();
+    let mut banned_by_opponents: HashMap<i64, u32> = HashMap::new();
+    let mut player_bans: HashMap<i64, u32> = HashMap::new();
+
+    for m in matches {
+        let win = m.won as u32;
+
+        record(&mut analysis.total, m.won);
+        if m.had_first_pick {
+            record(&mut analysis.first_pick, m.won);
+        } else {
+            record(&mut analysis.second_pick, m.won);
+        }
+
+        for &id in &m.my_monsters {
+            let entry = own.entry(id).or_insert_with(|| MonsterRecord {
+                monster_id: id,
+                ..Default::default()
+            });
+            entry.played += 1;
+            entry.wins += win;
+        }
+
+        for &id in &m.opponent_monsters {
+            let entry = opponents.entry(id).or_insert_with(|| MonsterRecord {
+                monster_id: id,
+                ..Default::default()
+            });
+            entry.played += 1;
+            entry.wins += win;
+        }
+
+        if m.my_bans != 0 {
+            *banned_by_opponents.entry(m.my_bans).or_default() += 1;
+        }
+        if m.opponent_bans != 0 {
+            *player_bans.entry(m.opponent_bans).or_default() += 1;
+        }
+    }
+
+    analysis.own_monsters = sorted_by_played(own);
+    analysis.opponent_monsters = sorted_by_played(opponents);
+    analysis.banned_by_opponents = sorted_counts(banned_by_opponents);
+    analysis.player_bans = sorted_counts(player_bans);
+    analysis
+}
+
+fn record(rec: &mut PickRecord, won: bool) {
+    rec.played += 1;
+    rec.wins += won as u32;
+}
+
+fn sorted_by_played(map: HashMap<i64, MonsterRecord>) -> Vec<MonsterRecord> {
+    let mut records: Vec<MonsterRecord> = map.into_values().collect();
+    records.sort_by(|a, b| {
+        b.played
+            .cmp(&a.played)
+            .then(b.wins.cmp(&a.wins))
+            .then(a.monster_id.cmp(&b.monster_id))
+    });
+    records
+}
+
+fn sorted_counts(map: HashMap<i64, u32>) -> Vec<(i64, u32)> {
+    let mut counts: Vec<(i64, u32)> = map.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    counts
+}
+
+/// Adversaires contre lesquels le joueur perd le plus (au moins `MIN_GAMES_FOR_MATCHUP` matchs)
+pub fn toughest_opponents(analysis: &MatchupAnalysis, limit: usize) -> Vec<&MonsterRecord> {
+    let mut records: Vec<&MonsterRecord> = analysis
+        .opponent_monsters
+        .iter()
+        .filter(|r| r.played >= MIN_GAMES_FOR_MATCHUP)
+        .collect();
+    records.sort_by(|a, b| {
+        a.win_rate()
+            .total_cmp(&b.win_rate())
+            .then(b.played.cmp(&a.played))
+    });
+    records.truncate(limit);
+    records
+}
+
+/* ------------------ Embed ------------------ */
+
+pub async fn format_records(
+    collection: Option<&Collection<mongodb::bson::Document>>,
+    records: &[&MonsterRecord],
+) -> String {
+    let mut lines = Vec::new();
+    for r in records {
+        let emoji = match collection {
+            Some(c) => get_lucksack_monster_emoji(c, r.monster_id as i32).await,
+            None => "❓".to_string(),
+        };
+        lines.push(format!(
+            "{} {} games • **{:.1}%** WR",
+            emoji,
+            r.played,
+            r.win_rate()
+        ));
+    }
+
+    if lines.is_empty() {
+        "No data".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+pub async fn format_counts(
+    collection: Option<&Collection<mongodb::bson::Document>>,
+    counts: &[(i64, u32)],
+) -> String {
+    let mut parts = Vec::new();
+    for (id, count) in counts {
+        let emoji = match collection {
+            Some(c) => get_lucksack_monster_emoji(c, *id as i32).await,
+            None => "❓".to_string(),
+        };
+        parts.push(format!("{} ×{}", emoji, count));
+    }
+
+    if parts.is_empty() {
+        "No data".to_string()
+    } else {
+        parts.join("  ")
+    }
+}
+
+pub fn format_pick_record(rec: &PickRecord) -> String {
+    format!(
+        "**{:.1}%** ({}W - {}L)",
+        rec.win_rate(),
+        rec.wins,
+        rec.played - rec.wins
+    )
+}
+
+pub async fn create_player_analysis_embed(
+    summary: &LucksackPlayerSummary,
+    analysis: &MatchupAnalysis,
+    season: i32,
+    chart_attachment: Option<&str>,
+) -> CreateEmbed {
+    let collection = get_mob_emoji_collection().await.ok();
+    let collection = collection.as_ref();
+    let info = &summary.user_info;
+
+    let display_name = match player_alias(info.player_id) {
+        Some(alias) => format!("{} (aka. {})", info.username, alias),
+        None => info.username.clone(),
+    };
+
+    let own: Vec<&MonsterRecord> = analysis.own_monsters.iter().take(8).collect();
+    let faced: Vec<&MonsterRecord> = analysis.opponent_monsters.iter().take(6).collect();
+    let toughest = toughest_opponents(analysis, 6);
+    let top_banned: Vec<(i64, u32)> = analysis
+        .banned_by_opponents
+        .iter()
+        .take(5)
+        .copied()
+        .collect();
+    let top_player_bans: Vec<(i64, u32)> = analysis.player_bans.iter().take(5).copied().collect();
+
+    let mut embed = CreateEmbed::default()
+        .title(format!(
+            ":flag_{}: {} - Season {} Matchup Analysis",
+            info.country.to_lowercase(),
+            display_name,
+            season
+        ))
+        .thumbnail(info.image.clone())
+        .color(serenity::Colour::from_rgb(0, 180, 255))
+        .description(format!(
+            "Analysis of **{}** matches (Elo {} • Rank #{})",
+            analysis.total.played, summary.summary.current_score, summary.summary.current_rank
+        ))
+        .field("Overall", format_pick_record(&analysis.total), true)
+        .field("First Pick", format_pick_record(&analysis.first_pick), true)
+        .field(
+            "Second Pick",
+            format_pick_record(&analysis.second_pick),
+            true,
+        )
+        .field(
+            "🛡️ Most Played Monsters",
+            format_records(collection, &own).await,
+            true,
+        )
+        .field(
+            "⚔️ Most Faced Monsters",
+            format_records(collection, &faced).await,
+            true,
+        )
+        .field(
+            format!(
+                "☠️ Toughest Matchups (min. {} games)",
+                MIN_GAMES_FOR_MATCHUP
+            ),
+            format_records(collection, &toughest).await,
+            false,
+        )
+        .field(
+            "🚫 Most Banned (by opponents)",
+            format_counts(collection, &top_banned).await,
+            false,
+        )
+        .field(
+            "🔨 Player's Bans",
+            format_counts(collection, &top_player_bans).await,
+            false,
+        )
+        .footer(CreateEmbedFooter::new("Data is gathered from lucksack.gg"));
+
+    if let Some(name) = chart_attachment {
+        embed = embed.image(format!("attachment://{}", name));
+    }
+
+    embed
+}
+
+pub fn create_loading_analysis_embed(player_name: &str, total_matches: usize) -> CreateEmbed {
+    CreateEmbed::default()
+        .title(format!("{} - Matchup Analysis", player_name))
+        .color(serenity::Colour::from_rgb(255, 165, 0))
+        .field(
+            "Status",
+            format!(
+                "<a:loading:1358029412716515418> Fetching {} matches...",
+                total_matches
+            ),
+            false,
+        )
+        .footer(CreateEmbedFooter::new(
+            "Please wait while we fetch the season data...",
+        ))
+}
+
+/* ------------------ Chart ------------------ */
+
+/// Horizontal win-rate bars, one row per monster (portrait, bar, "WR% (games)").
+pub async fn create_winrate_chart(records: &[&MonsterRecord], title: &str) -> Result<Vec<u8>> {
+    const WIDTH: u32 = 800;
+    const ROW_HEIGHT: u32 = 60;
+    const TITLE_HEIGHT: u32 = 50;
+    const ICON: u32 = 50;
+    const BAR_X: u32 = 80;
+    const LABEL_WIDTH: u32 = 170;
+
+    let height = TITLE_HEIGHT + ROW_HEIGHT * records.len().max(1) as u32 + 10;
+    let mut canvas: RgbaImage = ImageBuffer::from_pixel(WIDTH, height, Rgba([32, 34, 37, 255]));
+
+    let font = banner_font();
+    let white = Rgba([255, 255, 255, 255]);
+    draw_bold_text_mut(&mut canvas, white, 12, 10, PxScale::from(26.0), font, title);
+
+    let bar_max = (WIDTH - BAR_X - LABEL_WIDTH) as f64;
+    let img_map = get_comid_to_image_map();
+
+    // Ligne des 50 %
+    let half_x = BAR_X + (bar_max / 2.0) as u32;
+    draw_filled_rect_mut(
+        &mut canvas,
+        Rect::at(half_x as i32, TITLE_HEIGHT as i32).of_size(2, height - TITLE_HEIGHT),
+        Rgba([120, 120, 120, 255]),
+    );
+
+    for (i, r) in records.iter().enumerate() {
+        let y = TITLE_HEIGHT + i as u32 * ROW_HEIGHT;
+
+        let portrait = match img_map.get(&(r.monster_id as i32)) {
+            Some(filename) => get_portrait(filename).await,
+            None => placeholder_portrait().clone(),
+        };
+        let icon = portrait.resize_exact(ICON, ICON, image::imageops::FilterType::Triangle);
+        canvas.copy_from(&icon.to_rgba8(), 15, y + (ROW_HEIGHT - ICON) / 2)?;
+
+        let wr = r.win_rate();
+        let bar_len = ((wr / 100.0) * bar_max).round().max(1.0) as u32;
+        let color = if wr >= 50.0 {
+            Rgba([67, 181, 129, 255])
+        } else {
+            Rgba([240, 71, 71, 255])
+        };
+        draw_filled_rect_mut(
+            &mut canvas,
+            Rect::at(BAR_X as i32, (y + 15) as i32).of_size(bar_len, ROW_HEIGHT - 30),
+            color,
+        );
+
+        let label = format!("{:.1}% ({})", wr, r.played);
+        let scale = PxScale::from(22.0);
+        let label_x = (WIDTH - LABEL_WIDTH + 10) as i32;
+        let label_y = (y + (ROW_HEIGHT - 22) / 2) as i32;
+        draw_bold_text_mut(&mut canvas, white, label_x, label_y, scale, font, &label);
+    }
+
+    encode_png(canvas).await
+}
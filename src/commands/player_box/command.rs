@@ -0,0 +1,141 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::{Attachment, Error};
+
+use crate::commands::player_box::models::SavedBox;
+use crate::commands::player_box::utils::{
+    create_box_embed, delete_box, download_account_json, get_saved_box, parse_box, save_box,
+    BOX_LIFETIME_DAYS,
+};
+use crate::commands::shared::embed_error_handling::create_embed_error;
+use crate::commands::shared::logs::{get_server_name, send_log};
+use crate::commands::shared::models::LoggerDocument;
+use crate::Data;
+
+fn db_error(e: anyhow::Error) -> Error {
+    Error::from(std::io::Error::other(format!("DB error: {e}")))
+}
+
+async fn log_box(
+    ctx: &poise::ApplicationContext<'_, Data, Error>,
+    subcommand: &str,
+    success: bool,
+) -> Result<(), Error> {
+    send_log(LoggerDocument::new(
+        &ctx.author().name,
+        &format!("box {}", subcommand),
+        &get_server_name(ctx).await?,
+        success,
+        chrono::Utc::now().timestamp(),
+    ))
+    .await
+}
+
+/// Réponse visible uniquement par l'auteur
+async fn reply_private(
+    ctx: &poise::ApplicationContext<'_, Data, Error>,
+    reply: CreateReply,
+) -> Result<(), Error> {
+    ctx.send(reply.ephemeral(true)).await?;
+    Ok(())
+}
+
+/// 📂 Save your account box so commands can use it without re-uploading the JSON
+#[poise::command(
+    slash_command,
+    rename = "box",
+    subcommands("save", "delete", "show"),
+    subcommand_required
+)]
+pub async fn player_box(_ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Save your monsters from an account JSON (replaces the previous box)
+#[poise::command(slash_command)]
+pub async fn save(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Account JSON (SWEX export)"] file: Attachment,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let result: Result<(), Error> = async {
+        let parsed = match download_account_json(&file).await {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Invalid account JSON: {}", e))
+                .and_then(|json| {
+                    parse_box(&json, "monsters_elements.json")
+                        .map_err(|e| format!("Invalid account JSON: {}", e))
+                }),
+            Err(e) => Err(e),
+        };
+
+        let (wizard_name, monsters) = match parsed {
+            Ok(parsed) => parsed,
+            Err(err_msg) => {
+                reply_private(&ctx, create_embed_error(&err_msg)).await?;
+                return Ok(());
+            }
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        let saved = SavedBox {
+            discord_user_id: ctx.author().id.get() as i64,
+            wizard_name,
+            saved_at: now,
+            expires_at: now + BOX_LIFETIME_DAYS * 86400,
+            monsters,
+        };
+        save_box(saved.clone()).await.map_err(db_error)?;
+
+        reply_private(
+            &ctx,
+            CreateReply::default()
+                .content("✅ Box saved.")
+                .embed(create_box_embed(&saved)),
+        )
+        .await
+    }
+    .await;
+
+    log_box(&ctx, "save", result.is_ok()).await?;
+    result
+}
+
+/// Delete your saved box
+#[poise::command(slash_command)]
+pub async fn delete(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let result: Result<(), Error> = async {
+        let deleted = delete_box(ctx.author().id.get()).await.map_err(db_error)?;
+        let content = if deleted {
+            "🗑️ Your saved box has been deleted."
+        } else {
+            "ℹ️ You have no saved box."
+        };
+        reply_private(&ctx, CreateReply::default().content(content)).await
+    }
+    .await;
+
+    log_box(&ctx, "delete", result.is_ok()).await?;
+    result
+}
+
+/// Show what is stored in your saved box
+#[poise::command(slash_command)]
+pub async fn show(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let result: Result<(), Error> = async {
+        let reply = match get_saved_box(ctx.author().id.get())
+            .await
+            .map_err(db_error)?
+        {
+            Some(saved) => CreateReply::default().embed(create_box_embed(&saved)),
+            None => CreateReply::default()
+                .content("ℹ️ You have no saved box. Use `/box save` with your account JSON."),
+        };
+        reply_private(&ctx, reply).await
+    }
+    .await;
+
+    log_box(&ctx, "show", result.is_ok()).await?;
+    result
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use crate::commands::shared::game_data::RuneSet;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuneSetCount {
+    pub set: RuneSet,
+    pub count: u8,
+}
+
+/// Résumé des runes +12 et plus équipées sur un monstre
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BoxRuneSummary {
+    pub sets: Vec<RuneSetCount>,
+    /// Efficacité moyenne (%)
+    pub avg_efficiency: Option<f32>,
+    /// Vitesse apportée par les substats
+    pub speed: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoxMonster {
+    /// com2us_id (après remap des collabs)
+    pub com2us_id: u32,
+    pub awaken_level: u8,
+    /// Étoiles actuelles du monstre
+    pub grade: u8,
+    pub level: u8,
+    pub runes: BoxRuneSummary,
+}
+
+/// Box sauvegardé d'un utilisateur (collection `player-boxes`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedBox {
+    pub discord_user_id: i64,
+    pub wizard_name: String,
+    pub saved_at: i64,
+    pub expires_at: i64,
+    pub monsters: Vec<BoxMonster>,
+}
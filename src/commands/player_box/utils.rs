@@ -0,0 +1,276 @@
+use anyhow::{Context, Result};
+use mongodb::bson::doc;
+use mongodb::Collection;
+use poise::serenity_prelude as serenity;
+use serde_json::Value;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+use serenity::Attachment;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use crate::commands::mob_stats::utils::remap_monster_id;
+use crate::commands::player_box::models::{BoxMonster, BoxRuneSummary, RuneSetCount, SavedBox};
+use crate::commands::rta_core::models::{Monster, MonstersFile};
+use crate::commands::rta_core::utils::{get_monsters_from_ids, get_monsters_from_json_bytes};
+use crate::commands::shared::clients::mongo_client;
+use crate::commands::shared::game_data::RuneSet;
+use crate::commands::upload_json::process_json::extract_rune;
+
+/// Durée de conservation d'un box sauvegardé
+pub const BOX_LIFETIME_DAYS: i64 = 30;
+
+/// Box used by a command, from the attached JSON or the saved one
+pub struct ResolvedBox {
+    pub wizard_name: String,
+    pub monsters: Vec<Monster>,
+    pub from_saved: bool,
+}
+
+fn player_boxes_collection() -> Result<Collection<SavedBox>> {
+    Ok(mongo_client()?
+        .database("bot-swbox-db")
+        .collection::<SavedBox>("player-boxes"))
+}
+
+/* ------------------ Parsing ------------------ */
+
+fn wizard_name_from_json(json: &Value) -> String {
+    json.get("wizard_info")
+        .and_then(|w| w.get("wizard_name"))
+        .and_then(|w| w.as_str())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("Unknown")
+        .to_string()
+}
+
+fn summarize_runes(unit: &Value) -> BoxRuneSummary {
+    let runes: Vec<_> = unit
+        .get("runes")
+        .and_then(|r| r.as_array())
+        .map(|runes| runes.iter().filter_map(extract_rune).collect())
+        .unwrap_or_default();
+
+    let mut sets: Vec<RuneSetCount> = Vec::new();
+    for rune in &runes {
+        let set = rune.set_id;
+        match sets.iter_mut().find(|s| s.set == set) {
+            Some(entry) => entry.count += 1,
+            None => sets.push(RuneSetCount { set, count: 1 }),
+        }
+    }
+    sets.sort_by_key(|s| Reverse(s.count));
+
+    let efficiencies: Vec<f32> = runes.iter().filter_map(|r| r.efficiency).collect();
+    let avg_efficiency = (!efficiencies.is_empty())
+        .then(|| efficiencies.iter().sum::<f32>() / efficiencies.len() as f32);
+
+    BoxRuneSummary {
+        sets,
+        avg_efficiency,
+        speed: runes.iter().filter_map(|r| r.speed_value).sum(),
+    }
+}
+
+/// Extracts the wizard name and the monsters of an account JSON.
+pub fn parse_box(json: &Value, monsters_json_path: &str) -> Result<(String, Vec<BoxMonster>)> {
+    let unit_list = json
+        .get("unit_list")
+        .and_then(|v| v.as_array())
+        .context("Champ unit_list introuvable ou pas un tableau")?;
+
+    // Niveau d'éveil connu via monsters.json (absent du JSON de compte)
+    let monsters_data =
+        fs::read_to_string(monsters_json_path).context("Impossible de lire monsters.json")?;
+    let all: MonstersFile =
+        serde_json::from_str(&monsters_data).context("Impossible de parser monsters.json")?;
+    let awaken_levels: HashMap<u32, u8> = all
+        .monsters
+        .into_iter()
+        .map(|m| (m.com2us_id, m.awaken_level))
+        .collect();
+
+    let monsters = unit_list
+        .iter()
+        .filter_map(|unit| {
+            let com2us_id = remap_monster_id(unit.get("unit_master_id")?.as_u64()? as i32) as u32;
+            Some(BoxMonster {
+                com2us_id,
+                awaken_level: awaken_levels.get(&com2us_id).copied().unwrap_or(0),
+                grade: unit.get("class").and_then(|v| v.as_u64()).unwrap_or(0) as u8,
+                level: unit.get("unit_level").and_then(|v| v.as_u64()).unwrap_or(0) as u8,
+                runes: summarize_runes(unit),
+            })
+        })
+        .collect();
+
+    Ok((wizard_name_from_json(json), monsters))
+}
+
+/// Validates and downloads an attached account JSON.
+pub async fn download_account_json(file: &Attachment) -> Result<Vec<u8>, String> {
+    if file.url.is_empty() {
+        return Err("No file provided. Please attach a JSON file.".to_string());
+    }
+    if !file.filename.to_lowercase().ends_with(".json") {
+        return Err("The provided file is not a JSON file.".to_string());
+    }
+
+    file.download()
+        .await
+        .map_err(|e| format!("Impossible de télécharger : {}", e))
+}
+
+/* ------------------ Storage ------------------ */
+
+/// Replaces the user's saved box.
+pub async fn save_box(saved: SavedBox) -> Result<()> {
+    let col = player_boxes_collection()?;
+    col.delete_many(doc! { "discord_user_id": saved.discord_user_id })
+        .await?;
+    col.insert_one(saved).await?;
+    Ok(())
+}
+
+/// Saved box of the user, `None` if there is none or it has expired.
+pub async fn get_saved_box(discord_user_id: u64) -> Result<Option<SavedBox>> {
+    let col = player_boxes_collection()?;
+    let saved = col
+        .find_one(doc! { "discord_user_id": discord_user_id as i64 })
+        .await?;
+
+    match saved {
+        Some(saved) if saved.expires_at <= chrono::Utc::now().timestamp() => {
+            col.delete_many(doc! { "discord_user_id": discord_user_id as i64 })
+                .await?;
+            Ok(None)
+        }
+        saved => Ok(saved),
+    }
+}
+
+/// Returns `true` if a box was deleted.
+pub async fn delete_box(discord_user_id: u64) -> Result<bool> {
+    let col = player_boxes_collection()?;
+    let result = col
+        .delete_many(doc! { "discord_user_id": discord_user_id as i64 })
+        .await?;
+    Ok(result.deleted_count > 0)
+}
+
+/// Deletes every expired box, returns how many were removed.
+pub async fn purge_expired_boxes() -> Result<u64> {
+    let col = player_boxes_collection()?;
+    let result = col
+        .delete_many(doc! { "expires_at": { "$lte": chrono::Utc::now().timestamp() } })
+        .await?;
+    Ok(result.deleted_count)
+}
+
+/// Monsters of the attached JSON, otherwise of the user's saved box.
+pub async fn resolve_box(
+    file: Option<&Attachment>,
+    discord_user_id: u64,
+) -> Result<ResolvedBox, String> {
+    if let Some(file) = file {
+        let bytes = download_account_json(file).await?;
+        let json: Value =
+            serde_json::from_slice(&bytes).map_err(|e| format!("Invalid account JSON: {}", e))?;
+        let monsters = get_monsters_from_json_bytes(&bytes, "monsters_elements.json")
+            .map_err(|e| format!("Invalid account JSON: {}", e))?;
+        return Ok(ResolvedBox {
+            wizard_name: wizard_name_from_json(&json),
+            monsters,
+            from_saved: false,
+        });
+    }
+
+    let saved = get_saved_box(discord_user_id)
+        .await
+        .map_err(|e| format!("DB error: {}", e))?
+        .ok_or_else(|| {
+            "No account JSON attached and no saved box. Attach your JSON or use `/box save` first."
+                .to_string()
+        })?;
+
+    let ids: HashSet<u32> = saved.monsters.iter().map(|m| m.com2us_id).collect();
+    let monsters = get_monsters_from_ids(&ids, "monsters_elements.json")
+        .map_err(|e| format!("Erreur : {}", e))?;
+
+    Ok(ResolvedBox {
+        wizard_name: saved.wizard_name,
+        monsters,
+        from_saved: true,
+    })
+}
+
+/* ------------------ Embed ------------------ */
+
+pub fn create_box_embed(saved: &SavedBox) -> CreateEmbed {
+    let runed: Vec<&BoxMonster> = saved
+        .monsters
+        .iter()
+        .filter(|m| !m.runes.sets.is_empty())
+        .collect();
+    let awakened = saved
+        .monsters
+        .iter()
+        .filter(|m| m.awaken_level >= 1)
+        .count();
+    let six_stars = saved.monsters.iter().filter(|m| m.grade >= 6).count();
+
+    // Sets les plus équipés sur l'ensemble du box
+    let mut set_totals: HashMap<RuneSet, u32> = HashMap::new();
+    for set in runed.iter().flat_map(|m| &m.runes.sets) {
+        *set_totals.entry(set.set).or_default() += set.count as u32;
+    }
+    let mut set_totals: Vec<(RuneSet, u32)> = set_totals.into_iter().collect();
+    set_totals.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.name().cmp(b.0.name())));
+    let top_sets = if set_totals.is_empty() {
+        "No +12 rune equipped".to_string()
+    } else {
+        set_totals
+            .iter()
+            .take(5)
+            .map(|(set, count)| format!("{} {} ×{}", set.emoji(), set.name(), count))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    };
+
+    let efficiencies: Vec<f32> = runed
+        .iter()
+        .filter_map(|m| m.runes.avg_efficiency)
+        .collect();
+    let avg_efficiency = if efficiencies.is_empty() {
+        "-".to_string()
+    } else {
+        format!(
+            "{:.1} %",
+            efficiencies.iter().sum::<f32>() / efficiencies.len() as f32
+        )
+    };
+
+    CreateEmbed::default()
+        .title(format!("📦 Saved box - {}", saved.wizard_name))
+        .color(serenity::Colour::from_rgb(0, 180, 255))
+        .description(format!(
+            "Saved <t:{}:R>, expires <t:{}:R>.\nOnly you can use this box, remove it anytime with `/box delete`.",
+            saved.saved_at, saved.expires_at
+        ))
+        .field(
+            "Monsters",
+            format!(
+                "{} monsters · {} awakened · {} 6★ · {} runed",
+                saved.monsters.len(),
+                awakened,
+                six_stars,
+                runed.len()
+            ),
+            false,
+        )
+        .field("Most used sets", top_sets, false)
+        .field("Average rune efficiency", avg_efficiency, true)
+        .footer(CreateEmbedFooter::new(
+            "Used by /get_rta_core, /get_meta and /best_pve_teams when no JSON is attached",
+        ))
+}
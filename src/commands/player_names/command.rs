@@ -2,10 +2,12 @@ use crate::commands::player_names::modal::{
     PlayerNamesInfosModalById, PlayerNamesInfosModalByName,
 };
 use crate::commands::player_names::models::{PlayerNamesModalData, PlayerSearchInput};
+use crate::commands::player_names::snapshots::{get_name_snapshots, record_player_names};
 use crate::commands::player_names::utils::{
-    get_current_detail_from_swrt, get_player_all_names, get_swrt_id_from_db_by_player_id,
-    handle_modal, resolve_player_id,
+    build_name_timeline, format_name_timeline, get_current_detail_from_swrt, get_player_all_names,
+    get_swrt_id_from_db_by_player_id, handle_modal, resolve_player_id,
 };
+use crate::commands::player_stats::identity::identity_from_swarena;
 use crate::commands::shared::logs::{get_server_name, send_log};
 use crate::commands::shared::models::LoggerDocument;
 use crate::Data;
@@ -113,7 +115,27 @@ pub async fn track_player_names(
         }
     }
 
-    // 4) Recherche des pseudos SWArena (existant)
+    // 4) Pseudos observés par le bot, rangés par id lucksack
+    // (le pseudo actuel compte comme une observation)
+    let lucksack_id = match player_id.parse::<i64>() {
+        Ok(swarena_id) => identity_from_swarena(swarena_id)
+            .await
+            .ok()
+            .flatten()
+            .map(|identity| identity.lucksack_id),
+        Err(_) => None,
+    };
+    let mut snapshots = Vec::new();
+    if let Some(id) = lucksack_id {
+        if let Some(ref cname) = current_name {
+            if let Err(e) = record_player_names(&[(id, cname.clone())]).await {
+                eprintln!("Failed to record player names: {:?}", e);
+            }
+        }
+        snapshots = get_name_snapshots(id).await.unwrap_or_default();
+    }
+
+    // 5) Recherche des pseudos SWArena (par saison)
     let player_all_names = get_player_all_names(player_id.clone()).await;
 
     // Petit helper pour bâtir l’embed (sans "Current in-game name")
@@ -128,148 +150,82 @@ pub async fn track_player_names(
             )
     };
 
-    match player_all_names {
-        Ok(names) if names.is_empty() => {
-            let mut embed = base_embed(
-                "Username not found",
-                format!(
-                    "We couldn't find any usernames for the player with ID **{}**.",
-                    player_id
-                ),
-            )
-            .field(
-                "Tips",
-                "Check if the ID is correct or try another account.",
-                false,
-            )
-            .color(0xff0000);
-
-            // 👉 Ajouter le current name à la fin (s’il existe)
-            if let Some(ref cname) = current_name {
-                embed = embed.field("Current in-game name", cname, true);
-            }
-
-            let create_reply = CreateReply {
-                embeds: vec![embed],
-                ..Default::default()
-            };
-            ctx.send(create_reply).await?;
-
-            send_log(LoggerDocument::new(
-                &ctx.author().name,
-                "track_player_names",
-                &get_server_name(&ctx).await?,
-                false,
-                chrono::Utc::now().timestamp(),
-            ))
-            .await?;
-        }
-
-        Ok(names) if names.len() == 1 => {
-            let mut embed = base_embed(
-                "Username found",
-                format!("The username for the player with ID **{}** is:", player_id),
-            )
-            .field("Past Username", &names[0], true)
-            .color(0x00ff00);
-
-            // 👉 Ajouter le current name après l’historique
-            if let Some(ref cname) = current_name {
-                embed = embed.field("Current in-game name", cname, true);
-            }
-
-            let create_reply = CreateReply {
-                embeds: vec![embed],
-                ..Default::default()
-            };
-            ctx.send(create_reply).await?;
-
-            send_log(LoggerDocument::new(
-                &ctx.author().name,
-                "track_player_names",
-                &get_server_name(&ctx).await?,
-                true,
-                chrono::Utc::now().timestamp(),
-            ))
-            .await?;
-        }
-
-        Ok(names) => {
-            let formatted_names = names
-                .iter()
-                .map(|n| format!("- {}", n))
-                .collect::<Vec<_>>()
-                .join("\n");
-
-            let mut embed = base_embed(
-                "Usernames found",
-                format!(
-                    "The usernames for the player with ID **{}** are:",
-                    player_id
-                ),
-            )
-            .field("Past Usernames", formatted_names, false)
-            .color(0x00ff00);
-
-            // 👉 Ajouter le current name après la liste des pseudos
-            if let Some(ref cname) = current_name {
-                embed = embed.field("Current in-game name", cname, true);
-            }
-
-            let create_reply = CreateReply {
-                embeds: vec![embed],
-                ..Default::default()
-            };
-            ctx.send(create_reply).await?;
-
-            send_log(LoggerDocument::new(
-                &ctx.author().name,
-                "track_player_names",
-                &get_server_name(&ctx).await?,
-                true,
-                chrono::Utc::now().timestamp(),
-            ))
-            .await?;
-        }
-
-        Err(_e) => {
-            // Fallback: on envoie un embed "partiel" avec le current name si disponible
-            let mut embed = base_embed(
-                "Couldn't retrieve username history",
-                "We couldn't fetch the username history from SWArena right now.".to_string(),
-            )
-            .field(
+    let swarena_available = player_all_names.is_ok();
+    let timeline = build_name_timeline(&player_all_names.unwrap_or_default(), &snapshots);
+
+    let (mut embed, success) = if timeline.is_empty() && swarena_available {
+        let embed = base_embed(
+            "Username not found",
+            format!(
+                "We couldn't find any usernames for the player with ID **{}**.",
+                player_id
+            ),
+        )
+        .field(
+            "Tips",
+            "Check if the ID is correct or try another account.",
+            false,
+        )
+        .color(0xff0000);
+        (embed, false)
+    } else if timeline.is_empty() {
+        // Fallback: on envoie un embed "partiel" avec le current name si disponible
+        let embed = base_embed(
+            "Couldn't retrieve username history",
+            "We couldn't fetch the username history from SWArena right now.".to_string(),
+        )
+        .field(
+            "Info",
+            "This player never reached G1, hence no public profile on SWArena.",
+            false,
+        )
+        .color(0xffa500); // orange "warning"
+        (embed, current_name.is_some())
+    } else {
+        let title = if timeline.len() == 1 {
+            "Username found"
+        } else {
+            "Usernames found"
+        };
+        let mut embed = base_embed(
+            title,
+            format!(
+                "Rename timeline of the player with ID **{}**, oldest first:",
+                player_id
+            ),
+        )
+        .field("Rename timeline", format_name_timeline(&timeline), false)
+        .color(0x00ff00);
+
+        if !swarena_available {
+            embed = embed.field(
                 "Info",
-                "This player never reached G1, hence no public profile on SWArena.",
+                "SWArena history unavailable, only names seen by the bot are listed.",
                 false,
-            )
-            .color(0xffa500); // orange "warning"
-
-            // 👉 Toujours ajouter le current name s’il existe (récupéré via DB → SWRanking)
-            if let Some(ref cname) = current_name {
-                embed = embed.field("Current in-game name", cname, true);
-            }
-
-            // Si on n'a même pas le current name, on garde quand même un message d’erreur convivial
-            // (le thumbnail reste le logo par défaut via base_embed)
-
-            let create_reply = CreateReply {
-                embeds: vec![embed],
-                ..Default::default()
-            };
-            ctx.send(create_reply).await?;
-
-            // Logging plus informatif
-            send_log(LoggerDocument::new(
-                &ctx.author().name,
-                "track_player_names",
-                &get_server_name(&ctx).await?,
-                current_name.is_some(),
-                chrono::Utc::now().timestamp(),
-            ))
-            .await?;
+            );
         }
+        (embed, true)
+    };
+
+    // 👉 Ajouter le current name à la fin (s’il existe)
+    if let Some(ref cname) = current_name {
+        embed = embed.field("Current in-game name", cname, true);
     }
 
+    let create_reply = CreateReply {
+        embeds: vec![embed],
+        ..Default::default()
+    };
+    ctx.send(create_reply).await?;
+
+    send_log(LoggerDocument::new(
+        &ctx.author().name,
+        "track_player_names",
+        &get_server_name(&ctx).await?,
+        success,
+        chrono::Utc::now().timestamp(),
+    ))
+    .await?;
+
     Ok(())
 }
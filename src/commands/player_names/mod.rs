@@ -1,4 +1,5 @@
 pub mod command;
 pub mod modal;
 pub mod models;
+pub mod snapshots;
 pub mod utils;
@@ -4,7 +4,7 @@ use poise::Modal;
 #[name = "Enter the player's username"]
 pub struct PlayerNamesInfosModalByName {
     #[name = "Player's username"]
-    #[placeholder = "Enter the player's username (e.g., Reynca)"]
+    #[placeholder = "Username, alias or mention (e.g., Reynca)"]
     pub name: String,
 }
 
@@ -12,6 +12,6 @@ pub struct PlayerNamesInfosModalByName {
 #[name = "Enter the player's ID"]
 pub struct PlayerNamesInfosModalById {
     #[name = "Player's ID"]
-    #[placeholder = "Enter the player's ID (e.g., 123456789)"]
+    #[placeholder = "SWArena ID (e.g., 123456789), or lucksack:<id> / swrt:<id>"]
     pub id: String,
 }
@@ -9,3 +9,13 @@ pub struct PlayerSearchInput {
     pub id: Option<String>,
     pub name: Option<String>,
 }
+
+/// One name in a player's rename timeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameTimelineEntry {
+    pub name: String,
+    /// First and last SWArena season played under this name
+    pub seasons: Option<(i64, i64)>,
+    /// First and last time the bot saw this name (timestamps)
+    pub seen: Option<(i64, i64)>,
+}
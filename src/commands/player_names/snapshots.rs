@@ -0,0 +1,110 @@
+//! Pseudos observés par le bot. Chaque fois qu'un joueur apparaît (leaderboard,
+//! recherche, profil, /register), on garde `(player_id, name, first_seen,
+//! last_seen)` dans Mongo, ce qui date les renommages en cours de saison.
+use anyhow::Result;
+use futures::stream::TryStreamExt;
+use mongodb::bson::{doc, Document};
+use mongodb::options::IndexOptions;
+use mongodb::{Collection, IndexModel};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::shared::clients::mongo_client;
+
+/// One name seen for a player (lucksack / SWRanking id)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NameSnapshot {
+    pub player_id: i64,
+    pub name: String,
+    pub first_seen: i64,
+    pub last_seen: i64,
+}
+
+const NAME_SNAPSHOTS_COLLECTION: &str = "player-name-snapshots";
+
+fn name_snapshots_collection() -> Result<Collection<NameSnapshot>> {
+    Ok(mongo_client()?
+        .database("bot-swbox-db")
+        .collection::<NameSnapshot>(NAME_SNAPSHOTS_COLLECTION))
+}
+
+/// Unique `{player_id, name}` index: concurrent upserts of the same pair can't
+/// create duplicates, and lookups by player use its prefix. Called at startup.
+pub async fn ensure_name_snapshot_indexes() -> Result<()> {
+    name_snapshots_collection()?
+        .create_index(
+            IndexModel::builder()
+                .keys(doc! { "player_id": 1, "name": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Records that each `(player_id, name)` pair was seen now, in one request.
+pub async fn record_player_names(players: &[(i64, String)]) -> Result<()> {
+    let now = chrono::Utc::now().timestamp();
+
+    let updates: Vec<Document> = players
+        .iter()
+        .filter(|(_, name)| !name.is_empty())
+        .map(|(player_id, name)| {
+            doc! {
+                "q": { "player_id": player_id, "name": name },
+                "u": {
+                    "$min": { "first_seen": now },
+                    "$max": { "last_seen": now },
+                },
+                "upsert": true,
+            }
+        })
+        .collect();
+    if updates.is_empty() {
+        return Ok(());
+    }
+
+    // Commande `update` groupée plutôt que Client::bulk_write, qui exige MongoDB 8
+    let res = mongo_client()?
+        .database("bot-swbox-db")
+        .run_command(doc! {
+            "update": NAME_SNAPSHOTS_COLLECTION,
+            "updates": updates,
+            "ordered": false,
+        })
+        .await?;
+
+    // Les erreurs par document ne font pas échouer la commande elle-même
+    match res.get_array("writeErrors") {
+        Ok(errors) if !errors.is_empty() => Err(anyhow::anyhow!(
+            "{} name snapshots not recorded: {:?}",
+            errors.len(),
+            errors.first()
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Same as `record_player_names`, in the background: a failure never reaches
+/// the command that saw the players.
+pub fn spawn_record_player_names(players: Vec<(i64, String)>) {
+    if players.is_empty() {
+        return;
+    }
+    tokio::spawn(async move {
+        if let Err(e) = record_player_names(&players).await {
+            eprintln!("Failed to record player names: {:?}", e);
+        }
+    });
+}
+
+/// Names seen for a player, oldest first.
+pub async fn get_name_snapshots(player_id: i64) -> Result<Vec<NameSnapshot>> {
+    let col = name_snapshots_collection()?;
+    let mut snapshots: Vec<NameSnapshot> = col
+        .find(doc! { "player_id": player_id })
+        .await?
+        .try_collect()
+        .await?;
+    snapshots.sort_by_key(|s| s.first_seen);
+    Ok(snapshots)
+}
@@ -10,7 +10,9 @@ use crate::{
     Data,
 };
 
-use super::models::PlayerSearchInput;
+use super::models::{NameTimelineEntry, PlayerSearchInput};
+use super::snapshots::NameSnapshot;
+use crate::commands::player_stats::identity::{parse_prefixed_id, resolve_identity};
 
 pub async fn handle_modal<M, F>(
     ctx: poise::ApplicationContext<'_, Data, Error>,
@@ -53,6 +55,20 @@ pub async fn resolve_player_id(
             if let Some(id) = modal_data.id.clone() {
                 if id.parse::<i64>().is_ok() {
                     return Ok(Some(id));
+                } else if parse_prefixed_id(&id).is_some() {
+                    // Id lucksack / SWRanking : on passe par l'identité du joueur
+                    let swarena_id = resolve_identity(&id)
+                        .await
+                        .ok()
+                        .flatten()
+                        .and_then(|identity| identity.swarena_id);
+                    if let Some(swarena_id) = swarena_id {
+                        return Ok(Some(swarena_id.to_string()));
+                    }
+                    let error_message = format!("No SWArena profile is linked to **{}**.", id);
+                    let reply = ctx.send(create_embed_error(&error_message)).await?;
+                    schedule_message_deletion(reply, ctx).await?;
+                    return Ok(None);
                 } else {
                     let error_message = format!("The ID **{}** is not a valid integer.", id);
                     let reply = ctx.send(create_embed_error(&error_message)).await?;
@@ -60,6 +76,14 @@ pub async fn resolve_player_id(
                     return Ok(None);
                 }
             } else if let Some(name) = modal_data.name {
+                // Alias, mention ou pseudo lucksack déjà lié à un profil SWArena
+                if let Ok(Some(swarena_id)) = resolve_identity(&name)
+                    .await
+                    .map(|identity| identity.and_then(|i| i.swarena_id))
+                {
+                    return Ok(Some(swarena_id.to_string()));
+                }
+
                 // Sinon, on tente la recherche SWArena par nom comme avant
                 match get_player_id_by_name(name).await {
                     Ok(id) => return Ok(Some(id)),
@@ -81,7 +105,7 @@ pub async fn resolve_player_id(
     Ok(None)
 }
 
-async fn get_player_seasons_played(player_id: String) -> Result<Vec<i64>, String> {
+pub async fn get_player_seasons_played(player_id: String) -> Result<Vec<i64>, String> {
     let url = format!("https://api.swarena.gg/player/{}/seasons", player_id);
     let response = http_client()
         .get(url)
@@ -133,26 +157,115 @@ async fn get_player_name(player_id: String, seasons_played: String) -> Result<St
     Err("Failed to retrieve player name.".to_string())
 }
 
-pub async fn get_player_all_names(player_id: String) -> Result<Vec<String>, String> {
+/// Names of the player on SWArena, as (season, name) sorted by season.
+pub async fn get_player_all_names(player_id: String) -> Result<Vec<(i64, String)>, String> {
     let seasons_played = get_player_seasons_played(player_id.clone()).await?;
 
     let player_names_futures = seasons_played.into_iter().map(|season| {
         let player_id = player_id.clone();
-        tokio::spawn(async move { get_player_name(player_id, season.to_string()).await })
+        tokio::spawn(async move {
+            get_player_name(player_id, season.to_string())
+                .await
+                .map(|name| (season, name))
+        })
     });
 
     let results = future::join_all(player_names_futures).await;
 
-    let mut player_names = Vec::new();
-    for result in results {
-        if let Ok(Ok(name)) = result {
-            if !player_names.contains(&name) {
-                player_names.push(name);
+    let mut player_names: Vec<(i64, String)> = results
+        .into_iter()
+        .filter_map(|result| result.ok()?.ok())
+        .collect();
+    player_names.sort_by_key(|(season, _)| *season);
+
+    Ok(player_names)
+}
+
+/// Merges SWArena names (per season) with the names recorded by the bot,
+/// oldest first: SWArena seasons, then first time seen by the bot.
+pub fn build_name_timeline(
+    swarena_names: &[(i64, String)],
+    snapshots: &[NameSnapshot],
+) -> Vec<NameTimelineEntry> {
+    let mut timeline: Vec<NameTimelineEntry> = Vec::new();
+
+    let entry_for = |timeline: &mut Vec<NameTimelineEntry>, name: &str| -> usize {
+        match timeline.iter().position(|e| e.name == name) {
+            Some(i) => i,
+            None => {
+                timeline.push(NameTimelineEntry {
+                    name: name.to_string(),
+                    seasons: None,
+                    seen: None,
+                });
+                timeline.len() - 1
             }
         }
+    };
+
+    for (season, name) in swarena_names {
+        let i = entry_for(&mut timeline, name);
+        let seasons = timeline[i].seasons.get_or_insert((*season, *season));
+        seasons.0 = seasons.0.min(*season);
+        seasons.1 = seasons.1.max(*season);
     }
 
-    Ok(player_names)
+    for snapshot in snapshots {
+        let i = entry_for(&mut timeline, &snapshot.name);
+        let seen = timeline[i]
+            .seen
+            .get_or_insert((snapshot.first_seen, snapshot.last_seen));
+        seen.0 = seen.0.min(snapshot.first_seen);
+        seen.1 = seen.1.max(snapshot.last_seen);
+    }
+
+    timeline.sort_by_key(|e| {
+        (
+            e.seasons.is_none(),
+            e.seasons.map(|s| s.0).unwrap_or(0),
+            e.seen.map(|s| s.0).unwrap_or(0),
+        )
+    });
+    timeline
+}
+
+/// One line per name, cut to fit in an embed field.
+pub fn format_name_timeline(timeline: &[NameTimelineEntry]) -> String {
+    const FIELD_LIMIT: usize = 1024;
+
+    let lines: Vec<String> = timeline
+        .iter()
+        .map(|entry| {
+            let mut parts = Vec::new();
+            match entry.seasons {
+                Some((first, last)) if first == last => parts.push(format!("season {}", first)),
+                Some((first, last)) => parts.push(format!("seasons {}-{}", first, last)),
+                None => {}
+            }
+            if let Some((first, last)) = entry.seen {
+                parts.push(format!("seen <t:{}:d> → <t:{}:d>", first, last));
+            }
+            format!("- **{}** · {}", entry.name, parts.join(" · "))
+        })
+        .collect();
+
+    let mut out = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        let more = format!("\n… and {} more", lines.len() - i);
+        if out.len() + line.len() + 1 + more.len() > FIELD_LIMIT {
+            out.push_str(if out.is_empty() {
+                more.trim_start()
+            } else {
+                &more
+            });
+            break;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(line);
+    }
+    out
 }
 
 pub async fn get_swrt_id_from_db_by_player_id(player_id: i64) -> Result<i64, String> {
@@ -177,6 +290,26 @@ pub async fn get_swrt_id_from_db_by_player_id(player_id: i64) -> Result<i64, Str
     Ok(swrt_player_id)
 }
 
+/// Reverse of `get_swrt_id_from_db_by_player_id`: SWArena id of a SWRanking player.
+pub async fn get_player_id_from_db_by_swrt_id(swrt_player_id: i64) -> Result<i64, String> {
+    let client = mongo_client().map_err(|e| format!("Mongo client error: {e}"))?;
+
+    let coll = client
+        .database("bot-swbox-db")
+        .collection::<mongodb::bson::Document>("players");
+
+    let filter = doc! { "swrtPlayerId": swrt_player_id };
+    let doc = coll
+        .find_one(filter)
+        .await
+        .map_err(|e| format!("Mongo query error: {e}"))?
+        .ok_or_else(|| "Player not found in DB".to_string())?;
+
+    doc.get_i64("playerId")
+        .or_else(|_| doc.get_i32("playerId").map(|v| v as i64))
+        .map_err(|_| "Missing playerId in DB document".to_string())
+}
+
 pub async fn get_current_detail_from_swrt(
     swrt_player_id: i64,
 ) -> Result<(String, Option<String>), String> {
@@ -7,9 +7,12 @@ use serenity::{
     Error,
 };
 
+use crate::commands::player_stats::identity::{
+    identity_from_swarena, identity_from_swrt, parse_prefixed_id, PlayerIdInput,
+};
 use crate::commands::register::utils::get_user_link;
 use crate::commands::shared::logs::send_log;
-use crate::commands::shared::player_alias::ALIAS_LOOKUP_MAP;
+use crate::commands::shared::player_alias::{find_player_by_alias, suggest_alias};
 use crate::commands::{
     player_stats::utils::{
         create_lucksack_player_embed, create_lucksack_replay_image,
@@ -21,7 +24,7 @@ use crate::commands::{
     shared::{
         embed_error_handling::{create_embed_error, schedule_message_deletion},
         logs::get_server_name,
-        models::LoggerDocument,
+        models::{LoggerDocument, ReplayDisplay},
     },
 };
 use crate::Data;
@@ -29,9 +32,9 @@ use crate::Data;
 const REPLAY_PAGE_SIZE: usize = 6;
 const PLAYER_STATS_LOADING_REPLAY_GIF_URL: &str = "https://media1.giphy.com/media/v1.Y2lkPTc5MGI3NjExczN3N3YxcjAzc3g5bWpqY2VleXA2MHN0bm9rcDVvaG00MGZrbHoweSZlcD12MV9pbnRlcm5hbF9naWZfYnlfaWQmY3Q9Zw/2WjpfxAI5MvC9Nl8U7/giphy.gif";
 
-struct ResolvedPlayer<'a> {
-    player_id: i64,
-    reply_handle: Option<poise::ReplyHandle<'a>>,
+pub(crate) struct ResolvedPlayer<'a> {
+    pub player_id: i64,
+    pub reply_handle: Option<poise::ReplyHandle<'a>>,
 }
 
 /// 📂 Displays the RTA stats of the given player.
@@ -41,15 +44,22 @@ struct ResolvedPlayer<'a> {
 pub async fn get_player_stats(
     ctx: poise::ApplicationContext<'_, Data, Error>,
     #[description = "Player name"] player_name: String,
+    #[description = "Replay display (default: Grid)"] replay_display: Option<ReplayDisplay>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
-    let resolved = match resolve_player_id(&ctx, &player_name).await? {
+    let resolved = match resolve_player_id(&ctx, &player_name, "get_player_stats").await? {
         Some(r) => r,
         None => return Ok(()),
     };
 
-    let result = show_player_stats(&ctx, resolved.player_id, resolved.reply_handle).await;
+    let result = show_player_stats(
+        &ctx,
+        resolved.player_id,
+        resolved.reply_handle,
+        replay_display.unwrap_or_default(),
+    )
+    .await;
 
     send_log(LoggerDocument::new(
         &ctx.author().name,
@@ -63,9 +73,12 @@ pub async fn get_player_stats(
     result
 }
 
-async fn resolve_player_id<'a>(
+/// Resolves a player from a Discord mention, a `lucksack:`/`swarena:`/`swrt:` id,
+/// an alias or a lucksack name search (with a select menu when several players match).
+pub(crate) async fn resolve_player_id<'a>(
     ctx: &'a poise::ApplicationContext<'a, Data, Error>,
     player_name: &str,
+    command_name: &str,
 ) -> Result<Option<ResolvedPlayer<'a>>, Error> {
     // Discord mention
     if let Some(discord_id) = parse_discord_mention_to_id(player_name) {
@@ -89,8 +102,37 @@ async fn resolve_player_id<'a>(
         }));
     }
 
+    // Explicit id from any source: lucksack:, swarena: or swrt:
+    if let Some(id) = parse_prefixed_id(player_name) {
+        let player_id = match id {
+            PlayerIdInput::Lucksack(id) => Some(id),
+            PlayerIdInput::Swarena(id) => identity_from_swarena(id)
+                .await
+                .map_err(|e| Error::from(std::io::Error::other(format!("API error: {}", e))))?
+                .map(|identity| identity.lucksack_id),
+            PlayerIdInput::Swrt(id) => identity_from_swrt(id)
+                .await
+                .map_err(|e| Error::from(std::io::Error::other(format!("API error: {}", e))))?
+                .map(|identity| identity.lucksack_id),
+        };
+
+        let Some(player_id) = player_id else {
+            ctx.say(format!(
+                "❌ No lucksack player is linked to `{}`.",
+                player_name
+            ))
+            .await?;
+            return Ok(None);
+        };
+
+        return Ok(Some(ResolvedPlayer {
+            player_id,
+            reply_handle: None,
+        }));
+    }
+
     // Alias lookup
-    if let Some(&swrt_id) = ALIAS_LOOKUP_MAP.get(&player_name.to_lowercase()) {
+    if let Some(swrt_id) = find_player_by_alias(player_name) {
         return Ok(Some(ResolvedPlayer {
             player_id: swrt_id,
             reply_handle: None,
@@ -103,12 +145,15 @@ async fn resolve_player_id<'a>(
         .map_err(|e| Error::from(std::io::Error::other(format!("API error: {}", e))))?;
 
     if players.is_empty() {
-        ctx.say(format!("No players found for `{}`.", player_name))
+        let hint = suggest_alias(player_name)
+            .map(|alias| format!(" Did you mean `{}`?", alias))
+            .unwrap_or_default();
+        ctx.say(format!("No players found for `{}`.{}", player_name, hint))
             .await?;
 
         send_log(LoggerDocument::new(
             &ctx.author().name,
-            "get_player_stats",
+            command_name,
             &get_server_name(ctx).await?,
             false,
             chrono::Utc::now().timestamp(),
@@ -125,7 +170,7 @@ async fn resolve_player_id<'a>(
         }));
     }
 
-    let selected = select_player_from_menu(ctx, &players).await?;
+    let selected = select_player_from_menu(ctx, &players, command_name).await?;
     Ok(selected.map(|(id, handle)| ResolvedPlayer {
         player_id: id,
         reply_handle: Some(handle),
@@ -135,6 +180,7 @@ async fn resolve_player_id<'a>(
 async fn select_player_from_menu<'a>(
     ctx: &'a poise::ApplicationContext<'a, Data, Error>,
     players: &[LucksackSearchPlayer],
+    command_name: &str,
 ) -> Result<Option<(i64, poise::ReplyHandle<'a>)>, Error> {
     let options: Vec<CreateSelectMenuOption> = players
         .iter()
@@ -189,7 +235,7 @@ async fn select_player_from_menu<'a>(
 
         send_log(LoggerDocument::new(
             &ctx.author().name,
-            "get_player_stats",
+            command_name,
             &get_server_name(ctx).await?,
             false,
             chrono::Utc::now().timestamp(),
@@ -241,6 +287,7 @@ pub(crate) async fn show_player_stats<'a>(
     ctx: &'a poise::ApplicationContext<'a, Data, Error>,
     player_id: i64,
     existing_reply: Option<poise::ReplyHandle<'a>>,
+    replay_display: ReplayDisplay,
 ) -> Result<(), Error> {
     // Fetch season numbers from lucksack
     let seasons = match get_lucksack_season_numbers().await {
@@ -287,7 +334,12 @@ pub(crate) async fn show_player_stats<'a>(
     let ld_monsters = format_lucksack_ld_monsters_emojis(&ld_box).await;
     let rank_emojis = get_rank_emojis_for_bracket(summary.summary.current_rank_bracket);
     let total_matches = summary.summary.total_matches.max(0) as usize;
-    let last_replay_page = total_matches.div_ceil(REPLAY_PAGE_SIZE).max(1) as i32;
+    // Gallery mode flips through matches one at a time
+    let page_size = match replay_display {
+        ReplayDisplay::Gallery => 1,
+        ReplayDisplay::Grid | ReplayDisplay::Animated => REPLAY_PAGE_SIZE,
+    };
+    let last_replay_page = total_matches.div_ceil(page_size).max(1) as i32;
     let mut replay_page = 1i32;
 
     let loading_gif_image_ref = PLAYER_STATS_LOADING_REPLAY_GIF_URL;
@@ -331,14 +383,16 @@ pub(crate) async fn show_player_stats<'a>(
     };
 
     // --- Step 2: fetch matches, generate replay image, update embed ---
-    let matches = get_lucksack_player_matches(player_id, season, REPLAY_PAGE_SIZE, 0)
+    let matches = get_lucksack_player_matches(player_id, season, page_size, 0)
         .await
         .unwrap_or_default();
 
     let replay_image_path = if !matches.is_empty() {
         // println!("Generating replay image for {} matches...", matches.len());
         // let start = std::time::Instant::now();
-        let result = create_lucksack_replay_image(&matches).await.ok();
+        let result = create_lucksack_replay_image(&matches, replay_display)
+            .await
+            .ok();
         // let duration = start.elapsed();
         // println!("Replay image generation took: {:?}", duration);
         result
@@ -445,13 +499,15 @@ pub(crate) async fn show_player_stats<'a>(
             )
             .await?;
 
-        let offset = ((replay_page - 1) as usize) * REPLAY_PAGE_SIZE;
-        let matches = get_lucksack_player_matches(player_id, season, REPLAY_PAGE_SIZE, offset)
+        let offset = ((replay_page - 1) as usize) * page_size;
+        let matches = get_lucksack_player_matches(player_id, season, page_size, offset)
             .await
             .unwrap_or_default();
 
         let replay_image_path = if !matches.is_empty() {
-            create_lucksack_replay_image(&matches).await.ok()
+            create_lucksack_replay_image(&matches, replay_display)
+                .await
+                .ok()
         } else {
             None
         };
@@ -0,0 +1,541 @@
+//! Identité d'un joueur à travers les trois espaces d'ids du bot :
+//! `player_id` lucksack, id SWArena (`/track_player_names`) et `swrtPlayerId`
+//! SWRanking. Les liens sont trouvés automatiquement (alias, nom, pays, score)
+//! puis gardés dans la collection Mongo `player-identities`.
+use anyhow::{anyhow, Result};
+use mongodb::bson::doc;
+use mongodb::Collection;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::commands::player_names::utils::{
+    get_player_id_from_db_by_swrt_id, get_player_seasons_played, get_swrt_id_from_db_by_player_id,
+};
+use crate::commands::player_stats::utils::{
+    get_lucksack_player_summary, get_lucksack_season_numbers, parse_discord_mention_to_id,
+    search_players_lucksack,
+};
+use crate::commands::register::utils::get_user_link;
+use crate::commands::shared::clients::{http_client, mongo_client};
+use crate::commands::shared::player_alias::{find_player_by_alias, list_aliases};
+
+/// Écart de score toléré entre deux sources pour un même joueur
+const SCORE_TOLERANCE: i64 = 100;
+
+/// Délai avant de retenter un lien manquant ou de revérifier un lien par nom
+const RELINK_AFTER_SECS: i64 = 24 * 3600;
+
+/// How a SWArena id was linked to a lucksack id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentityLink {
+    /// Hand-written alias entry
+    Alias,
+    /// Same name, country and score on both sites
+    NameMatch,
+}
+
+/// Ids of one player in every data source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerIdentity {
+    pub lucksack_id: i64,
+    #[serde(default)]
+    pub swarena_id: Option<i64>,
+    #[serde(default)]
+    pub swrt_player_id: Option<i64>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub country: Option<String>,
+    #[serde(default)]
+    pub linked_by: Option<IdentityLink>,
+    pub updated_at: i64,
+}
+
+impl PlayerIdentity {
+    fn is_complete(&self) -> bool {
+        self.swarena_id.is_some() && self.swrt_player_id.is_some()
+    }
+
+    /// Whether the stored links can be used as is. Complete alias links are
+    /// kept for good; name matches and missing links expire after
+    /// `RELINK_AFTER_SECS`, as the name may have moved to another player.
+    fn is_settled(&self, now: i64) -> bool {
+        let permanent = self.is_complete() && self.linked_by != Some(IdentityLink::NameMatch);
+        permanent || now - self.updated_at < RELINK_AFTER_SECS
+    }
+
+    /// Drops the links found by name, so they are looked for again.
+    fn clear_name_match(&mut self) {
+        if self.linked_by == Some(IdentityLink::NameMatch) {
+            self.swarena_id = None;
+            self.swrt_player_id = None;
+            self.linked_by = None;
+        }
+    }
+}
+
+/// Explicit id typed by a user: `lucksack:123`, `swarena:123` or `swrt:123`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerIdInput {
+    Lucksack(i64),
+    Swarena(i64),
+    Swrt(i64),
+}
+
+pub fn parse_prefixed_id(input: &str) -> Option<PlayerIdInput> {
+    let (prefix, id) = input.trim().split_once(':')?;
+    let id: i64 = id.trim().parse().ok()?;
+    match prefix.trim().to_lowercase().as_str() {
+        "lucksack" | "ls" => Some(PlayerIdInput::Lucksack(id)),
+        "swarena" | "sa" => Some(PlayerIdInput::Swarena(id)),
+        "swrt" | "swranking" => Some(PlayerIdInput::Swrt(id)),
+        _ => None,
+    }
+}
+
+/// A player seen on one site, compared against the other site
+#[derive(Debug, Clone)]
+pub struct IdentityCandidate {
+    pub id: i64,
+    pub name: String,
+    pub country: Option<String>,
+    pub score: Option<i64>,
+}
+
+fn known_country(country: &str) -> Option<String> {
+    let country = country.trim();
+    (!country.is_empty() && !country.eq_ignore_ascii_case("UNKNOWN"))
+        .then(|| country.to_uppercase())
+}
+
+/// Id of the candidate that is the same player as `target`: same name, same
+/// country when both are known, and a score within `SCORE_TOLERANCE`. A
+/// missing score on either side never links. `None` when no candidate or
+/// several match.
+pub fn match_candidate(
+    target: &IdentityCandidate,
+    candidates: &[IdentityCandidate],
+) -> Option<i64> {
+    let name = target.name.trim().to_lowercase();
+    let target_score = target.score?;
+
+    let same_player: Vec<&IdentityCandidate> = candidates
+        .iter()
+        .filter(|c| c.name.trim().to_lowercase() == name)
+        .filter(|c| match (&target.country, &c.country) {
+            (Some(a), Some(b)) => a == b,
+            _ => true,
+        })
+        .filter(|c| {
+            c.score
+                .is_some_and(|s| (s - target_score).abs() <= SCORE_TOLERANCE)
+        })
+        .collect();
+
+    match same_player.as_slice() {
+        [only] => Some(only.id),
+        _ => None,
+    }
+}
+
+/* ------------------ Mongo storage ------------------ */
+
+fn identities_collection() -> Result<Collection<PlayerIdentity>> {
+    Ok(mongo_client()?
+        .database("bot-swbox-db")
+        .collection::<PlayerIdentity>("player-identities"))
+}
+
+async fn find_identity(field: &str, id: i64) -> Result<Option<PlayerIdentity>> {
+    Ok(identities_collection()?
+        .find_one(doc! { field: id })
+        .await?)
+}
+
+async fn save_identity(identity: &PlayerIdentity) -> Result<()> {
+    let col = identities_collection()?;
+    col.replace_one(doc! { "lucksack_id": identity.lucksack_id }, identity)
+        .upsert(true)
+        .await?;
+
+    // Un id SWArena ne désigne qu'un seul joueur : on retire un ancien lien par nom
+    if let Some(swarena_id) = identity.swarena_id {
+        col.update_many(
+            doc! {
+                "swarena_id": swarena_id,
+                "lucksack_id": { "$ne": identity.lucksack_id },
+            },
+            doc! { "$set": {
+                "swarena_id": null,
+                "swrt_player_id": null,
+                "linked_by": null,
+                "updated_at": 0,
+            } },
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/* ------------------ SWArena ------------------ */
+
+fn swarena_candidate(v: &Value, fallback_name: &str) -> Option<IdentityCandidate> {
+    Some(IdentityCandidate {
+        id: v["id"].as_i64()?,
+        name: v["wizard_name"]
+            .as_str()
+            .or(v["name"].as_str())
+            .unwrap_or(fallback_name)
+            .to_string(),
+        country: v["country"].as_str().and_then(known_country),
+        score: v["score"].as_i64().or(v["rating"].as_i64()),
+    })
+}
+
+/// SWArena players found for `name`.
+async fn search_swarena_players(name: &str) -> Result<Vec<IdentityCandidate>> {
+    let url = format!("https://api.swarena.gg/player/search/{}", name);
+    let res = http_client()
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| anyhow!("SWArena request error: {}", e))?;
+
+    if !res.status().is_success() {
+        return Err(anyhow!("SWArena status {}", res.status()));
+    }
+
+    let v: Value = res
+        .json()
+        .await
+        .map_err(|e| anyhow!("Failed to parse SWArena JSON: {}", e))?;
+
+    Ok(v["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|p| swarena_candidate(p, name))
+        .collect())
+}
+
+/// Latest SWArena profile of a player (name, country, score).
+async fn get_swarena_profile(swarena_id: i64) -> Result<IdentityCandidate> {
+    let seasons = get_player_seasons_played(swarena_id.to_string())
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let season = seasons
+        .into_iter()
+        .max()
+        .ok_or_else(|| anyhow!("No SWArena season"))?;
+
+    let url = format!(
+        "https://api.swarena.gg/player/{}/summary?season={}",
+        swarena_id, season
+    );
+    let v: Value = http_client()
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| anyhow!("SWArena request error: {}", e))?
+        .json()
+        .await
+        .map_err(|e| anyhow!("Failed to parse SWArena JSON: {}", e))?;
+
+    let mut data = v["data"].clone();
+    if data.is_null() {
+        return Err(anyhow!("Missing SWArena profile"));
+    }
+    data["id"] = Value::from(swarena_id);
+    swarena_candidate(&data, "").ok_or_else(|| anyhow!("Invalid SWArena profile"))
+}
+
+/* ------------------ Resolution ------------------ */
+
+/// Identity of a lucksack player, linking the missing ids first. `seen` is
+/// the player as shown by lucksack, when the caller already has it.
+pub async fn identity_from_lucksack(
+    lucksack_id: i64,
+    seen: Option<IdentityCandidate>,
+) -> Result<PlayerIdentity> {
+    let now = chrono::Utc::now().timestamp();
+    let stored = find_identity("lucksack_id", lucksack_id).await?;
+
+    if let Some(identity) = &stored {
+        if identity.is_settled(now) {
+            return Ok(identity.clone());
+        }
+    }
+
+    let mut identity = match stored {
+        Some(mut identity) => {
+            identity.clear_name_match();
+            identity
+        }
+        None => PlayerIdentity {
+            lucksack_id,
+            swarena_id: None,
+            swrt_player_id: None,
+            name: None,
+            country: None,
+            linked_by: None,
+            updated_at: now,
+        },
+    };
+
+    // Profil lucksack : nom, pays et score servent à retrouver le joueur ailleurs
+    let seen = match seen {
+        Some(seen) => Some(seen),
+        None => match get_lucksack_season_numbers()
+            .await
+            .ok()
+            .and_then(|s| s.first().copied())
+        {
+            Some(season) => get_lucksack_player_summary(lucksack_id, season)
+                .await
+                .ok()
+                .map(|s| IdentityCandidate {
+                    id: lucksack_id,
+                    name: s.user_info.username,
+                    country: known_country(&s.user_info.country),
+                    score: Some(s.summary.current_score as i64),
+                }),
+            None => None,
+        },
+    };
+    if let Some(seen) = &seen {
+        identity.name = Some(seen.name.clone());
+        identity.country = seen.country.clone();
+    }
+
+    if identity.swarena_id.is_none() {
+        if let Some(swarena_id) = list_aliases()
+            .into_iter()
+            .find(|a| a.lucksack_id == lucksack_id)
+            .and_then(|a| a.swarena_id)
+        {
+            identity.swarena_id = Some(swarena_id);
+            identity.linked_by = Some(IdentityLink::Alias);
+        } else if let Some(seen) = &seen {
+            let candidates = search_swarena_players(&seen.name).await.unwrap_or_default();
+            if let Some(swarena_id) = match_candidate(seen, &candidates) {
+                identity.swarena_id = Some(swarena_id);
+                identity.linked_by = Some(IdentityLink::NameMatch);
+            }
+        }
+    }
+
+    if let (Some(swarena_id), None) = (identity.swarena_id, identity.swrt_player_id) {
+        identity.swrt_player_id = get_swrt_id_from_db_by_player_id(swarena_id).await.ok();
+    }
+
+    identity.updated_at = now;
+    save_identity(&identity).await?;
+    Ok(identity)
+}
+
+/// Identity of a SWArena player, `None` if no lucksack player matches.
+pub async fn identity_from_swarena(swarena_id: i64) -> Result<Option<PlayerIdentity>> {
+    let now = chrono::Utc::now().timestamp();
+    if let Some(identity) = find_identity("swarena_id", swarena_id).await? {
+        if identity.is_settled(now) {
+            return Ok(Some(identity));
+        }
+    }
+
+    let lucksack_id = match list_aliases()
+        .into_iter()
+        .find(|a| a.swarena_id == Some(swarena_id))
+    {
+        Some(alias) => Some(alias.lucksack_id),
+        None => {
+            let profile = get_swarena_profile(swarena_id).await?;
+            let candidates: Vec<IdentityCandidate> = search_players_lucksack(&profile.name)
+                .await?
+                .into_iter()
+                .map(|p| IdentityCandidate {
+                    id: p.player_id,
+                    name: p.username,
+                    country: known_country(&p.country),
+                    score: p.current_score.map(i64::from),
+                })
+                .collect();
+            match_candidate(&profile, &candidates)
+        }
+    };
+
+    let Some(lucksack_id) = lucksack_id else {
+        return Ok(None);
+    };
+    let mut identity = identity_from_lucksack(lucksack_id, None).await?;
+
+    // Le lien trouvé depuis SWArena fait foi si la recherche inverse a échoué
+    if identity.swarena_id.is_none() {
+        identity.swarena_id = Some(swarena_id);
+        identity.linked_by = Some(IdentityLink::NameMatch);
+        identity.swrt_player_id = get_swrt_id_from_db_by_player_id(swarena_id).await.ok();
+        save_identity(&identity).await?;
+    }
+    Ok(Some(identity))
+}
+
+/// Identity of a SWRanking player, through its SWArena id.
+pub async fn identity_from_swrt(swrt_player_id: i64) -> Result<Option<PlayerIdentity>> {
+    if let Some(identity) = find_identity("swrt_player_id", swrt_player_id).await? {
+        if identity.is_settled(chrono::Utc::now().timestamp()) {
+            return Ok(Some(identity));
+        }
+    }
+    match get_player_id_from_db_by_swrt_id(swrt_player_id).await {
+        Ok(swarena_id) => identity_from_swarena(swarena_id).await,
+        Err(_) => Ok(None),
+    }
+}
+
+/// Identity from any identifier: Discord mention, `lucksack:`/`swarena:`/`swrt:`
+/// id, alias, or a lucksack name matching a single player.
+pub async fn resolve_identity(input: &str) -> Result<Option<PlayerIdentity>> {
+    let input = input.trim();
+
+    if let Some(discord_id) = parse_discord_mention_to_id(input) {
+        let Some(link) = get_user_link(discord_id).await? else {
+            return Ok(None);
+        };
+        let lucksack_id = link
+            .get_i64("swrt_player_id")
+            .map_err(|_| anyhow!("Invalid stored player_id in DB"))?;
+        return identity_from_lucksack(lucksack_id, None).await.map(Some);
+    }
+
+    if let Some(id) = parse_prefixed_id(input) {
+        return match id {
+            PlayerIdInput::Lucksack(id) => identity_from_lucksack(id, None).await.map(Some),
+            PlayerIdInput::Swarena(id) => identity_from_swarena(id).await,
+            PlayerIdInput::Swrt(id) => identity_from_swrt(id).await,
+        };
+    }
+
+    if let Some(lucksack_id) = find_player_by_alias(input) {
+        return identity_from_lucksack(lucksack_id, None).await.map(Some);
+    }
+
+    let players = search_players_lucksack(input).await?;
+    let exact: Vec<_> = players
+        .into_iter()
+        .filter(|p| p.username.eq_ignore_ascii_case(input))
+        .collect();
+    match exact.as_slice() {
+        [player] => identity_from_lucksack(
+            player.player_id,
+            Some(IdentityCandidate {
+                id: player.player_id,
+                name: player.username.clone(),
+                country: known_country(&player.country),
+                score: player.current_score.map(i64::from),
+            }),
+        )
+        .await
+        .map(Some),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(
+        id: i64,
+        name: &str,
+        country: Option<&str>,
+        score: Option<i64>,
+    ) -> IdentityCandidate {
+        IdentityCandidate {
+            id,
+            name: name.to_string(),
+            country: country.map(str::to_string),
+            score,
+        }
+    }
+
+    #[test]
+    fn links_unique_name_with_close_score() {
+        let target = candidate(1, "Tars", Some("CN"), Some(3100));
+        let candidates = [
+            candidate(10, "tars ", Some("CN"), Some(3050)),
+            candidate(11, "Lest", Some("JP"), Some(3100)),
+        ];
+        assert_eq!(match_candidate(&target, &candidates), Some(10));
+    }
+
+    #[test]
+    fn refuses_unique_name_with_distant_score() {
+        let target = candidate(1, "Tars", Some("CN"), Some(3100));
+        let candidates = [candidate(10, "Tars", Some("CN"), Some(1500))];
+        assert_eq!(match_candidate(&target, &candidates), None);
+    }
+
+    #[test]
+    fn refuses_clashing_countries() {
+        let target = candidate(1, "Tars", Some("CN"), Some(3100));
+        let candidates = [candidate(10, "Tars", Some("KR"), Some(3100))];
+        assert_eq!(match_candidate(&target, &candidates), None);
+
+        // Pays inconnu d'un côté : pas de conflit
+        let candidates = [candidate(10, "Tars", None, Some(3100))];
+        assert_eq!(match_candidate(&target, &candidates), Some(10));
+    }
+
+    #[test]
+    fn refuses_missing_score() {
+        let candidates = [candidate(10, "Tars", Some("CN"), Some(3100))];
+        let target = candidate(1, "Tars", Some("CN"), None);
+        assert_eq!(match_candidate(&target, &candidates), None);
+
+        let target = candidate(1, "Tars", Some("CN"), Some(3100));
+        let candidates = [candidate(10, "Tars", Some("CN"), None)];
+        assert_eq!(match_candidate(&target, &candidates), None);
+    }
+
+    #[test]
+    fn same_name_candidates_need_a_single_close_score() {
+        let target = candidate(1, "Tars", None, Some(3100));
+        let candidates = [
+            candidate(10, "Tars", Some("CN"), Some(3120)),
+            candidate(11, "Tars", Some("FR"), Some(1800)),
+        ];
+        assert_eq!(match_candidate(&target, &candidates), Some(10));
+
+        let candidates = [
+            candidate(10, "Tars", Some("CN"), Some(3120)),
+            candidate(11, "Tars", Some("FR"), Some(3080)),
+        ];
+        assert_eq!(match_candidate(&target, &candidates), None);
+    }
+
+    #[test]
+    fn name_match_links_expire() {
+        let now = 10 * RELINK_AFTER_SECS;
+        let mut identity = PlayerIdentity {
+            lucksack_id: 1,
+            swarena_id: Some(2),
+            swrt_player_id: Some(3),
+            name: None,
+            country: None,
+            linked_by: Some(IdentityLink::NameMatch),
+            updated_at: now - RELINK_AFTER_SECS - 1,
+        };
+        assert!(!identity.is_settled(now));
+
+        identity.updated_at = now - 60;
+        assert!(identity.is_settled(now));
+
+        identity.linked_by = Some(IdentityLink::Alias);
+        identity.updated_at = 0;
+        assert!(identity.is_settled(now));
+
+        identity.linked_by = Some(IdentityLink::NameMatch);
+        identity.clear_name_match();
+        assert_eq!((identity.swarena_id, identity.swrt_player_id), (None, None));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod command;
+pub mod identity;
 pub mod models;
 pub mod utils;
@@ -1,8 +1,5 @@
-use ab_glyph::{Font, FontArc, PxScale, ScaleFont};
-use anyhow::{anyhow, Context, Result};
-use image::GenericImage;
-use image::{DynamicImage, ImageBuffer, Rgba, RgbaImage};
-use imageproc::drawing::draw_text_mut;
+use anyhow::{anyhow, Result};
+use futures::StreamExt;
 use mongodb::{bson::doc, Collection};
 use poise::serenity_prelude as serenity;
 use serde::Deserialize;
@@ -14,8 +11,14 @@ use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 use std::sync::{OnceLock, RwLock};
 
+use crate::commands::player_names::snapshots::spawn_record_player_names;
 use crate::commands::shared::clients::{http_client, mongo_client};
-use crate::commands::shared::player_alias::PLAYER_ALIAS_MAP;
+use crate::commands::shared::models::ReplayDisplay;
+use crate::commands::shared::player_alias::player_alias;
+use crate::commands::shared::render::{
+    prune_tmp_replay_files, render_replay_frames, render_replay_grid, save_gif, save_png,
+    MatchOutcome, MatchSection, ReplayLayout, TeamDraft,
+};
 use crate::{CONQUEROR_EMOJI_ID, GUARDIAN_EMOJI_ID, PUNISHER_EMOJI_ID};
 
 /* ------------------ Mongo / emojis helpers ------------------ */
@@ -29,39 +32,17 @@ pub async fn get_mob_emoji_collection() -> Result<Collection<mongodb::bson::Docu
 
 /* ------------------ Replay image generation ------------------ */
 
-static GLOBAL_MONSTER_IMAGE_CACHE: OnceLock<RwLock<HashMap<String, DynamicImage>>> =
-    OnceLock::new();
-static CROSS_IMAGE_100: OnceLock<RgbaImage> = OnceLock::new();
-static BANNER_FONT: OnceLock<FontArc> = OnceLock::new();
-static LUCKSACK_REPLAY_PATH_CACHE: OnceLock<RwLock<HashMap<u64, PathBuf>>> = OnceLock::new();
-
-fn global_monster_image_cache() -> &'static RwLock<HashMap<String, DynamicImage>> {
-    GLOBAL_MONSTER_IMAGE_CACHE.get_or_init(|| RwLock::new(HashMap::new()))
-}
+const REPLAY_GIF_FRAME_DELAY_MS: u32 = 2500;
 
-fn cross_image_100() -> &'static RgbaImage {
-    CROSS_IMAGE_100.get_or_init(|| {
-        const CROSS_BYTES: &[u8] = include_bytes!("cross.png");
-        image::load_from_memory(CROSS_BYTES)
-            .expect("Erreur lors du chargement de cross.png")
-            .resize_exact(100, 100, image::imageops::FilterType::Triangle)
-            .to_rgba8()
-    })
-}
-
-fn banner_font() -> &'static FontArc {
-    BANNER_FONT.get_or_init(|| {
-        const FONT_BYTES: &[u8] = include_bytes!("NotoSansCJK-Regular.otf");
-        FontArc::try_from_vec(FONT_BYTES.to_vec()).expect("Police invalide")
-    })
-}
+static LUCKSACK_REPLAY_PATH_CACHE: OnceLock<RwLock<HashMap<u64, PathBuf>>> = OnceLock::new();
 
 fn lucksack_replay_path_cache() -> &'static RwLock<HashMap<u64, PathBuf>> {
     LUCKSACK_REPLAY_PATH_CACHE.get_or_init(|| RwLock::new(HashMap::new()))
 }
 
-fn lucksack_matches_cache_key(matches: &[LucksackMatch]) -> u64 {
+fn lucksack_matches_cache_key(matches: &[LucksackMatch], display: ReplayDisplay) -> u64 {
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    (display as u8).hash(&mut hasher);
 
     for m in matches.iter().take(6) {
         m.won.hash(&mut hasher);
@@ -84,47 +65,13 @@ fn lucksack_matches_cache_key(matches: &[LucksackMatch]) -> u64 {
     hasher.finish()
 }
 
-fn prune_tmp_replay_files(max_files: usize) {
-    let Ok(entries) = fs::read_dir("/tmp") else {
-        return;
-    };
-
-    let mut files: Vec<(PathBuf, std::time::SystemTime)> = entries
-        .flatten()
-        .filter_map(|entry| {
-            let path = entry.path();
-            let name = path.file_name().and_then(|n| n.to_str())?;
-
-            if !(name.starts_with("replay-") && name.ends_with(".png")) {
-                return None;
-            }
-
-            let modified = entry
-                .metadata()
-                .ok()
-                .and_then(|m| m.modified().ok())
-                .unwrap_or(std::time::UNIX_EPOCH);
-
-            Some((path, modified))
-        })
-        .collect();
-
-    let overflow = files.len().saturating_sub(max_files);
-    if overflow == 0 {
-        return;
-    }
-
-    // Remove oldest files first to keep most recent replay images.
-    files.sort_by_key(|(_, modified)| *modified);
-    for (path, _) in files.into_iter().take(overflow) {
-        let _ = fs::remove_file(path);
-    }
-}
-
-pub async fn create_lucksack_replay_image(matches: &[LucksackMatch]) -> Result<PathBuf> {
+pub async fn create_lucksack_replay_image(
+    matches: &[LucksackMatch],
+    display: ReplayDisplay,
+) -> Result<PathBuf> {
     prune_tmp_replay_files(128);
 
-    let key = lucksack_matches_cache_key(matches);
+    let key = lucksack_matches_cache_key(matches, display);
 
     let cached_path = if let Ok(read_guard) = lucksack_replay_path_cache().read() {
         read_guard.get(&key).cloned()
@@ -137,123 +84,35 @@ pub async fn create_lucksack_replay_image(matches: &[LucksackMatch]) -> Result<P
     }
 
     let img_map = get_comid_to_image_map();
-    let mut sections: Vec<RgbaImage> = Vec::new();
-    let mut image_cache: HashMap<String, DynamicImage> = HashMap::new();
-
-    for m in matches.iter().take(6) {
-        let filenames_mine: Vec<String> = m
-            .my_monsters
-            .iter()
-            .filter_map(|&id| img_map.get(&(id as i32)).cloned())
-            .collect();
-        let filenames_opp: Vec<String> = m
-            .opponent_monsters
-            .iter()
-            .filter_map(|&id| img_map.get(&(id as i32)).cloned())
-            .collect();
+    let sections: Vec<MatchSection> = matches
+        .iter()
+        .take(6)
+        .filter_map(|m| lucksack_match_to_section(m, img_map))
+        .collect();
 
-        if filenames_mine.is_empty() || filenames_opp.is_empty() {
-            continue;
+    let output_path = match display {
+        ReplayDisplay::Grid => {
+            let canvas = render_replay_grid(&sections, &ReplayLayout::default()).await?;
+            save_png(canvas, PathBuf::from(format!("/tmp/replay-{}.png", key))).await?
         }
-
-        let ids_mine: Vec<u32> = m.my_monsters.iter().map(|&id| id as u32).collect();
-        let ids_opp: Vec<u32> = m.opponent_monsters.iter().map(|&id| id as u32).collect();
-
-        let img_me = create_team_collage_custom_layout(
-            &filenames_mine,
-            &ids_mine,
-            m.my_bans as u32,
-            m.my_leader as u32,
-            m.had_first_pick,
-            &mut image_cache,
-        )
-        .await?;
-
-        let img_opp = create_team_collage_custom_layout(
-            &filenames_opp,
-            &ids_opp,
-            m.opponent_bans as u32,
-            m.opponent_leader as u32,
-            !m.had_first_pick,
-            &mut image_cache,
-        )
-        .await?;
-
-        let image_width = img_me.width() / 3;
-        let spacing = image_width / 2;
-        let combined_width = img_me.width() + img_opp.width() + spacing;
-        let height = img_me.height().max(img_opp.height());
-
-        let mut combined = ImageBuffer::new(combined_width, height);
-        combined.copy_from(&img_me, 0, 0).unwrap();
-        combined
-            .copy_from(&img_opp, img_me.width() + spacing, 0)
-            .unwrap();
-
-        // Parse the match timestamp → "DD/MM - HH:MM"
-        let time_text = chrono::DateTime::parse_from_rfc3339(&m.battle_time)
-            .map(|dt| dt.format("%d/%m - %H:%M").to_string())
-            .unwrap_or_else(|_| m.battle_time.get(..16).unwrap_or("").replace('-', "/"));
-
-        let left_text = format!("{} - {}", m.my_score, m.my_username);
-        let right_text = format!("{} - {}", m.opponent_username, m.opponent_score);
-
-        let banner = create_match_banner(
-            &left_text,
-            &time_text,
-            &right_text,
-            combined_width,
-            Rgba([0, 0, 0, 0]),
-        );
-        let banner_height = banner.height();
-
-        let border = 10u32;
-        let inner_w = combined_width;
-        let inner_h = banner_height + combined.height();
-        let total_w = inner_w + 2 * border;
-        let total_h = inner_h + 2 * border;
-
-        let bg_color = if m.won {
-            Rgba([0, 255, 0, 100])
-        } else {
-            Rgba([255, 0, 0, 100])
-        };
-
-        let mut section = ImageBuffer::from_pixel(total_w, total_h, bg_color);
-        let mut inner = ImageBuffer::from_pixel(inner_w, inner_h, Rgba([0, 0, 0, 0]));
-        inner.copy_from(&banner, 0, 0).unwrap();
-        inner.copy_from(&combined, 0, banner_height).unwrap();
-        section.copy_from(&inner, border, border).unwrap();
-        sections.push(section);
-    }
-
-    if sections.is_empty() {
-        return Err(anyhow!("No valid matches to render"));
-    }
-
-    let cols = 2u32;
-    let rows = (sections.len() as u32).div_ceil(cols);
-    let padding = 10u32;
-    let sw = sections[0].width();
-    let sh = sections[0].height();
-    let full_w = cols * sw + (cols - 1) * padding;
-    let full_h = rows * sh + (rows - 1) * padding;
-
-    let mut canvas = ImageBuffer::new(full_w, full_h);
-    for (i, section) in sections.iter().enumerate() {
-        let col = (i as u32) % cols;
-        let row = (i as u32) / cols;
-        canvas.copy_from(section, col * (sw + padding), row * (sh + padding))?;
-    }
-
-    let output_path = PathBuf::from(format!("/tmp/replay-{}.png", key));
-    let output_path_clone = output_path.clone();
-    tokio::task::spawn_blocking(move || {
-        std::fs::create_dir_all("/tmp")?;
-        canvas.save(&output_path_clone)?;
-        Ok::<_, anyhow::Error>(output_path_clone)
-    })
-    .await??;
+        ReplayDisplay::Gallery => {
+            let layout = ReplayLayout {
+                cols: 1,
+                ..Default::default()
+            };
+            let canvas = render_replay_grid(&sections, &layout).await?;
+            save_png(canvas, PathBuf::from(format!("/tmp/replay-{}.png", key))).await?
+        }
+        ReplayDisplay::Animated => {
+            let frames = render_replay_frames(&sections, &ReplayLayout::default()).await?;
+            save_gif(
+                frames,
+                PathBuf::from(format!("/tmp/replay-{}.gif", key)),
+                REPLAY_GIF_FRAME_DELAY_MS,
+            )
+            .await?
+        }
+    };
 
     if let Ok(mut write_guard) = lucksack_replay_path_cache().write() {
         if write_guard.len() >= 128 {
@@ -266,276 +125,50 @@ pub async fn create_lucksack_replay_image(matches: &[LucksackMatch]) -> Result<P
     Ok(output_path)
 }
 
-async fn create_team_collage_custom_layout(
-    image_filenames: &[String],
-    monster_ids: &[u32],
-    ban_id: u32,
-    leader_id: u32,
-    first_pick: bool,
-    cache: &mut HashMap<String, DynamicImage>,
-) -> Result<RgbaImage> {
-    let mut images = Vec::new();
-    for filename in image_filenames {
-        let img = load_image_local(filename, cache).await?;
-        images.push(img);
-    }
-
-    let width = images[0].width();
-    let height = images[0].height();
-    let mut collage = ImageBuffer::new(width * 3, height * 2);
-
-    let cross = if width == 100 && height == 100 {
-        cross_image_100().clone()
-    } else {
-        image::imageops::resize(
-            cross_image_100(),
-            width,
-            height,
-            image::imageops::FilterType::Triangle,
-        )
+fn lucksack_match_to_section(
+    m: &LucksackMatch,
+    img_map: &HashMap<i32, String>,
+) -> Option<MatchSection> {
+    let to_draft = |monsters: &[i64], ban: i64, leader: i64, first_pick: bool| TeamDraft {
+        image_filenames: monsters
+            .iter()
+            .filter_map(|&id| img_map.get(&(id as i32)).cloned())
+            .collect(),
+        monster_ids: monsters.iter().map(|&id| id as u32).collect(),
+        ban_id: ban as u32,
+        leader_id: leader as u32,
+        first_pick,
     };
 
-    let mut grid_slots = [(0, 0); 5];
+    let left = to_draft(&m.my_monsters, m.my_bans, m.my_leader, m.had_first_pick);
+    let right = to_draft(
+        &m.opponent_monsters,
+        m.opponent_bans,
+        m.opponent_leader,
+        !m.had_first_pick,
+    );
 
-    if first_pick {
-        grid_slots[1] = (1, 0);
-        grid_slots[2] = (1, 1);
-        grid_slots[3] = (2, 0);
-        grid_slots[4] = (2, 1);
-        grid_slots[0] = (0, 1);
-    } else {
-        grid_slots[0] = (0, 0);
-        grid_slots[1] = (0, 1);
-        grid_slots[2] = (1, 0);
-        grid_slots[3] = (1, 1);
-        grid_slots[4] = (2, 1);
+    if left.image_filenames.is_empty() || right.image_filenames.is_empty() {
+        return None;
     }
 
-    for (i, (img, &monster_id)) in images.iter().zip(monster_ids).enumerate() {
-        let mut rgba = img.to_rgba8();
-
-        if monster_id == leader_id {
-            let border_color = Rgba([255, 215, 0, 255]);
-            let thickness = 5;
-            for x in 0..width {
-                for t in 0..thickness {
-                    rgba.put_pixel(x, t, border_color);
-                    rgba.put_pixel(x, height - 1 - t, border_color);
-                }
-            }
-            for y in 0..height {
-                for t in 0..thickness {
-                    rgba.put_pixel(t, y, border_color);
-                    rgba.put_pixel(width - 1 - t, y, border_color);
-                }
-            }
-        }
-
-        if monster_id == ban_id {
-            image::imageops::overlay(&mut rgba, &cross, 0, 0);
-        }
+    // Parse the match timestamp → "DD/MM - HH:MM"
+    let time_text = chrono::DateTime::parse_from_rfc3339(&m.battle_time)
+        .map(|dt| dt.format("%d/%m - %H:%M").to_string())
+        .unwrap_or_else(|_| m.battle_time.get(..16).unwrap_or("").replace('-', "/"));
 
-        let (grid_x, grid_y) = grid_slots[i];
-        let x = grid_x as u32 * width;
-        let y = if (first_pick && i == 0) || (!first_pick && i == 4) {
-            ((collage.height() - height) / 2).min(collage.height() - height)
+    Some(MatchSection {
+        left,
+        right,
+        left_text: format!("{} - {}", m.my_score, m.my_username),
+        center_text: time_text,
+        right_text: format!("{} - {}", m.opponent_username, m.opponent_score),
+        outcome: if m.won {
+            MatchOutcome::Win
         } else {
-            grid_y as u32 * height
-        };
-
-        collage.copy_from(&rgba, x, y).unwrap();
-    }
-
-    Ok(collage)
-}
-
-async fn load_image_local(
-    filename: &str,
-    cache: &mut HashMap<String, DynamicImage>,
-) -> Result<DynamicImage> {
-    if let Some(img) = cache.get(filename) {
-        return Ok(img.clone());
-    }
-
-    if let Ok(read_guard) = global_monster_image_cache().read() {
-        if let Some(img) = read_guard.get(filename) {
-            let cloned = img.clone();
-            cache.insert(filename.to_string(), cloned.clone());
-            return Ok(cloned);
-        }
-    }
-
-    let path = format!("assets/monster_images/{}", filename);
-    let filename_string = filename.to_string();
-
-    let img: DynamicImage;
-
-    let local_result = tokio::task::spawn_blocking(move || -> Result<DynamicImage> {
-        let data = std::fs::read(&path)
-            .with_context(|| format!("Failed to read image file: {}", filename_string))?;
-        image::load_from_memory(&data)
-            .with_context(|| format!("Failed to decode image: {}", filename_string))
+            MatchOutcome::Loss
+        },
     })
-    .await;
-
-    match local_result {
-        Ok(Ok(image)) => img = image,
-        Ok(Err(e))
-            if e.downcast_ref::<std::io::Error>().map(|io| io.kind())
-                == Some(std::io::ErrorKind::NotFound) =>
-        {
-            let url = format!(
-                "https://swarfarm.com/static/herders/images/monsters/{}",
-                filename
-            );
-            let bytes = http_client()
-                .get(&url)
-                .send()
-                .await
-                .with_context(|| format!("Failed to download image from: {}", url))?
-                .bytes()
-                .await
-                .with_context(|| {
-                    format!("Failed to read downloaded bytes for file: {}", filename)
-                })?;
-
-            img = image::load_from_memory(&bytes)
-                .with_context(|| format!("Failed to decode downloaded image: {}", filename))?;
-        }
-        Ok(Err(e)) => return Err(e),
-        Err(e) => {
-            return Err(anyhow!(
-                "Blocking task failed for file: {}: {}",
-                filename,
-                e
-            ))
-        }
-    }
-
-    let img = img.resize_exact(100, 100, image::imageops::FilterType::Triangle);
-
-    if let Ok(mut write_guard) = global_monster_image_cache().write() {
-        write_guard.insert(filename.to_string(), img.clone());
-    }
-
-    cache.insert(filename.to_string(), img.clone());
-    Ok(img)
-}
-
-fn create_match_banner(
-    left_text: &str,
-    center_text: &str,
-    right_text: &str,
-    width: u32,
-    color: Rgba<u8>,
-) -> RgbaImage {
-    let height = 40;
-    let mut image = ImageBuffer::from_pixel(width, height, color);
-
-    let font = banner_font();
-
-    let scale = PxScale::from(26.0);
-    let margin = 8.0_f32;
-    let width_f = width as f32;
-
-    let max_left_width = width_f / 3.0 - margin * 2.0;
-    let max_center_width = width_f / 3.0 - margin * 2.0;
-    let max_right_width = width_f / 3.0 - margin * 2.0;
-
-    let left = fit_text_to_width(font, scale, left_text, max_left_width);
-    let center = fit_text_to_width(font, scale, center_text, max_center_width);
-    let right = fit_text_to_width(font, scale, right_text, max_right_width);
-
-    let center_w = text_width(font, scale, &center);
-    let right_w = text_width(font, scale, &right);
-
-    let y = 8;
-
-    draw_bold_text_mut(
-        &mut image,
-        Rgba([255, 255, 255, 255]),
-        margin as i32,
-        y,
-        scale,
-        font,
-        &left,
-    );
-
-    let center_x: i32 = ((width_f - center_w) / 2.0).round() as i32;
-    draw_bold_text_mut(
-        &mut image,
-        Rgba([255, 255, 255, 255]),
-        center_x,
-        y,
-        scale,
-        font,
-        &center,
-    );
-
-    let right_x: i32 = (width_f - right_w - margin).round() as i32;
-    draw_bold_text_mut(
-        &mut image,
-        Rgba([255, 255, 255, 255]),
-        right_x,
-        y,
-        scale,
-        font,
-        &right,
-    );
-
-    image
-}
-
-fn draw_bold_text_mut(
-    image: &mut RgbaImage,
-    color: Rgba<u8>,
-    x: i32,
-    y: i32,
-    scale: PxScale,
-    font: &FontArc,
-    text: &str,
-) {
-    let offsets = [(0, 0), (1, 0), (0, 1), (1, 1)];
-    for (dx, dy) in offsets {
-        draw_text_mut(image, color, x + dx, y + dy, scale, font, text);
-    }
-}
-
-fn text_width(font: &FontArc, scale: PxScale, text: &str) -> f32 {
-    let scaled = font.as_scaled(scale);
-    let mut pen = 0.0f32;
-    let mut prev_id = None;
-
-    for ch in text.chars() {
-        let g = scaled.glyph_id(ch);
-        if let Some(pid) = prev_id {
-            pen += scaled.kern(pid, g);
-        }
-        pen += scaled.h_advance(g);
-        prev_id = Some(g);
-    }
-    pen
-}
-
-fn fit_text_to_width(font: &FontArc, scale: PxScale, text: &str, max_width: f32) -> String {
-    let s = text.to_string();
-
-    if text_width(font, scale, &s) <= max_width {
-        return s;
-    }
-
-    let mut chars: Vec<char> = s.chars().collect();
-    while !chars.is_empty()
-        && text_width(font, scale, &chars.iter().collect::<String>()) > max_width
-    {
-        chars.pop();
-    }
-
-    let mut result: String = chars.iter().collect();
-    if !result.is_empty() {
-        result.push('…');
-    }
-    result
 }
 
 pub fn parse_discord_mention_to_id(s: &str) -> Option<u64> {
@@ -614,9 +247,19 @@ pub async fn search_players_lucksack(username: &str) -> Result<Vec<LucksackSearc
         return Err(anyhow!("HTTP {}", res.status()));
     }
 
-    res.json::<Vec<LucksackSearchPlayer>>()
+    let players = res
+        .json::<Vec<LucksackSearchPlayer>>()
         .await
-        .map_err(|e| anyhow!("Failed to parse search JSON: {}", e))
+        .map_err(|e| anyhow!("Failed to parse search JSON: {}", e))?;
+
+    spawn_record_player_names(
+        players
+            .iter()
+            .map(|p| (p.player_id, p.username.clone()))
+            .collect(),
+    );
+
+    Ok(players)
 }
 
 pub async fn get_lucksack_season_numbers() -> Result<Vec<i32>> {
@@ -672,9 +315,17 @@ pub async fn get_lucksack_player_summary(
         return Err(anyhow!("HTTP {}", res.status()));
     }
 
-    res.json::<LucksackPlayerSummary>()
+    let summary = res
+        .json::<LucksackPlayerSummary>()
         .await
-        .map_err(|e| anyhow!("Failed to parse summary JSON: {}", e))
+        .map_err(|e| anyhow!("Failed to parse summary JSON: {}", e))?;
+
+    spawn_record_player_names(vec![(
+        summary.user_info.player_id,
+        summary.user_info.username.clone(),
+    )]);
+
+    Ok(summary)
 }
 
 /* ------------------ Lucksack rank emojis ------------------ */
@@ -766,7 +417,7 @@ pub async fn get_lucksack_player_ld5_box(
 /// com2us_id → image_filename (from monsters_elements.json)
 static COMID_TO_IMAGE: OnceLock<HashMap<i32, String>> = OnceLock::new();
 
-fn get_comid_to_image_map() -> &'static HashMap<i32, String> {
+pub fn get_comid_to_image_map() -> &'static HashMap<i32, String> {
     COMID_TO_IMAGE.get_or_init(|| {
         let file =
             fs::read_to_string("monsters_elements.json").expect("monsters_elements.json not found");
@@ -785,39 +436,44 @@ fn get_comid_to_image_map() -> &'static HashMap<i32, String> {
     })
 }
 
+/// Emoji Discord d'un monstre (com2us_id), "❓" si introuvable
+pub async fn get_lucksack_monster_emoji(
+    collection: &Collection<mongodb::bson::Document>,
+    monster_id: i32,
+) -> String {
+    let Some(image_filename) = get_comid_to_image_map().get(&monster_id) else {
+        return "❓".to_string();
+    };
+
+    // "unit_icon_0168_1_5.png" → "0168_1_5"
+    let emoji_name = image_filename
+        .trim_start_matches("unit_icon_")
+        .trim_end_matches(".png");
+
+    let doc = collection
+        .find_one(doc! { "name": emoji_name })
+        .await
+        .ok()
+        .flatten();
+
+    if let Some(d) = doc {
+        let id = d.get_str("id").unwrap_or("");
+        let name = d.get_str("name").unwrap_or("unit");
+        format!("<:{}:{}>", name, id)
+    } else {
+        "❓".to_string()
+    }
+}
+
 pub async fn format_lucksack_top_monsters(picks: &[LucksackPickEntry]) -> String {
     let Ok(collection) = get_mob_emoji_collection().await else {
         return String::new();
     };
 
-    let img_map = get_comid_to_image_map();
     let mut lines = vec![];
 
     for (idx, pick) in picks.iter().take(10).enumerate() {
-        let monster_id = pick.monster_id as i32;
-
-        let emoji_str = if let Some(image_filename) = img_map.get(&monster_id) {
-            // "unit_icon_0168_1_5.png" → "0168_1_5"
-            let emoji_name = image_filename
-                .trim_start_matches("unit_icon_")
-                .trim_end_matches(".png");
-
-            let doc = collection
-                .find_one(doc! { "name": emoji_name })
-                .await
-                .ok()
-                .flatten();
-
-            if let Some(d) = doc {
-                let id = d.get_str("id").unwrap_or("");
-                let name = d.get_str("name").unwrap_or("unit");
-                format!("<:{}:{}>", name, id)
-            } else {
-                "❓".to_string()
-            }
-        } else {
-            "❓".to_string()
-        };
+        let emoji_str = get_lucksack_monster_emoji(&collection, pick.monster_id as i32).await;
 
         lines.push(format!(
             "{}. {} {} picks, **{:.1}%** WR",
@@ -910,8 +566,7 @@ pub fn create_lucksack_player_embed(
         _ => "??",
     };
 
-    let alias_suffix = PLAYER_ALIAS_MAP
-        .get(&info.player_id)
+    let alias_suffix = player_alias(info.player_id)
         .map(|alias| format!(" (aka. {})", alias))
         .unwrap_or_default();
     let display_name = if alias_suffix.is_empty() {
@@ -1060,6 +715,27 @@ pub struct LucksackMatch {
     pub opponent_score: i32,
 }
 
+impl LucksackMatch {
+    /// Same match seen from the opponent's side.
+    pub fn swapped(&self) -> LucksackMatch {
+        LucksackMatch {
+            won: !self.won,
+            had_first_pick: !self.had_first_pick,
+            battle_time: self.battle_time.clone(),
+            my_monsters: self.opponent_monsters.clone(),
+            my_leader: self.opponent_leader,
+            my_bans: self.opponent_bans,
+            my_username: self.opponent_username.clone(),
+            my_score: self.opponent_score,
+            opponent_monsters: self.my_monsters.clone(),
+            opponent_leader: self.my_leader,
+            opponent_bans: self.my_bans,
+            opponent_username: self.my_username.clone(),
+            opponent_score: self.my_score,
+        }
+    }
+}
+
 pub async fn get_lucksack_player_matches(
     player_id: i64,
     season: i32,
@@ -1089,3 +765,43 @@ pub async fn get_lucksack_player_matches(
 
     Ok(resp.matches)
 }
+
+/// Fetches the player's matches of the season, `PAGE_SIZE` at a time and up to
+/// `max_matches`. Pages are requested concurrently (offsets derived from
+/// `total_matches`); the results are then merged in order and truncated at the
+/// first short or failing page.
+pub async fn get_all_lucksack_player_matches(
+    player_id: i64,
+    season: i32,
+    total_matches: usize,
+    max_matches: usize,
+) -> Result<Vec<LucksackMatch>> {
+    const PAGE_SIZE: usize = 50;
+    const CONCURRENT_PAGES: usize = 4;
+
+    let wanted = total_matches.min(max_matches);
+    let offsets: Vec<usize> = (0..wanted).step_by(PAGE_SIZE).collect();
+
+    let pages: Vec<Result<Vec<LucksackMatch>>> = futures::stream::iter(offsets)
+        .map(|offset| get_lucksack_player_matches(player_id, season, PAGE_SIZE, offset))
+        .buffered(CONCURRENT_PAGES)
+        .collect()
+        .await;
+
+    let mut matches = Vec::with_capacity(wanted);
+    for page in pages {
+        let page = match page {
+            Ok(page) => page,
+            Err(e) if matches.is_empty() => return Err(e),
+            Err(_) => break,
+        };
+        let is_last = page.len() < PAGE_SIZE;
+        matches.extend(page);
+        if is_last {
+            break;
+        }
+    }
+
+    matches.truncate(wanted);
+    Ok(matches)
+}
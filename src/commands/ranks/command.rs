@@ -1,18 +1,26 @@
-use crate::commands::ranks::utils::{get_prediction_info, get_rank_info, ENABLE_PREDICTION};
+use crate::commands::player_stats::utils::get_lucksack_season_numbers;
+use crate::commands::ranks::history::{
+    create_cutoff_chart, estimate_end_of_season, get_season_history,
+};
+use crate::commands::ranks::prediction::{
+    get_prediction_info, prediction_enabled, prediction_source, PredictionSource,
+};
+use crate::commands::ranks::utils::{
+    get_rank_info, season_end_date, upcoming_season_end, CutoffValues,
+};
 use crate::commands::shared::logs::{get_server_name, send_log};
 use crate::commands::shared::models::LoggerDocument;
 use crate::{
     commands::shared::embed_error_handling::{create_embed_error, schedule_message_deletion},
     Data,
 };
-use chrono::TimeZone;
 use poise::{
     serenity_prelude::{self as serenity, Error},
     CreateReply,
 };
 use serenity::builder::CreateEmbedFooter;
 
-/// 📂 Displays the current scores for ranks (P2 -> G3) with prediction inline (from swrta.top)
+/// 📂 Displays the rank thresholds (P2 -> G3) with their season trend and end-of-season estimate
 ///
 /// Usage: `/get_ranks`
 #[poise::command(slash_command)]
@@ -40,11 +48,20 @@ pub async fn get_ranks(ctx: poise::ApplicationContext<'_, Data, Error>) -> Resul
     };
 
     // 2) Prediction thresholds (HTML scraping) - optional
-    let prediction = if ENABLE_PREDICTION {
-        get_prediction_info().await.ok()
-    } else {
-        None
+    let prediction_on = prediction_enabled();
+    let prediction = get_prediction_info().await;
+
+    // 3) Season history (chart + SWbox estimate), the command works without it
+    let history = match get_lucksack_season_numbers()
+        .await
+        .ok()
+        .and_then(|s| s.first().copied())
+    {
+        Some(season) => get_season_history(season).await.unwrap_or_default(),
+        None => Vec::new(),
     };
+    let season_end = upcoming_season_end();
+    let estimate = estimate_end_of_season(&history, season_end);
 
     let thumbnail = "https://raw.githubusercontent.com/B4tiste/landing-page-bot/refs/heads/main/src/assets/images/old_bot_logo.gif";
 
@@ -52,6 +69,7 @@ pub async fn get_ranks(ctx: poise::ApplicationContext<'_, Data, Error>) -> Resul
     fn build_grouped_description(
         live_pairs: &[(String, i32)],
         pred_pairs: &Option<Vec<(String, i32)>>,
+        estimate: Option<&CutoffValues>,
     ) -> String {
         use std::collections::HashMap;
 
@@ -74,10 +92,14 @@ pub async fn get_ranks(ctx: poise::ApplicationContext<'_, Data, Error>) -> Resul
             for idx in range {
                 let (rank_key, live) = &live_pairs[idx];
                 if let Some(pred) = pred_map.get(rank_key.as_str()) {
-                    description.push_str(&format!("{rank_key} : {live} (→ **{pred}**)\n"));
+                    description.push_str(&format!("{rank_key} : {live} (→ **{pred}**)"));
                 } else {
-                    description.push_str(&format!("{rank_key} : **{live}**\n"));
+                    description.push_str(&format!("{rank_key} : **{live}**"));
+                }
+                if let Some(estimate) = estimate {
+                    description.push_str(&format!(" · est. `{}`", estimate.as_array()[idx]));
                 }
+                description.push('\n');
             }
             description.push('\n');
         }
@@ -87,17 +109,13 @@ pub async fn get_ranks(ctx: poise::ApplicationContext<'_, Data, Error>) -> Resul
 
     // Single section (Current + prediction inline)
     let mut full_description = String::new();
-    if ENABLE_PREDICTION {
+    if prediction_on {
         full_description.push_str("Format → [ELO : live threshold (→ predicted cutoff)]\n\n");
     } else {
         full_description.push_str("Format → [ELO : live threshold]\n\n");
     }
 
-    // Exact season end date/time provided by maintainer: 26/09/2026 07:00:00 (UTC)
-    let date = chrono::Utc
-        .with_ymd_and_hms(2026, 9, 19, 7, 0, 0)
-        .single()
-        .expect("Invalid hardcoded season end date");
+    let date = season_end_date();
 
     // Calculate the remaining time
     let now = chrono::Utc::now();
@@ -126,24 +144,35 @@ pub async fn get_ranks(ctx: poise::ApplicationContext<'_, Data, Error>) -> Resul
         formatted_time
     ));
 
-    full_description.push_str(&build_grouped_description(&scores, &prediction));
+    if estimate.is_some() {
+        full_description
+            .push_str("`est.` → SWbox end-of-season estimate (trend of the last 7 days)\n\n");
+    }
+
+    full_description.push_str(&build_grouped_description(
+        &scores,
+        &prediction,
+        estimate.as_ref(),
+    ));
 
     full_description.push_str("Check out `/services` or [MyShop](https://discord.gg/myshop) if you need help reaching your desired rank.\n");
 
     // Optional small source note if predictions were attempted
-    if ENABLE_PREDICTION {
+    if prediction_on {
+        let source_url = prediction_source().source_url();
         if prediction.is_some() {
-            full_description.push_str("\n*Prediction source: <https://swrta.top/predict>*\n");
+            full_description.push_str(&format!("\n*Prediction source: <{source_url}>*\n"));
         } else {
-            full_description
-                .push_str("\n_Prediction currently unavailable (failed to fetch from swrta.top)._");
+            full_description.push_str(&format!(
+                "\n_Prediction currently unavailable (failed to fetch from <{source_url}>)._"
+            ));
         }
     }
 
     full_description.push_str("\n⚠️ SWbox is not responsible for any data inaccuracy ⚠️");
 
-    let embed = serenity::CreateEmbed::default()
-        .title(if ENABLE_PREDICTION {
+    let mut embed = serenity::CreateEmbed::default()
+        .title(if prediction_on {
             "Rank thresholds (Live + Prediction)"
         } else {
             "Rank thresholds (Live)"
@@ -155,8 +184,21 @@ pub async fn get_ranks(ctx: poise::ApplicationContext<'_, Data, Error>) -> Resul
             "Data is gathered from m.swranking.com",
         ));
 
+    let mut attachments = vec![];
+    if history.len() >= 2 {
+        let file_name = "ranks-cutoffs.png";
+        match create_cutoff_chart(&history, season_end.zip(estimate.as_ref())).await {
+            Ok(bytes) => {
+                attachments.push(serenity::CreateAttachment::bytes(bytes, file_name));
+                embed = embed.image(format!("attachment://{file_name}"));
+            }
+            Err(e) => eprintln!("Failed to render cutoff chart: {e:?}"),
+        }
+    }
+
     let reply = CreateReply {
         embeds: vec![embed],
+        attachments,
         ..Default::default()
     };
 
@@ -0,0 +1,250 @@
+//! Historique des seuils P2 → G3 (collection `rank-cutoffs`), pour tracer
+//! leur évolution sur la saison et estimer les seuils de fin de saison.
+use anyhow::Result;
+use futures::stream::TryStreamExt;
+use image::Rgba;
+use mongodb::bson::doc;
+use mongodb::options::FindOneOptions;
+use mongodb::Collection;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::player_stats::utils::get_lucksack_season_numbers;
+use crate::commands::ranks::prediction::fetch_prediction_cutoffs;
+use crate::commands::ranks::utils::{fetch_live_cutoffs, CutoffValues};
+use crate::commands::shared::chart::{render_line_chart, ChartSeries};
+use crate::commands::shared::clients::mongo_client;
+use crate::commands::shared::render::encode_png;
+
+/// Écart minimum entre deux relevés (évite les doublons après un redémarrage)
+const MIN_RECORD_INTERVAL_SECS: i64 = 50 * 60;
+
+/// Fenêtre utilisée pour la régression de l'estimation de fin de saison
+const ESTIMATE_WINDOW_DAYS: i64 = 7;
+
+/// Nombre minimum de relevés dans la fenêtre pour proposer une estimation
+const MIN_ESTIMATE_POINTS: usize = 6;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CutoffRecord {
+    /// Numéro de saison lucksack (0 pour les anciens relevés, ignorés)
+    #[serde(default)]
+    pub season: i32,
+    pub taken_at: i64,
+    pub live: CutoffValues,
+    pub prediction: Option<CutoffValues>,
+}
+
+fn cutoffs_collection() -> Result<Collection<CutoffRecord>> {
+    Ok(mongo_client()?
+        .database("bot-swbox-db")
+        .collection::<CutoffRecord>("rank-cutoffs"))
+}
+
+/// Stores the current cutoffs (and predictions when enabled). Returns `false`
+/// when the latest record is too recent.
+pub async fn record_cutoffs() -> Result<bool> {
+    let col = cutoffs_collection()?;
+    let now = chrono::Utc::now().timestamp();
+
+    let latest = col
+        .find_one(doc! {})
+        .with_options(
+            FindOneOptions::builder()
+                .sort(doc! { "taken_at": -1 })
+                .build(),
+        )
+        .await?;
+    if latest.is_some_and(|r| now - r.taken_at < MIN_RECORD_INTERVAL_SECS) {
+        return Ok(false);
+    }
+
+    let season = get_lucksack_season_numbers()
+        .await?
+        .first()
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("No lucksack season found"))?;
+    let live = fetch_live_cutoffs().await.map_err(anyhow::Error::msg)?;
+    let prediction = fetch_prediction_cutoffs().await;
+
+    col.insert_one(CutoffRecord {
+        season,
+        taken_at: now,
+        live,
+        prediction,
+    })
+    .await?;
+
+    Ok(true)
+}
+
+/// All records of the lucksack `season`, oldest first.
+pub async fn get_season_history(season: i32) -> Result<Vec<CutoffRecord>> {
+    let col = cutoffs_collection()?;
+    let records: Vec<CutoffRecord> = col
+        .find(doc! { "season": season })
+        .sort(doc! { "taken_at": 1 })
+        .await?
+        .try_collect()
+        .await?;
+    Ok(records)
+}
+
+/* ------------------ End-of-season estimate ------------------ */
+
+/// Moindres carrés sur (temps, valeur), renvoie (pente, ordonnée à l'origine)
+fn linear_fit(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let var_x: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if var_x == 0.0 {
+        return None;
+    }
+    let cov: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+
+    let slope = cov / var_x;
+    Some((slope, mean_y - slope * mean_x))
+}
+
+/// Extrapolates the trend of the last days up to `season_end`. `None` when
+/// the season end is unknown or past, or there is not enough history yet.
+pub fn estimate_end_of_season(
+    history: &[CutoffRecord],
+    season_end: Option<i64>,
+) -> Option<CutoffValues> {
+    let end = season_end?;
+    let latest = history.last()?;
+    if latest.taken_at >= end {
+        return None;
+    }
+
+    let window_start = latest.taken_at - ESTIMATE_WINDOW_DAYS * 86400;
+    let window: Vec<&CutoffRecord> = history
+        .iter()
+        .filter(|r| r.taken_at >= window_start)
+        .collect();
+    if window.len() < MIN_ESTIMATE_POINTS {
+        return None;
+    }
+
+    // Temps en jours pour garder des valeurs raisonnables
+    let to_days = |t: i64| (t - window_start) as f64 / 86400.0;
+    let end_x = to_days(end);
+
+    let mut values = [0; 5];
+    for (i, value) in values.iter_mut().enumerate() {
+        let points: Vec<(f64, f64)> = window
+            .iter()
+            .map(|r| (to_days(r.taken_at), r.live.as_array()[i] as f64))
+            .collect();
+        let (slope, intercept) = linear_fit(&points)?;
+        *value = (slope * end_x + intercept).round() as i32;
+    }
+
+    Some(CutoffValues::from_array(values))
+}
+
+/* ------------------ Chart ------------------ */
+
+/// Couleurs des seuils, dans l'ordre P2 → G3
+pub const CUTOFF_COLORS: [Rgba<u8>; 5] = [
+    Rgba([255, 159, 64, 255]),
+    Rgba([240, 71, 71, 255]),
+    Rgba([102, 204, 255, 255]),
+    Rgba([54, 120, 240, 255]),
+    Rgba([170, 110, 240, 255]),
+];
+
+/// Points (timestamp, value) of one cutoff, `index` following `CutoffValues::LABELS`.
+pub fn cutoff_points(history: &[CutoffRecord], index: usize) -> Vec<(i64, i32)> {
+    history
+        .iter()
+        .map(|r| (r.taken_at, r.live.as_array()[index]))
+        .collect()
+}
+
+/// Draws the season history of every cutoff, with the estimate as a dashed
+/// line up to the season end. `estimate` is `(season end, values)`.
+pub async fn create_cutoff_chart(
+    history: &[CutoffRecord],
+    estimate: Option<(i64, &CutoffValues)>,
+) -> Result<Vec<u8>> {
+    let mut series = Vec::new();
+
+    for (i, (label, color)) in CutoffValues::LABELS.iter().zip(CUTOFF_COLORS).enumerate() {
+        series.push(ChartSeries {
+            label: Some(label.to_string()),
+            color,
+            points: cutoff_points(history, i),
+            dashed: false,
+        });
+
+        if let (Some((end, estimate)), Some(last)) = (estimate, history.last()) {
+            series.push(ChartSeries {
+                label: None,
+                color,
+                points: vec![
+                    (last.taken_at, last.live.as_array()[i]),
+                    (end, estimate.as_array()[i]),
+                ],
+                dashed: true,
+            });
+        }
+    }
+
+    encode_png(render_line_chart("Rank cutoffs this season", &series)?).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Un relevé par jour, seuils en hausse de 10 points par jour
+    fn daily_history(days: i64) -> Vec<CutoffRecord> {
+        (0..days)
+            .map(|d| {
+                let base = 1300 + 10 * d as i32;
+                CutoffRecord {
+                    season: 40,
+                    taken_at: d * 86400,
+                    live: CutoffValues::from_array([
+                        base,
+                        base + 100,
+                        base + 200,
+                        base + 300,
+                        base + 400,
+                    ]),
+                    prediction: None,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn extrapolates_to_a_known_season_end() {
+        let history = daily_history(7);
+        // Dernier relevé au jour 6, fin de saison au jour 16 : +100
+        let estimate = estimate_end_of_season(&history, Some(16 * 86400));
+        assert_eq!(
+            estimate,
+            Some(CutoffValues::from_array([1460, 1560, 1660, 1760, 1860]))
+        );
+    }
+
+    #[test]
+    fn no_estimate_without_a_future_season_end() {
+        let history = daily_history(7);
+        assert_eq!(estimate_end_of_season(&history, None), None);
+        assert_eq!(estimate_end_of_season(&history, Some(86400)), None);
+    }
+
+    #[test]
+    fn no_estimate_with_too_few_records() {
+        let history = daily_history(MIN_ESTIMATE_POINTS as i64 - 1);
+        assert_eq!(estimate_end_of_season(&history, Some(30 * 86400)), None);
+    }
+}
@@ -1,2 +1,4 @@
 pub mod command;
+pub mod history;
+pub mod prediction;
 pub mod utils;
@@ -0,0 +1,237 @@
+//! Seuils prédits pour la fin de saison. La source est derrière le trait
+//! `PredictionSource` : aujourd'hui le scraping de swrta.top, activable au
+//! lancement avec la variable d'environnement `ENABLE_PREDICTION`.
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::commands::ranks::utils::CutoffValues;
+use crate::commands::shared::clients::http_client;
+
+/// Toggle for prediction gathering, off unless enabled at startup
+static PREDICTION_ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn prediction_enabled() -> bool {
+    PREDICTION_ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn set_prediction_enabled(enabled: bool) {
+    PREDICTION_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PredictionError {
+    Request(String),
+    /// The page no longer matches the expected structure
+    LayoutChanged(String),
+    MissingRank(&'static str),
+    InvalidValue {
+        rank: String,
+        value: String,
+    },
+}
+
+impl fmt::Display for PredictionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PredictionError::Request(e) => write!(f, "request failed: {e}"),
+            PredictionError::LayoutChanged(e) => write!(f, "page layout changed: {e}"),
+            PredictionError::MissingRank(rank) => write!(f, "missing prediction for {rank}"),
+            PredictionError::InvalidValue { rank, value } => {
+                write!(f, "invalid number for {rank}: {value}")
+            }
+        }
+    }
+}
+
+pub trait PredictionSource {
+    /// Page credited in the embeds
+    fn source_url(&self) -> &'static str;
+
+    /// Downloads the raw page.
+    fn fetch_raw(&self) -> impl Future<Output = Result<String, PredictionError>> + Send;
+
+    /// Extracts the cutoffs from a page returned by `fetch_raw`.
+    fn parse(&self, raw: &str) -> Result<CutoffValues, PredictionError>;
+}
+
+/// Scraper for https://swrta.top/predict
+pub struct SwrtaTopScraper;
+
+impl PredictionSource for SwrtaTopScraper {
+    fn source_url(&self) -> &'static str {
+        "https://swrta.top/predict"
+    }
+
+    async fn fetch_raw(&self) -> Result<String, PredictionError> {
+        let resp = http_client()
+            .get(self.source_url())
+            .send()
+            .await
+            .map_err(|_| PredictionError::Request("Error sending the request.".into()))?;
+
+        if !resp.status().is_success() {
+            return Err(PredictionError::Request(format!(
+                "Non-success status: {}",
+                resp.status()
+            )));
+        }
+
+        resp.text()
+            .await
+            .map_err(|_| PredictionError::Request("Error reading response body".into()))
+    }
+
+    fn parse(&self, raw: &str) -> Result<CutoffValues, PredictionError> {
+        // Paires ("P2", 1300), ... dans les blocs .predict_box .point_box
+        let document = scraper::Html::parse_document(raw);
+        let box_sel = scraper::Selector::parse(".predict_box .point_box").unwrap();
+        let rank_sel = scraper::Selector::parse(".rank_icon").unwrap();
+        let val_sel = scraper::Selector::parse(".col-8").unwrap();
+
+        let mut found: HashMap<String, i32> = HashMap::new();
+
+        for el in document.select(&box_sel) {
+            let rank = el
+                .select(&rank_sel)
+                .next()
+                .map(|n| n.text().collect::<String>().trim().to_string())
+                .ok_or_else(|| PredictionError::LayoutChanged("missing .rank_icon".into()))?;
+
+            let val_text = el
+                .select(&val_sel)
+                .next()
+                .map(|n| n.text().collect::<String>().trim().to_string())
+                .ok_or_else(|| PredictionError::LayoutChanged("missing .col-8 (value)".into()))?;
+
+            let score: i32 =
+                val_text
+                    .replace(',', "")
+                    .parse()
+                    .map_err(|_| PredictionError::InvalidValue {
+                        rank: rank.clone(),
+                        value: val_text.clone(),
+                    })?;
+
+            found.insert(rank, score);
+        }
+
+        if found.is_empty() {
+            return Err(PredictionError::LayoutChanged(
+                "no .predict_box .point_box block".into(),
+            ));
+        }
+
+        let mut values = [0; 5];
+        for (i, key) in CutoffValues::LABELS.iter().enumerate() {
+            values[i] = *found.get(*key).ok_or(PredictionError::MissingRank(key))?;
+        }
+
+        // Des seuils dans le désordre signalent des blocs mal associés
+        if values.windows(2).any(|w| w[0] > w[1]) {
+            return Err(PredictionError::LayoutChanged(format!(
+                "cutoffs out of order: {values:?}"
+            )));
+        }
+
+        Ok(CutoffValues::from_array(values))
+    }
+}
+
+/// Source used by the bot
+pub fn prediction_source() -> SwrtaTopScraper {
+    SwrtaTopScraper
+}
+
+/// Predicted cutoffs, `None` when predictions are disabled or unavailable.
+/// Failures are logged and never surface to the user.
+pub async fn fetch_prediction_cutoffs() -> Option<CutoffValues> {
+    if !prediction_enabled() {
+        return None;
+    }
+
+    let source = prediction_source();
+    let result = source.fetch_raw().await.and_then(|raw| source.parse(&raw));
+
+    match result {
+        Ok(values) => Some(values),
+        Err(e) => {
+            eprintln!("Prediction from {} unavailable: {e}", source.source_url());
+            None
+        }
+    }
+}
+
+/// Predicted thresholds as (rank emotes, score), in fixed order: P2,P3,G1,G2,G3
+pub async fn get_prediction_info() -> Option<Vec<(String, i32)>> {
+    fetch_prediction_cutoffs()
+        .await
+        .map(CutoffValues::to_emote_pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &str) -> Result<CutoffValues, PredictionError> {
+        SwrtaTopScraper.parse(raw)
+    }
+
+    #[test]
+    fn parses_valid_page() {
+        assert_eq!(
+            parse(include_str!("fixtures/predict_valid.html")),
+            Ok(CutoffValues::from_array([1312, 1455, 2521, 2687, 2904]))
+        );
+    }
+
+    #[test]
+    fn matches_blocks_by_rank_not_position() {
+        assert_eq!(
+            parse(include_str!("fixtures/predict_shuffled_blocks.html")),
+            Ok(CutoffValues::from_array([1312, 1455, 2521, 2687, 2904]))
+        );
+    }
+
+    #[test]
+    fn reports_missing_rank() {
+        assert_eq!(
+            parse(include_str!("fixtures/predict_missing_rank.html")),
+            Err(PredictionError::MissingRank("G2"))
+        );
+    }
+
+    #[test]
+    fn reports_non_numeric_value() {
+        assert_eq!(
+            parse(include_str!("fixtures/predict_invalid_value.html")),
+            Err(PredictionError::InvalidValue {
+                rank: "G1".into(),
+                value: "N/A".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_order_values() {
+        assert!(matches!(
+            parse(include_str!("fixtures/predict_out_of_order.html")),
+            Err(PredictionError::LayoutChanged(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_changed_layout() {
+        assert!(matches!(
+            parse(include_str!("fixtures/predict_layout_changed.html")),
+            Err(PredictionError::LayoutChanged(_))
+        ));
+        assert!(matches!(
+            parse(
+                r#"<div class="predict_box"><div class="point_box"><span class="rank_icon">P2</span></div></div>"#
+            ),
+            Err(PredictionError::LayoutChanged(_))
+        ));
+    }
+}
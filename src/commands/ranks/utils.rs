@@ -1,10 +1,62 @@
 use crate::{GUARDIAN_EMOJI_ID, PUNISHER_EMOJI_ID};
-use serde::Deserialize;
+use chrono::TimeZone;
+use serde::{Deserialize, Serialize};
 
 use crate::commands::shared::clients::http_client;
 
-/// Global toggle for prediction gathering (set to false to disable)
-pub const ENABLE_PREDICTION: bool = false;
+/// Exact season end date/time provided by maintainer (UTC)
+pub fn season_end_date() -> chrono::DateTime<chrono::Utc> {
+    chrono::Utc
+        .with_ymd_and_hms(2026, 9, 19, 7, 0, 0)
+        .single()
+        .expect("Invalid hardcoded season end date")
+}
+
+/// Season end as a timestamp while it is still ahead. The date above is only
+/// updated by hand, so once it has passed the end of the running season is
+/// unknown and nothing is extrapolated to it.
+pub fn upcoming_season_end() -> Option<i64> {
+    let end = season_end_date();
+    (end > chrono::Utc::now()).then(|| end.timestamp())
+}
+
+/// Seuils P2 → G3, indépendants des emotes (stockage / graphique)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CutoffValues {
+    pub p2: i32,
+    pub p3: i32,
+    pub g1: i32,
+    pub g2: i32,
+    pub g3: i32,
+}
+
+impl CutoffValues {
+    pub const LABELS: [&'static str; 5] = ["P2", "P3", "G1", "G2", "G3"];
+
+    /// Order: P2,P3,G1,G2,G3
+    pub fn as_array(&self) -> [i32; 5] {
+        [self.p2, self.p3, self.g1, self.g2, self.g3]
+    }
+
+    pub fn from_array(values: [i32; 5]) -> Self {
+        let [p2, p3, g1, g2, g3] = values;
+        Self { p2, p3, g1, g2, g3 }
+    }
+
+    /// Same pairs as `get_rank_info`: (rank emotes, score)
+    pub fn to_emote_pairs(self) -> Vec<(String, i32)> {
+        let punisher_emote_str = format!("<:punisher:{}>", PUNISHER_EMOJI_ID.lock().unwrap());
+        let guardian_emote_str = format!("<:guardian:{}>", GUARDIAN_EMOJI_ID.lock().unwrap());
+
+        vec![
+            (punisher_emote_str.repeat(2), self.p2),
+            (punisher_emote_str.repeat(3), self.p3),
+            (guardian_emote_str.to_string(), self.g1),
+            (guardian_emote_str.repeat(2), self.g2),
+            (guardian_emote_str.repeat(3), self.g3),
+        ]
+    }
+}
 
 // ---------- Live thresholds (JSON API) ----------
 
@@ -31,6 +83,10 @@ struct RankInfo {
 /// Live thresholds from https://m.swranking.com/api/player/nowline
 /// Returns in fixed order: P2,P3,G1,G2,G3
 pub async fn get_rank_info() -> Result<Vec<(String, i32)>, String> {
+    Ok(fetch_live_cutoffs().await?.to_emote_pairs())
+}
+
+pub async fn fetch_live_cutoffs() -> Result<CutoffValues, String> {
     let url = "https://m.swranking.com/api/player/nowline";
     let response = match http_client().get(url).send().await {
         Ok(response) => response,
@@ -46,95 +102,11 @@ pub async fn get_rank_info() -> Result<Vec<(String, i32)>, String> {
         Err(_) => return Err("Error converting to JSON".into()),
     };
 
-    let punisher_emote_str = format!("<:punisher:{}>", PUNISHER_EMOJI_ID.lock().unwrap());
-    let guardian_emote_str = format!("<:guardian:{}>", GUARDIAN_EMOJI_ID.lock().unwrap());
-
-    let scores = vec![
-        (punisher_emote_str.repeat(2), api_response.data.s2.score), // P2
-        (punisher_emote_str.repeat(3), api_response.data.s3.score), // P3
-        (guardian_emote_str.to_string(), api_response.data.g1.score), // G1
-        (guardian_emote_str.repeat(2), api_response.data.g2.score), // G2
-        (guardian_emote_str.repeat(3), api_response.data.g3.score), // G3
-    ];
-
-    Ok(scores)
-}
-
-// ---------- Prediction thresholds (HTML scraping) ----------
-
-/// Fetch predicted thresholds from https://swrta.top/predict
-/// Returns in fixed order: P2,P3,G1,G2,G3
-pub async fn get_prediction_info() -> Result<Vec<(String, i32)>, String> {
-    let url = "https://swrta.top/predict";
-    let resp = http_client()
-        .get(url)
-        .send()
-        .await
-        .map_err(|_| "Error sending the request.".to_string())?;
-
-    if !resp.status().is_success() {
-        return Err(format!("Non-success status: {}", resp.status()));
-    }
-
-    let html = resp
-        .text()
-        .await
-        .map_err(|_| "Error reading response body".to_string())?;
-
-    // Parse the HTML and extract pairs like ("P2", 1300), ...
-    let document = scraper::Html::parse_document(&html);
-    let box_sel = scraper::Selector::parse(".predict_box .point_box").unwrap();
-    let rank_sel = scraper::Selector::parse(".rank_icon").unwrap();
-    let val_sel = scraper::Selector::parse(".col-8").unwrap();
-
-    use std::collections::HashMap;
-    let mut found: HashMap<String, i32> = HashMap::new();
-
-    for el in document.select(&box_sel) {
-        let rank = el
-            .select(&rank_sel)
-            .next()
-            .map(|n| n.text().collect::<String>().trim().to_string())
-            .ok_or_else(|| "Missing .rank_icon".to_string())?;
-
-        let val_text = el
-            .select(&val_sel)
-            .next()
-            .map(|n| n.text().collect::<String>().trim().to_string())
-            .ok_or_else(|| "Missing .col-8 (value)".to_string())?;
-
-        let score: i32 = val_text
-            .replace(',', "")
-            .parse()
-            .map_err(|_| format!("Invalid number for {rank}: {val_text}"))?;
-
-        found.insert(rank, score);
-    }
-
-    let punisher_emote_str = format!("<:punisher:{}>", PUNISHER_EMOJI_ID.lock().unwrap());
-    let guardian_emote_str = format!("<:guardian:{}>", GUARDIAN_EMOJI_ID.lock().unwrap());
-
-    fn emotes_for(rank: &str, p: &str, g: &str) -> String {
-        // rank like "P2", "G3"
-        let (letter, num) = rank.split_at(1);
-        let repeats = num.parse::<usize>().unwrap_or(1).clamp(1, 3);
-        match letter {
-            "P" => p.repeat(repeats),
-            "G" => g.repeat(repeats),
-            _ => p.to_string(),
-        }
-    }
-
-    let order = ["P2", "P3", "G1", "G2", "G3"];
-    let mut out: Vec<(String, i32)> = Vec::with_capacity(order.len());
-
-    for key in order {
-        let score = *found
-            .get(key)
-            .ok_or_else(|| format!("Missing prediction for {key}"))?;
-        let emote = emotes_for(key, &punisher_emote_str, &guardian_emote_str);
-        out.push((emote, score));
-    }
-
-    Ok(out)
+    Ok(CutoffValues {
+        p2: api_response.data.s2.score,
+        p3: api_response.data.s3.score,
+        g1: api_response.data.g1.score,
+        g2: api_response.data.g2.score,
+        g3: api_response.data.g3.score,
+    })
 }
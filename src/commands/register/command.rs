@@ -6,6 +6,7 @@ use serenity::{
     Error,
 };
 
+use crate::commands::player_names::snapshots::spawn_record_player_names;
 use crate::commands::player_stats::utils::search_players_lucksack;
 use crate::commands::register::utils::upsert_user_link;
 use crate::commands::shared::logs::{get_server_name, send_log};
@@ -130,6 +131,7 @@ pub async fn register(
             0, // server not available from search; filled from summary on first /mystats
             &selected.country,
             now_ts,
+            ctx.guild_id().map(|g| g.get()),
         )
         .await
         .map_err(|e| {
@@ -137,6 +139,7 @@ pub async fn register(
                 format!("DB error: {e}"),
             ))
         })?;
+        spawn_record_player_names(vec![(selected.player_id, selected.name.clone())]);
 
         msg_handle
             .edit(
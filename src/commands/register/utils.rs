@@ -1,8 +1,11 @@
 use anyhow::Result;
+use moka::future::Cache;
 use mongodb::{
     bson::{doc, Document},
     Collection,
 };
+use once_cell::sync::Lazy;
+use std::time::Duration;
 
 use crate::commands::shared::clients::mongo_client;
 
@@ -20,6 +23,7 @@ pub async fn upsert_user_link(
     player_server: i32,
     player_country: &str,
     updated_at: i64,
+    guild_id: Option<u64>,
 ) -> Result<()> {
     let col = get_user_links_collection().await?;
 
@@ -34,19 +38,46 @@ pub async fn upsert_user_link(
         "updated_at": updated_at,
     };
 
-    match col.find_one(filter.clone()).await? {
-        Some(_) => {
-            let update = doc! { "$set": update_doc };
-            col.update_one(filter, update).await?;
-        }
-        None => {
-            col.insert_one(update_doc).await?;
-        }
+    // Serveurs où le compte a été lié, pour /guild_ranking
+    let mut update = doc! { "$set": update_doc };
+    if let Some(guild_id) = guild_id {
+        update.insert("$addToSet", doc! { "guild_ids": guild_id as i64 });
     }
+    col.update_one(filter, update).upsert(true).await?;
 
     Ok(())
 }
 
+/// Paires (discord_user_id, guild_id) déjà enregistrées, pour ne pas écrire
+/// en base à chaque commande
+static RECORDED_GUILDS: Lazy<Cache<(u64, u64), ()>> = Lazy::new(|| {
+    Cache::builder()
+        .time_to_live(Duration::from_secs(24 * 3600))
+        .max_capacity(50_000)
+        .build()
+});
+
+/// Adds `guild_id` to the servers of the user's link, if they have one. Run
+/// before every command so links made elsewhere (or before guilds were
+/// recorded) show up in `/guild_ranking` once the user is active here.
+pub async fn record_user_guild(discord_user_id: u64, guild_id: u64) -> Result<()> {
+    if RECORDED_GUILDS.contains_key(&(discord_user_id, guild_id)) {
+        return Ok(());
+    }
+
+    get_user_links_collection()
+        .await?
+        .update_one(
+            doc! { "discord_user_id": discord_user_id as i64 },
+            doc! { "$addToSet": { "guild_ids": guild_id as i64 } },
+        )
+        .await?;
+    RECORDED_GUILDS
+        .insert((discord_user_id, guild_id), ())
+        .await;
+    Ok(())
+}
+
 pub async fn get_user_link(discord_user_id: u64) -> Result<Option<Document>> {
     let col = get_user_links_collection().await?;
     let doc_opt = col
@@ -5,9 +5,11 @@ use serenity::{CreateInteractionResponse, CreateInteractionResponseMessage, Erro
 
 use crate::commands::mob_stats::command::autocomplete_monster;
 use crate::commands::mob_stats::utils::remap_monster_id;
+use crate::commands::replays::models::{PickOrderFilter, ReplayFilters, ReplayOutcomeFilter};
 use crate::commands::replays::utils::{
     create_loading_replays_embed, create_replay_image, create_replay_level_buttons,
-    create_replays_embed, get_replays_data,
+    create_replay_pagination_buttons, create_replays_embed, format_replay_summary,
+    get_filtered_replays, summarize_replays, Replay,
 };
 
 use crate::commands::shared::embed_error_handling::{
@@ -15,7 +17,7 @@ use crate::commands::shared::embed_error_handling::{
 };
 use crate::commands::shared::logs::get_server_name;
 use crate::commands::shared::logs::send_log;
-use crate::commands::shared::models::LoggerDocument;
+use crate::commands::shared::models::{LoggerDocument, ReplayDisplay};
 use crate::{Data, GUARDIAN_EMOJI_ID, PUNISHER_EMOJI_ID};
 
 // Import de la map des monstres
@@ -23,6 +25,7 @@ use crate::MONSTER_MAP;
 
 /// 📂 Display replays containing the selected monsters
 #[poise::command(slash_command)]
+#[allow(clippy::too_many_arguments)]
 pub async fn get_replays(
     ctx: poise::ApplicationContext<'_, Data, Error>,
     #[autocomplete = "autocomplete_monster"]
@@ -40,6 +43,15 @@ pub async fn get_replays(
     #[autocomplete = "autocomplete_monster"]
     #[description = "Monster 5 (optional)"]
     monster5: Option<String>,
+    #[description = "Replay display (default: Grid)"] display: Option<ReplayDisplay>,
+    #[description = "Only wins or only losses"] outcome: Option<ReplayOutcomeFilter>,
+    #[description = "First pick or second pick"] pick_order: Option<PickOrderFilter>,
+    #[autocomplete = "autocomplete_monster"]
+    #[description = "Opponent had this monster"]
+    opponent_monster: Option<String>,
+    #[autocomplete = "autocomplete_monster"]
+    #[description = "This monster was banned"]
+    banned_monster: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -60,8 +72,14 @@ pub async fn get_replays(
     }
 
     let user_id = ctx.author().id;
+    let display = display.unwrap_or_default();
 
-    for name in &monster_names {
+    // Les monstres des filtres sont résolus avec la même map, en fin de liste
+    for name in monster_names
+        .iter()
+        .chain(&opponent_monster)
+        .chain(&banned_monster)
+    {
         match MONSTER_MAP.get(name) {
             Some(&id) => monster_ids.push(id as i32),
             None => {
@@ -89,53 +107,67 @@ pub async fn get_replays(
         *id = remap_monster_id(*id);
     }
 
+    // Les IDs des filtres sont en fin de liste, dans l'ordre opponent puis banned
+    let mut filter_ids = monster_ids.split_off(monster_names.len()).into_iter();
+    let filters = ReplayFilters {
+        outcome,
+        pick_order,
+        opponent_monster: opponent_monster
+            .as_ref()
+            .and_then(|_| filter_ids.next())
+            .map(|id| id as u32),
+        banned_monster: banned_monster
+            .as_ref()
+            .and_then(|_| filter_ids.next())
+            .map(|id| id as u32),
+    };
+
     let mut current_level = 1;
+    let mut page = 0usize;
 
     // 1) Récupération des replays
-    let replays = get_replays_data(&monster_ids, current_level)
+    let mut replays = get_filtered_replays(&monster_ids, current_level, &filters)
         .await
         .map_err(|e| Error::from(std::io::Error::other(e)))?;
 
     // 2) Construction d'un set des IDs recherchés
     let search_ids: Vec<u32> = monster_ids.iter().map(|&i| i as u32).collect();
 
-    // 3) Filtrer et collecter uniquement les joueurs qui ont joué AU MOINS un monstre recherché
-    let mut player_names: Vec<String> = replays
-        .iter()
-        .flat_map(|r| {
-            let mut names = Vec::new();
-            // joueur 1
-            if r.player_one
-                .monster_info_list
-                .iter()
-                .any(|m| search_ids.contains(&m.monster_id))
-            {
-                names.push(r.player_one.player_name.clone());
-            }
-            // joueur 2
-            if r.player_two
-                .monster_info_list
-                .iter()
-                .any(|m| search_ids.contains(&m.monster_id))
-            {
-                names.push(r.player_two.player_name.clone());
-            }
-            names
-        })
-        .collect();
+    // 3) Joueurs ayant joué AU MOINS un monstre recherché
+    let mut player_names = collect_player_names(&replays, &search_ids);
+    let mut summary = format_replay_summary(&summarize_replays(&replays), &filters);
 
-    // 4) Trier et dédupliquer
-    player_names.sort();
-    player_names.dedup();
+    if replays.is_empty() {
+        let reply = ctx
+            .send(create_embed_error("No replay matches these filters."))
+            .await?;
+        schedule_message_deletion(reply, ctx).await?;
+        send_log(LoggerDocument::new(
+            &ctx.author().name,
+            "get_replays",
+            &get_server_name(&ctx).await?,
+            false,
+            chrono::Utc::now().timestamp(),
+        ))
+        .await?;
+        return Ok(());
+    }
 
-    let replay_image_path = create_replay_image(replays, 4, 4)
+    let replay_image_path = create_replay_image(&replays, display, page)
         .await
         .map_err(|e| Error::from(std::io::Error::other(e)))?;
 
     // Create attachment for the replay image
     let attachment = serenity::CreateAttachment::path(&replay_image_path).await?;
 
-    let embed = create_replays_embed(&monster_names, current_level, &player_names);
+    let embed = create_replays_embed(
+        &monster_names,
+        current_level,
+        &player_names,
+        &attachment.filename,
+        gallery_page(display, page, replays.len()),
+        &summary,
+    );
 
     let guardian_id: u64 = GUARDIAN_EMOJI_ID.lock().unwrap().parse().unwrap();
     let punisher_id: u64 = PUNISHER_EMOJI_ID.lock().unwrap().parse().unwrap();
@@ -144,12 +176,15 @@ pub async fn get_replays(
         .send(CreateReply {
             embeds: vec![embed],
             attachments: vec![attachment],
-            components: Some(vec![create_replay_level_buttons(
+            components: Some(replay_components(
                 guardian_id,
                 punisher_id,
                 current_level,
+                display,
+                page,
+                replays.len(),
                 false,
-            )]),
+            )),
             ..Default::default()
         })
         .await?;
@@ -167,83 +202,109 @@ pub async fn get_replays(
             .await
     {
         let selected_level = match interaction.data.custom_id.as_str() {
-            "level_g1g2" => 1,
-            "level_g3" => 3,
-            "level_p1p3" => 4,
+            "level_g1g2" => Some(1),
+            "level_g3" => Some(3),
+            "level_p1p3" => Some(4),
+            "replays_previous_page" if page > 0 => {
+                page -= 1;
+                None
+            }
+            "replays_next_page" if page + 1 < replays.len() => {
+                page += 1;
+                None
+            }
             _ => continue,
         };
 
-        if selected_level == current_level {
+        if selected_level == Some(current_level) {
             continue;
         }
 
-        current_level = selected_level;
-
-        // Afficher l'embed de chargement
-        let loading_embed = create_loading_replays_embed(&monster_names, current_level);
+        if let Some(level) = selected_level {
+            current_level = level;
+            page = 0;
+
+            // Afficher l'embed de chargement
+            let loading_embed = create_loading_replays_embed(&monster_names, current_level);
+
+            interaction
+                .create_response(
+                    &ctx.serenity_context,
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new()
+                            .embed(loading_embed)
+                            .components(vec![create_replay_level_buttons(
+                                guardian_id,
+                                punisher_id,
+                                current_level,
+                                true, // Boutons désactivés pendant le chargement
+                            )]),
+                    ),
+                )
+                .await?;
 
-        interaction
-            .create_response(
-                &ctx.serenity_context,
-                CreateInteractionResponse::UpdateMessage(
-                    CreateInteractionResponseMessage::new()
-                        .embed(loading_embed)
-                        .components(vec![create_replay_level_buttons(
-                            guardian_id,
-                            punisher_id,
-                            current_level,
-                            true, // Boutons désactivés pendant le chargement
-                        )]),
-                ),
-            )
-            .await?;
+            // Récupérer les nouvelles données
+            replays = match get_filtered_replays(&monster_ids, current_level, &filters).await {
+                Ok(data) => data,
+                Err(e) => {
+                    interaction
+                        .edit_response(
+                            &ctx.serenity_context.http,
+                            EditInteractionResponse::new()
+                                .content(format!("❌ Error fetching replay data: {}", e))
+                                .components(vec![])
+                                .embeds(vec![]),
+                        )
+                        .await?;
+                    continue;
+                }
+            };
+            player_names = collect_player_names(&replays, &search_ids);
+            summary = format_replay_summary(&summarize_replays(&replays), &filters);
 
-        // Récupérer les nouvelles données
-        let new_replays = match get_replays_data(&monster_ids, current_level).await {
-            Ok(data) => data,
-            Err(e) => {
+            // Aucun replay à ce niveau : on garde les boutons pour changer de niveau
+            if replays.is_empty() {
                 interaction
                     .edit_response(
                         &ctx.serenity_context.http,
                         EditInteractionResponse::new()
-                            .content(format!("❌ Error fetching replay data: {}", e))
-                            .components(vec![])
-                            .embeds(vec![]),
+                            .embeds(vec![serenity::CreateEmbed::default()
+                                .title("No replays")
+                                .description("No replay matches these filters at this level.")
+                                .color(0xffa500)])
+                            .components(vec![create_replay_level_buttons(
+                                guardian_id,
+                                punisher_id,
+                                current_level,
+                                false,
+                            )])
+                            .attachments(EditAttachments::new()),
                     )
                     .await?;
                 continue;
             }
-        };
-
-        let search_ids: Vec<u32> = monster_ids.iter().map(|&i| i as u32).collect();
-        let mut new_player_names: Vec<String> = new_replays
-            .iter()
-            .flat_map(|r| {
-                let mut names = Vec::new();
-                // joueur 1
-                if r.player_one
-                    .monster_info_list
-                    .iter()
-                    .any(|m| search_ids.contains(&m.monster_id))
-                {
-                    names.push(r.player_one.player_name.clone());
-                }
-                // joueur 2
-                if r.player_two
-                    .monster_info_list
-                    .iter()
-                    .any(|m| search_ids.contains(&m.monster_id))
-                {
-                    names.push(r.player_two.player_name.clone());
-                }
-                names
-            })
-            .collect();
-        new_player_names.sort();
-        new_player_names.dedup();
+        } else {
+            // Changement de page : on garde l'image courante en désactivant les boutons
+            interaction
+                .create_response(
+                    &ctx.serenity_context,
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new().components(replay_components(
+                            guardian_id,
+                            punisher_id,
+                            current_level,
+                            display,
+                            page,
+                            replays.len(),
+                            true,
+                        )),
+                    ),
+                )
+                .await?;
+        }
 
         // Créer la nouvelle image
-        let new_replay_image_path = match create_replay_image(new_replays, 4, 4).await {
+        let new_replay_image_path = match create_replay_image(&replays, display, page).await {
             Ok(path) => path,
             Err(e) => {
                 interaction
@@ -263,19 +324,29 @@ pub async fn get_replays(
         let new_attachment = serenity::CreateAttachment::path(&new_replay_image_path).await?;
 
         // Créer l'embed final
-        let final_embed = create_replays_embed(&monster_names, current_level, &new_player_names);
+        let final_embed = create_replays_embed(
+            &monster_names,
+            current_level,
+            &player_names,
+            &new_attachment.filename,
+            gallery_page(display, page, replays.len()),
+            &summary,
+        );
 
         interaction
             .edit_response(
                 &ctx.serenity_context.http,
                 EditInteractionResponse::new()
                     .embeds(vec![final_embed])
-                    .components(vec![create_replay_level_buttons(
+                    .components(replay_components(
                         guardian_id,
                         punisher_id,
                         current_level,
+                        display,
+                        page,
+                        replays.len(),
                         false, // Boutons réactivés
-                    )])
+                    ))
                     .attachments(EditAttachments::new().add(new_attachment)),
             )
             .await?;
@@ -292,3 +363,46 @@ pub async fn get_replays(
 
     Ok(())
 }
+
+/// Noms triés et dédupliqués des joueurs ayant joué au moins un des monstres recherchés
+fn collect_player_names(replays: &[Replay], search_ids: &[u32]) -> Vec<String> {
+    let mut names: Vec<String> = replays
+        .iter()
+        .flat_map(|r| [&r.player_one, &r.player_two])
+        .filter(|p| {
+            p.monster_info_list
+                .iter()
+                .any(|m| search_ids.contains(&m.monster_id))
+        })
+        .map(|p| p.player_name.clone())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Page courante (1-based) et nombre de pages, uniquement en mode galerie
+fn gallery_page(display: ReplayDisplay, page: usize, total: usize) -> Option<(usize, usize)> {
+    (display == ReplayDisplay::Gallery).then_some((page + 1, total.max(1)))
+}
+
+fn replay_components(
+    guardian_id: u64,
+    punisher_id: u64,
+    level: i32,
+    display: ReplayDisplay,
+    page: usize,
+    total: usize,
+    disabled: bool,
+) -> Vec<serenity::CreateActionRow> {
+    let mut rows = vec![create_replay_level_buttons(
+        guardian_id,
+        punisher_id,
+        level,
+        disabled,
+    )];
+    if display == ReplayDisplay::Gallery && total > 1 {
+        rows.push(create_replay_pagination_buttons(page + 1, total, disabled));
+    }
+    rows
+}
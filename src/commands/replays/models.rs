@@ -12,3 +12,78 @@ pub struct Root {
 pub struct ReplayListData {
     pub list: Vec<Replay>,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum ReplayOutcomeFilter {
+    #[name = "Wins only"]
+    Wins,
+    #[name = "Losses only"]
+    Losses,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum PickOrderFilter {
+    #[name = "First pick"]
+    FirstPick,
+    #[name = "Second pick"]
+    SecondPick,
+}
+
+/// Filtres appliqués côté bot sur les replays récupérés.
+/// Le "joueur" est toujours `player_one` (celui qui a joué les monstres recherchés).
+#[derive(Debug, Clone, Default)]
+pub struct ReplayFilters {
+    pub outcome: Option<ReplayOutcomeFilter>,
+    pub pick_order: Option<PickOrderFilter>,
+    pub opponent_monster: Option<u32>,
+    pub banned_monster: Option<u32>,
+}
+
+impl ReplayFilters {
+    pub fn is_active(&self) -> bool {
+        self.outcome.is_some()
+            || self.pick_order.is_some()
+            || self.opponent_monster.is_some()
+            || self.banned_monster.is_some()
+    }
+
+    pub fn matches(&self, replay: &Replay) -> bool {
+        let outcome_ok = match self.outcome {
+            Some(ReplayOutcomeFilter::Wins) => replay.status == 1,
+            Some(ReplayOutcomeFilter::Losses) => replay.status == 2,
+            None => true,
+        };
+
+        let is_first_pick = replay.first_pick == replay.player_one.player_id;
+        let pick_ok = match self.pick_order {
+            Some(PickOrderFilter::FirstPick) => is_first_pick,
+            Some(PickOrderFilter::SecondPick) => !is_first_pick,
+            None => true,
+        };
+
+        let opponent_ok = self.opponent_monster.is_none_or(|id| {
+            replay
+                .player_two
+                .monster_info_list
+                .iter()
+                .any(|m| m.monster_id == id)
+        });
+
+        let banned_ok = self.banned_monster.is_none_or(|id| {
+            replay.player_one.ban_monster_id == id || replay.player_two.ban_monster_id == id
+        });
+
+        outcome_ok && pick_ok && opponent_ok && banned_ok
+    }
+}
+
+/// Agrégats calculés sur la liste de replays affichée
+#[derive(Debug, Clone, Default)]
+pub struct ReplaySummary {
+    pub wins: usize,
+    pub losses: usize,
+    /// (monster_id, nombre d'apparitions chez l'adversaire), trié décroissant
+    pub top_opponents: Vec<(u32, usize)>,
+    /// (monster_id, nombre de bans), tous camps confondus
+    pub most_banned: Option<(u32, usize)>,
+}
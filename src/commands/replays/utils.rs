@@ -1,19 +1,28 @@
-use ab_glyph::{Font, FontArc, PxScale, ScaleFont};
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, Result};
 use chrono::NaiveDateTime;
-use image::GenericImage;
-use image::{DynamicImage, ImageBuffer, Rgba, RgbaImage};
-use imageproc::drawing::draw_text_mut;
 use poise::serenity_prelude as serenity;
+use poise::ChoiceParameter;
 use reqwest::Client;
 use serde::Deserialize;
 use serenity::builder::{CreateEmbed, CreateEmbedFooter};
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
-use std::sync::{OnceLock, RwLock};
 
-use crate::commands::replays::models::Root;
-use crate::commands::shared::clients::http_client;
+use crate::commands::mob_stats::utils::remap_monster_id;
+use crate::commands::replays::models::{ReplayFilters, ReplaySummary, Root};
+use crate::commands::shared::models::ReplayDisplay;
+use crate::commands::shared::render::{
+    prune_tmp_replay_files, render_replay_frames, render_replay_grid, save_gif, save_png,
+    MatchOutcome, MatchSection, ReplayLayout, TeamDraft,
+};
+
+use crate::MONSTER_MAP;
+
+const REPLAY_GIF_FRAME_DELAY_MS: u32 = 2500;
+const REPLAY_PAGE_SIZE: usize = 16;
+/// Nombre max de pages swranking parcourues quand des filtres sont actifs
+const REPLAY_FILTER_MAX_PAGES: i32 = 4;
 
 #[derive(Debug, Deserialize)]
 pub struct Replay {
@@ -53,38 +62,13 @@ pub struct ReplayMonster {
     pub monster_id: u32,
 }
 
-static GLOBAL_MONSTER_IMAGE_CACHE: OnceLock<RwLock<HashMap<String, DynamicImage>>> =
-    OnceLock::new();
-static CROSS_IMAGE_100: OnceLock<RgbaImage> = OnceLock::new();
-static BANNER_FONT: OnceLock<FontArc> = OnceLock::new();
-fn global_monster_image_cache() -> &'static RwLock<HashMap<String, DynamicImage>> {
-    GLOBAL_MONSTER_IMAGE_CACHE.get_or_init(|| RwLock::new(HashMap::new()))
-}
-
-fn cross_image_100() -> &'static RgbaImage {
-    CROSS_IMAGE_100.get_or_init(|| {
-        const CROSS_BYTES: &[u8] = include_bytes!("../player_stats/cross.png");
-        image::load_from_memory(CROSS_BYTES)
-            .expect("Erreur lors du chargement de cross.png")
-            .resize_exact(100, 100, image::imageops::FilterType::Triangle)
-            .to_rgba8()
-    })
-}
-
-fn banner_font() -> &'static FontArc {
-    BANNER_FONT.get_or_init(|| {
-        const FONT_BYTES: &[u8] = include_bytes!("../player_stats/NotoSansCJK-Regular.otf");
-        FontArc::try_from_vec(FONT_BYTES.to_vec()).expect("Police invalide")
-    })
-}
-
-pub async fn get_replays_data(ids: &Vec<i32>, level: i32) -> Result<Vec<Replay>> {
+pub async fn get_replays_data(ids: &Vec<i32>, level: i32, page_num: i32) -> Result<Vec<Replay>> {
     let url = "https://m.swranking.com/api/player/replayallist";
     let client = Client::new();
 
     let body = serde_json::json!({
-        "pageNum":1,
-        "pageSize":16,
+        "pageNum": page_num,
+        "pageSize": REPLAY_PAGE_SIZE,
         "level": level,
         "monsterIds": ids,
     });
@@ -134,455 +118,260 @@ pub async fn get_replays_data(ids: &Vec<i32>, level: i32) -> Result<Vec<Replay>>
     Ok(replays)
 }
 
-pub async fn create_replay_image(
-    recent_replays: Vec<Replay>,
-    rows: i32,
-    cols: i32,
-) -> Result<PathBuf> {
-    let nb_battles = recent_replays.len();
-
-    let mut sections: Vec<RgbaImage> = Vec::new();
-    let mut image_cache: HashMap<String, DynamicImage> = HashMap::new();
-
-    for battle in recent_replays.iter().take(nb_battles) {
-        let actual_first_pick_id = battle.first_pick;
-
-        let urls_player_one: Vec<String> = battle
-            .player_one
-            .monster_info_list
-            .iter()
-            .map(|m| m.image_filename.clone())
-            .collect();
-
-        let urls_player_two: Vec<String> = battle
-            .player_two
-            .monster_info_list
-            .iter()
-            .map(|m| m.image_filename.clone())
-            .collect();
-
-        let monster_ids_one: Vec<u32> = battle
-            .player_one
-            .monster_info_list
-            .iter()
-            .map(|m| m.monster_id)
-            .collect();
-
-        let monster_ids_two: Vec<u32> = battle
-            .player_two
-            .monster_info_list
-            .iter()
-            .map(|m| m.monster_id)
-            .collect();
-
-        let is_p1_first_pick = actual_first_pick_id == battle.player_one.player_id;
-
-        let img1 = create_team_collage_custom_layout(
-            &urls_player_one,
-            &monster_ids_one,
-            battle.player_one.ban_monster_id,
-            battle.player_one.leader_monster_id,
-            is_p1_first_pick,
-            &mut image_cache,
-        )
-        .await?;
-
-        let img2 = create_team_collage_custom_layout(
-            &urls_player_two,
-            &monster_ids_two,
-            battle.player_two.ban_monster_id,
-            battle.player_two.leader_monster_id,
-            !is_p1_first_pick,
-            &mut image_cache,
-        )
-        .await?;
-
-        let image_width = img1.width() / 3;
-        let spacing = image_width / 2;
-
-        let combined_width = img1.width() + img2.width() + spacing;
-        let height = img1.height().max(img2.height());
-
-        let mut final_image = ImageBuffer::new(combined_width, height);
-        final_image.copy_from(&img1, 0, 0).unwrap();
-        final_image
-            .copy_from(&img2, img1.width() + spacing, 0)
-            .unwrap();
-
-        let left_text = if battle.player_one.player_score == 0 {
-            battle.player_one.player_name.clone()
-        } else {
-            format!(
-                "{} - {}",
-                battle.player_one.player_score, battle.player_one.player_name
-            )
-        };
-
-        let right_text = if battle.player_two.player_score == 0 {
-            battle.player_two.player_name.clone()
-        } else {
-            format!(
-                "{} - {}",
-                battle.player_two.player_name, battle.player_two.player_score
-            )
-        };
-
-        let date_text = NaiveDateTime::parse_from_str(&battle.date, "%Y-%m-%d %H:%M:%S")
-            .map(|dt| dt.date().format("%d-%m-%Y").to_string())
-            .unwrap_or_else(|_| battle.date.clone());
-
-        let match_banner = create_match_banner(
-            &left_text,
-            &date_text,
-            &right_text,
-            combined_width,
-            Rgba([0, 0, 0, 0]),
-        );
-        let banner_height = match_banner.height();
-
-        let border_thickness = 10;
-        let section_inner_width = combined_width;
-        let section_inner_height = banner_height + final_image.height();
-
-        let section_total_width = section_inner_width + 2 * border_thickness;
-        let section_total_height = section_inner_height + 2 * border_thickness;
-
-        let bg_color = match battle.status {
-            1 => Rgba([0, 255, 0, 100]),
-            2 => Rgba([255, 0, 0, 100]),
-            _ => Rgba([0, 0, 0, 100]),
-        };
-
-        let mut section =
-            ImageBuffer::from_pixel(section_total_width, section_total_height, bg_color);
-
-        let mut inner = ImageBuffer::from_pixel(
-            section_inner_width,
-            section_inner_height,
-            Rgba([0, 0, 0, 0]),
-        );
-
-        inner.copy_from(&match_banner, 0, 0).unwrap();
-        inner.copy_from(&final_image, 0, banner_height).unwrap();
-
-        section
-            .copy_from(&inner, border_thickness, border_thickness)
-            .unwrap();
-        sections.push(section);
+/// Récupère jusqu'à `REPLAY_PAGE_SIZE` replays respectant les filtres.
+/// Sans filtre, une seule page est demandée ; sinon on pagine un peu plus loin.
+pub async fn get_filtered_replays(
+    ids: &Vec<i32>,
+    level: i32,
+    filters: &ReplayFilters,
+) -> Result<Vec<Replay>> {
+    if !filters.is_active() {
+        return get_replays_data(ids, level, 1).await;
     }
 
-    let rows = rows as u32;
-    let cols = cols as u32;
-    let padding = 10;
-
-    let section_width = sections.first().map(|img| img.width()).unwrap_or(0);
-    let section_height = sections.first().map(|img| img.height()).unwrap_or(0);
-
-    let full_width = cols * section_width + (cols - 1) * padding;
-    let full_height = rows * section_height + (rows - 1) * padding;
-
-    let mut final_image = ImageBuffer::new(full_width, full_height);
+    let mut kept = Vec::new();
+    for page_num in 1..=REPLAY_FILTER_MAX_PAGES {
+        let page = get_replays_data(ids, level, page_num).await?;
+        let page_len = page.len();
 
-    for (i, section) in sections.iter().enumerate() {
-        let col = (i as u32) % cols;
-        let row = (i as u32) / cols;
+        kept.extend(page.into_iter().filter(|r| filters.matches(r)));
 
-        let x = col * (section_width + padding);
-        let y = row * (section_height + padding);
-
-        final_image.copy_from(section, x, y)?;
+        if kept.len() >= REPLAY_PAGE_SIZE || page_len < REPLAY_PAGE_SIZE {
+            break;
+        }
     }
 
-    // IMPORTANT: Save to /tmp and keep the filename replay.png to match "attachment://replay.png"
-    let output_path = PathBuf::from("/tmp/replay.png");
-
-    let output_path_clone = output_path.clone();
-    tokio::task::spawn_blocking(move || {
-        std::fs::create_dir_all("/tmp")?;
-        final_image.save(&output_path_clone)?;
-        Ok::<_, anyhow::Error>(output_path_clone)
-    })
-    .await??;
-
-    Ok(output_path)
+    kept.truncate(REPLAY_PAGE_SIZE);
+    Ok(kept)
 }
 
-async fn create_team_collage_custom_layout(
-    image_filenames: &[String],
-    monster_ids: &[u32],
-    ban_id: u32,
-    leader_id: u32,
-    first_pick: bool,
-    cache: &mut HashMap<String, DynamicImage>,
-) -> Result<RgbaImage> {
-    let mut images = Vec::new();
-    for filename in image_filenames {
-        let img = load_image_local(filename, cache).await?;
-        images.push(img);
-    }
-
-    let width = images[0].width();
-    let height = images[0].height();
-    let mut collage = ImageBuffer::new(width * 3, height * 2);
-
-    let cross = if width == 100 && height == 100 {
-        cross_image_100().clone()
-    } else {
-        image::imageops::resize(
-            cross_image_100(),
-            width,
-            height,
-            image::imageops::FilterType::Triangle,
-        )
-    };
-
-    let mut grid_slots = [(0, 0); 5];
-
-    if first_pick {
-        grid_slots[1] = (1, 0);
-        grid_slots[2] = (1, 1);
-        grid_slots[3] = (2, 0);
-        grid_slots[4] = (2, 1);
-        grid_slots[0] = (0, 1);
-    } else {
-        grid_slots[0] = (0, 0);
-        grid_slots[1] = (0, 1);
-        grid_slots[2] = (1, 0);
-        grid_slots[3] = (1, 1);
-        grid_slots[4] = (2, 1);
-    }
+/// W/L, unités adverses les plus fréquentes et unité la plus bannie
+pub fn summarize_replays(replays: &[Replay]) -> ReplaySummary {
+    let mut opponents: HashMap<u32, usize> = HashMap::new();
+    let mut bans: HashMap<u32, usize> = HashMap::new();
 
-    for (i, (img, &monster_id)) in images.iter().zip(monster_ids).enumerate() {
-        let mut rgba = img.to_rgba8();
-
-        if monster_id == leader_id {
-            let border_color = Rgba([255, 215, 0, 255]);
-            let thickness = 5;
-            for x in 0..width {
-                for t in 0..thickness {
-                    rgba.put_pixel(x, t, border_color);
-                    rgba.put_pixel(x, height - 1 - t, border_color);
-                }
-            }
-            for y in 0..height {
-                for t in 0..thickness {
-                    rgba.put_pixel(t, y, border_color);
-                    rgba.put_pixel(width - 1 - t, y, border_color);
-                }
+    for r in replays {
+        for m in &r.player_two.monster_info_list {
+            *opponents.entry(m.monster_id).or_default() += 1;
+        }
+        for ban in [r.player_one.ban_monster_id, r.player_two.ban_monster_id] {
+            if ban != 0 {
+                *bans.entry(ban).or_default() += 1;
             }
         }
+    }
 
-        if monster_id == ban_id {
-            image::imageops::overlay(&mut rgba, &cross, 0, 0);
-        }
+    let mut top_opponents: Vec<(u32, usize)> = opponents.into_iter().collect();
+    top_opponents.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    top_opponents.truncate(5);
 
-        let (grid_x, grid_y) = grid_slots[i];
-        let x = grid_x as u32 * width;
-        let y = if (first_pick && i == 0) || (!first_pick && i == 4) {
-            ((collage.height() - height) / 2).min(collage.height() - height)
-        } else {
-            grid_y as u32 * height
-        };
+    let most_banned = bans
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)));
 
-        collage.copy_from(&rgba, x, y).unwrap();
+    ReplaySummary {
+        wins: replays.iter().filter(|r| r.status == 1).count(),
+        losses: replays.iter().filter(|r| r.status == 2).count(),
+        top_opponents,
+        most_banned,
     }
+}
 
-    Ok(collage)
+/// Nom d'un monstre à partir de son com2us_id (via MONSTER_MAP), `#id` sinon
+pub fn monster_name_by_id(monster_id: u32) -> String {
+    MONSTER_MAP
+        .iter()
+        .find(|(_, &id)| id == monster_id || remap_monster_id(id as i32) as u32 == monster_id)
+        .map(|(name, _)| name.clone())
+        .unwrap_or_else(|| format!("#{}", monster_id))
 }
 
-async fn load_image_local(
-    filename: &str,
-    cache: &mut HashMap<String, DynamicImage>,
-) -> Result<DynamicImage> {
-    if let Some(img) = cache.get(filename) {
-        return Ok(img.clone());
-    }
+pub fn format_replay_summary(summary: &ReplaySummary, filters: &ReplayFilters) -> String {
+    let mut lines = vec![format!(
+        "**Record**: {}W - {}L",
+        summary.wins, summary.losses
+    )];
 
-    if let Ok(read_guard) = global_monster_image_cache().read() {
-        if let Some(img) = read_guard.get(filename) {
-            let cloned = img.clone();
-            cache.insert(filename.to_string(), cloned.clone());
-            return Ok(cloned);
-        }
+    if !summary.top_opponents.is_empty() {
+        let opponents = summary
+            .top_opponents
+            .iter()
+            .map(|(id, count)| format!("{} ({})", monster_name_by_id(*id), count))
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(format!("**Most faced**: {}", opponents));
     }
 
-    let path = format!("assets/monster_images/{}", filename);
-    let filename_string = filename.to_string();
+    if let Some((id, count)) = summary.most_banned {
+        lines.push(format!(
+            "**Most banned**: {} ({})",
+            monster_name_by_id(id),
+            count
+        ));
+    }
 
-    let img: DynamicImage;
+    let mut active = Vec::new();
+    if let Some(outcome) = filters.outcome {
+        active.push(outcome.name().to_string());
+    }
+    if let Some(pick_order) = filters.pick_order {
+        active.push(pick_order.name().to_string());
+    }
+    if let Some(id) = filters.opponent_monster {
+        active.push(format!("vs {}", monster_name_by_id(id)));
+    }
+    if let Some(id) = filters.banned_monster {
+        active.push(format!("{} banned", monster_name_by_id(id)));
+    }
+    if !active.is_empty() {
+        lines.push(format!("**Filters**: {}", active.join(" • ")));
+    }
 
-    let local_result = tokio::task::spawn_blocking(move || -> Result<DynamicImage> {
-        let data = std::fs::read(&path)
-            .with_context(|| format!("Failed to read image file: {}", filename_string))?;
-        image::load_from_memory(&data)
-            .with_context(|| format!("Failed to decode image: {}", filename_string))
-    })
-    .await;
+    lines.join("\n")
+}
 
-    match local_result {
-        Ok(Ok(image)) => img = image,
-        Ok(Err(e))
-            if e.downcast_ref::<std::io::Error>().map(|io| io.kind())
-                == Some(std::io::ErrorKind::NotFound) =>
-        {
-            let url = format!(
-                "https://swarfarm.com/static/herders/images/monsters/{}",
-                filename
-            );
-            let bytes = http_client()
-                .get(&url)
-                .send()
-                .await
-                .with_context(|| format!("Failed to download image from: {}", url))?
-                .bytes()
-                .await
-                .with_context(|| {
-                    format!("Failed to read downloaded bytes for file: {}", filename)
-                })?;
-
-            img = image::load_from_memory(&bytes)
-                .with_context(|| format!("Failed to decode downloaded image: {}", filename))?;
+/// Renders the replays for the chosen display mode and returns the file path:
+/// a 4x4 grid, the single match at `page` (gallery), or an animated GIF.
+pub async fn create_replay_image(
+    replays: &[Replay],
+    display: ReplayDisplay,
+    page: usize,
+) -> Result<PathBuf> {
+    prune_tmp_replay_files(128);
+
+    let key = replays_cache_key(replays, display, page);
+    let sections: Vec<MatchSection> = replays.iter().map(replay_to_section).collect();
+
+    match display {
+        ReplayDisplay::Grid => {
+            let layout = ReplayLayout {
+                cols: 4,
+                rows: Some(4),
+                ..Default::default()
+            };
+            let final_image = render_replay_grid(&sections, &layout).await?;
+            save_png(
+                final_image,
+                PathBuf::from(format!("/tmp/replay-{}.png", key)),
+            )
+            .await
+        }
+        ReplayDisplay::Gallery => {
+            let section = sections
+                .get(page)
+                .ok_or_else(|| anyhow!("No replay on page {}", page + 1))?;
+            let layout = ReplayLayout {
+                cols: 1,
+                ..Default::default()
+            };
+            let final_image = render_replay_grid(std::slice::from_ref(section), &layout).await?;
+            save_png(
+                final_image,
+                PathBuf::from(format!("/tmp/replay-{}.png", key)),
+            )
+            .await
         }
-        Ok(Err(e)) => return Err(e),
-        Err(e) => {
-            return Err(anyhow!(
-                "Blocking task failed for file: {}: {}",
-                filename,
-                e
-            ))
+        ReplayDisplay::Animated => {
+            let frames = render_replay_frames(&sections, &ReplayLayout::default()).await?;
+            save_gif(
+                frames,
+                PathBuf::from(format!("/tmp/replay-{}.gif", key)),
+                REPLAY_GIF_FRAME_DELAY_MS,
+            )
+            .await
         }
     }
+}
 
-    let img = img.resize_exact(100, 100, image::imageops::FilterType::Triangle);
-
-    if let Ok(mut write_guard) = global_monster_image_cache().write() {
-        write_guard.insert(filename.to_string(), img.clone());
+fn replays_cache_key(replays: &[Replay], display: ReplayDisplay, page: usize) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    (display as u8).hash(&mut hasher);
+    page.hash(&mut hasher);
+
+    for r in replays {
+        r.date.hash(&mut hasher);
+        r.status.hash(&mut hasher);
+        r.first_pick.hash(&mut hasher);
+        for p in [&r.player_one, &r.player_two] {
+            p.player_id.hash(&mut hasher);
+            p.player_score.hash(&mut hasher);
+            p.ban_monster_id.hash(&mut hasher);
+            p.leader_monster_id.hash(&mut hasher);
+            for m in &p.monster_info_list {
+                m.monster_id.hash(&mut hasher);
+            }
+        }
     }
 
-    cache.insert(filename.to_string(), img.clone());
-    Ok(img)
-}
-
-fn create_match_banner(
-    left_text: &str,
-    center_text: &str,
-    right_text: &str,
-    width: u32,
-    color: Rgba<u8>,
-) -> RgbaImage {
-    let height = 40;
-    let mut image = ImageBuffer::from_pixel(width, height, color);
-
-    let font = banner_font();
-
-    let scale = PxScale::from(26.0);
-    let margin = 8.0_f32;
-    let width_f = width as f32;
-
-    let max_left_width = width_f / 3.0 - margin * 2.0;
-    let max_center_width = width_f / 3.0 - margin * 2.0;
-    let max_right_width = width_f / 3.0 - margin * 2.0;
-
-    let left = fit_text_to_width(font, scale, left_text, max_left_width);
-    let center = fit_text_to_width(font, scale, center_text, max_center_width);
-    let right = fit_text_to_width(font, scale, right_text, max_right_width);
-
-    let center_w = text_width(font, scale, &center);
-    let right_w = text_width(font, scale, &right);
-
-    let y = 8;
-
-    draw_bold_text_mut(
-        &mut image,
-        Rgba([255, 255, 255, 255]),
-        margin as i32,
-        y,
-        scale,
-        font,
-        &left,
-    );
-
-    let center_x: i32 = ((width_f - center_w) / 2.0).round() as i32;
-    draw_bold_text_mut(
-        &mut image,
-        Rgba([255, 255, 255, 255]),
-        center_x,
-        y,
-        scale,
-        font,
-        &center,
-    );
-
-    let right_x: i32 = (width_f - right_w - margin).round() as i32;
-    draw_bold_text_mut(
-        &mut image,
-        Rgba([255, 255, 255, 255]),
-        right_x,
-        y,
-        scale,
-        font,
-        &right,
-    );
-
-    image
+    hasher.finish()
 }
 
-fn draw_bold_text_mut(
-    image: &mut RgbaImage,
-    color: Rgba<u8>,
-    x: i32,
-    y: i32,
-    scale: PxScale,
-    font: &FontArc,
-    text: &str,
-) {
-    let offsets = [(0, 0), (1, 0), (0, 1), (1, 1)];
-    for (dx, dy) in offsets {
-        draw_text_mut(image, color, x + dx, y + dy, scale, font, text);
-    }
-}
+fn replay_to_section(battle: &Replay) -> MatchSection {
+    let is_p1_first_pick = battle.first_pick == battle.player_one.player_id;
 
-fn text_width(font: &FontArc, scale: PxScale, text: &str) -> f32 {
-    let scaled = font.as_scaled(scale);
-    let mut pen = 0.0f32;
-    let mut prev_id = None;
+    let left_text = if battle.player_one.player_score == 0 {
+        battle.player_one.player_name.clone()
+    } else {
+        format!(
+            "{} - {}",
+            battle.player_one.player_score, battle.player_one.player_name
+        )
+    };
 
-    for ch in text.chars() {
-        let g = scaled.glyph_id(ch);
-        if let Some(pid) = prev_id {
-            pen += scaled.kern(pid, g);
-        }
-        pen += scaled.h_advance(g);
-        prev_id = Some(g);
-    }
-    pen
-}
+    let right_text = if battle.player_two.player_score == 0 {
+        battle.player_two.player_name.clone()
+    } else {
+        format!(
+            "{} - {}",
+            battle.player_two.player_name, battle.player_two.player_score
+        )
+    };
 
-fn fit_text_to_width(font: &FontArc, scale: PxScale, text: &str, max_width: f32) -> String {
-    let s = text.to_string();
+    let date_text = NaiveDateTime::parse_from_str(&battle.date, "%Y-%m-%d %H:%M:%S")
+        .map(|dt| dt.date().format("%d-%m-%Y").to_string())
+        .unwrap_or_else(|_| battle.date.clone());
 
-    if text_width(font, scale, &s) <= max_width {
-        return s;
-    }
+    let outcome = match battle.status {
+        1 => MatchOutcome::Win,
+        2 => MatchOutcome::Loss,
+        _ => MatchOutcome::Unknown,
+    };
 
-    let mut chars: Vec<char> = s.chars().collect();
-    while !chars.is_empty()
-        && text_width(font, scale, &chars.iter().collect::<String>()) > max_width
-    {
-        chars.pop();
+    MatchSection {
+        left: player_to_draft(&battle.player_one, is_p1_first_pick),
+        right: player_to_draft(&battle.player_two, !is_p1_first_pick),
+        left_text,
+        center_text: date_text,
+        right_text,
+        outcome,
     }
+}
 
-    let mut result: String = chars.iter().collect();
-    if !result.is_empty() {
-        result.push('…');
+fn player_to_draft(player: &ReplayPlayer, first_pick: bool) -> TeamDraft {
+    TeamDraft {
+        image_filenames: player
+            .monster_info_list
+            .iter()
+            .map(|m| m.image_filename.clone())
+            .collect(),
+        monster_ids: player
+            .monster_info_list
+            .iter()
+            .map(|m| m.monster_id)
+            .collect(),
+        ban_id: player.ban_monster_id,
+        leader_id: player.leader_monster_id,
+        first_pick,
     }
-    result
 }
 
 pub fn create_replays_embed(
     monster_names: &[String],
     level: i32,
     player_names: &[String],
+    image_name: &str,
+    gallery_page: Option<(usize, usize)>,
+    summary: &str,
 ) -> CreateEmbed {
     let level_str = match level {
         1 => "G1-G2",
@@ -625,12 +414,19 @@ pub fn create_replays_embed(
             .join("\n")
     };
 
-    CreateEmbed::default()
+    let mut embed = CreateEmbed::default()
         .title("🎬 Replays")
         .description(description)
         .color(serenity::Colour::from_rgb(0, 123, 255)) // Bleu
-        .image("attachment://replay.png")
-        .field("Players", players_field, false) // ← insertion du champ
+        .image(format!("attachment://{}", image_name))
+        .field("📊 Summary", summary, false)
+        .field("Players", players_field, false); // ← insertion du champ
+
+    if let Some((page, last_page)) = gallery_page {
+        embed = embed.field("Replay", format!("Match {}/{}", page, last_page), false);
+    }
+
+    embed
         .field(
             "ℹ️ Tip",
             "Use the buttons below to view stats for different RTA ranks (P1-P3, G1-G2, G3).",
@@ -725,3 +521,20 @@ pub fn create_replay_level_buttons(
             .style(style_for(3)),
     ])
 }
+
+pub fn create_replay_pagination_buttons(
+    page: usize,
+    last_page: usize,
+    disabled: bool,
+) -> serenity::CreateActionRow {
+    serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new("replays_previous_page")
+            .label("⬅️ Previous")
+            .style(serenity::ButtonStyle::Primary)
+            .disabled(disabled || page <= 1),
+        serenity::CreateButton::new("replays_next_page")
+            .label("➡️ Next")
+            .style(serenity::ButtonStyle::Primary)
+            .disabled(disabled || page >= last_page),
+    ])
+}
@@ -1,12 +1,12 @@
 use crate::commands::mob_stats::command::autocomplete_monster;
 use crate::commands::mob_stats::utils::get_swrt_settings;
+use crate::commands::player_box::utils::{resolve_box, ResolvedBox};
 use crate::commands::player_stats::utils::get_mob_emoji_collection;
 use crate::commands::rta_core::cache::get_monster_duos_cached;
 use crate::commands::rta_core::models::MonstersFile;
 use crate::commands::rta_core::models::{Mode, Rank, Trio};
 use crate::commands::rta_core::utils::{
-    filter_monster, get_emoji_from_id, get_monsters_from_json_bytes, get_swrt_version,
-    get_tierlist_data,
+    filter_monster, get_emoji_from_id, get_swrt_version, get_tierlist_data,
 };
 use crate::commands::shared::embed_error_handling::{
     create_embed_error, schedule_message_deletion,
@@ -22,11 +22,13 @@ use std::fs;
 // Import de la map globale
 use crate::MONSTER_MAP;
 
-/// 📂 Displays best Trios to play for any given account JSON
+/// 📂 Displays best Trios to play for any given account JSON or your saved box
 #[poise::command(slash_command)]
 pub async fn get_rta_core(
     ctx: poise::ApplicationContext<'_, Data, Error>,
-    file: Attachment,
+    #[description = "Account JSON (defaults to your saved box, see /box save)"] file: Option<
+        Attachment,
+    >,
     #[description = "Select the targeted rank"] rank: Rank,
     #[autocomplete = "autocomplete_monster"]
     #[description = "Monster you want to get core for (optional)"]
@@ -45,56 +47,8 @@ pub async fn get_rta_core(
         })?
     };
 
-    // Vérification présence de fichier
-    if file.url.is_empty() {
-        let err = "No file provided. Please attach a JSON file.";
-        let reply = ctx.send(create_embed_error(err)).await?;
-        schedule_message_deletion(reply, ctx).await?;
-        send_log(LoggerDocument::new(
-            &ctx.author().name,
-            "get_rta_core",
-            &get_server_name(&ctx).await?,
-            false,
-            chrono::Utc::now().timestamp(),
-        ))
-        .await?;
-        return Ok(());
-    }
-
-    // Vérification extension
-    if !file.filename.to_lowercase().ends_with(".json") {
-        let err = "The provided file is not a JSON file.";
-        let reply = ctx.send(create_embed_error(err)).await?;
-        schedule_message_deletion(reply, ctx).await?;
-        send_log(LoggerDocument::new(
-            &ctx.author().name,
-            "get_rta_core",
-            &get_server_name(&ctx).await?,
-            false,
-            chrono::Utc::now().timestamp(),
-        ))
-        .await?;
-        return Ok(());
-    }
-
-    // Téléchargement
-    let bytes = match file.download().await {
-        Ok(b) => b,
-        Err(e) => {
-            let err_msg = format!("Impossible de télécharger : {}", e);
-            let reply = ctx.send(create_embed_error(&err_msg)).await?;
-            schedule_message_deletion(reply, ctx).await?;
-            send_log(LoggerDocument::new(
-                &ctx.author().name,
-                "get_rta_core",
-                &get_server_name(&ctx).await?,
-                false,
-                chrono::Utc::now().timestamp(),
-            ))
-            .await?;
-            return Ok(());
-        }
-    };
+    // Box : JSON joint, sinon box sauvegardé (/box save)
+    let resolved_box = resolve_box(file.as_ref(), ctx.author().id.get()).await;
 
     // 1️⃣ Charger le JSON statique "monsters_elements.json" pour connaître l'élément de chaque monstre
     let monsters_json_str = fs::read_to_string("monsters_elements.json")
@@ -110,8 +64,12 @@ pub async fn get_rta_core(
         .collect();
 
     // Extraction des monsters
-    match get_monsters_from_json_bytes(&bytes, "monsters_elements.json") {
-        Ok(monsters) => {
+    match resolved_box {
+        Ok(ResolvedBox {
+            wizard_name,
+            monsters,
+            from_saved,
+        }) => {
             // Si l'utilisateur a passé un nom de monstre, on le convertit en ID
             let filter_monster_id: Option<u32> = if let Some(ref name) = monster {
                 match MONSTER_MAP.get(name) {
@@ -404,20 +362,6 @@ pub async fn get_rta_core(
                 Rank::G3 => "G3",
             };
 
-            // Récupération du pseudo dans le fichier JSON uploadé (field wizard_info.wizard_name)
-            let json_str = String::from_utf8_lossy(&bytes).to_string();
-            let json_value: serde_json::Value = serde_json::from_str(&json_str).unwrap_or_default();
-            let wizard_name = json_value
-                .get("wizard_info")
-                .and_then(|w| w.get("wizard_name"))
-                .and_then(|w| w.as_str())
-                .unwrap_or("Unknown");
-            let wizard_name = if wizard_name.is_empty() {
-                "Unknown"
-            } else {
-                wizard_name
-            };
-
             // Affichage final unique
             let mode_str = match mode {
                 Mode::MetaSlayer => "Meta Slayer",
@@ -434,6 +378,9 @@ pub async fn get_rta_core(
                     wizard_name, rank_str, mode_str
                 )
             };
+            if from_saved {
+                msg.push_str("-# Using your saved box (`/box show`)\n");
+            }
             if top.is_empty() {
                 msg.push_str("– No Trio Found\n");
             } else {
@@ -34,6 +34,15 @@ pub fn get_monsters_from_json_bytes(
         })
         .collect();
 
+    get_monsters_from_ids(&wanted_ids, monsters_json_path)
+}
+
+/// Charge monsters.json et renvoie les Monster de `wanted_ids` (com2us_id
+/// après remap) qui respectent les critères d'éveil / étoiles.
+pub fn get_monsters_from_ids(
+    wanted_ids: &HashSet<u32>,
+    monsters_json_path: &str,
+) -> Result<Vec<Monster>> {
     // 3) Lire et parser monsters.json
     let monsters_data =
         fs::read_to_string(monsters_json_path).context("Impossible de lire monsters.json")?;
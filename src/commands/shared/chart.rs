@@ -0,0 +1,168 @@
+//! Graphiques en courbes (valeur / temps) pour les commandes de suivi.
+use ab_glyph::PxScale;
+use anyhow::Result;
+use image::{ImageBuffer, Rgba, RgbaImage};
+use imageproc::drawing::{draw_filled_rect_mut, draw_line_segment_mut};
+use imageproc::rect::Rect;
+
+use crate::commands::shared::render::{banner_font, draw_bold_text_mut, text_width};
+
+/// One line of a chart, points as (timestamp, value).
+#[derive(Debug, Clone)]
+pub struct ChartSeries {
+    /// `None` hides the series from the legend
+    pub label: Option<String>,
+    pub color: Rgba<u8>,
+    pub points: Vec<(i64, i32)>,
+    pub dashed: bool,
+}
+
+fn draw_thick_segment(canvas: &mut RgbaImage, from: (f32, f32), to: (f32, f32), color: Rgba<u8>) {
+    for (dx, dy) in [(0.0, 0.0), (0.0, 1.0), (1.0, 0.0)] {
+        draw_line_segment_mut(
+            canvas,
+            (from.0 + dx, from.1 + dy),
+            (to.0 + dx, to.1 + dy),
+            color,
+        );
+    }
+}
+
+fn draw_dashed_segment(canvas: &mut RgbaImage, from: (f32, f32), to: (f32, f32), color: Rgba<u8>) {
+    const DASHES: usize = 12;
+    for i in (0..DASHES).step_by(2) {
+        let t0 = i as f32 / DASHES as f32;
+        let t1 = (i + 1) as f32 / DASHES as f32;
+        draw_thick_segment(
+            canvas,
+            (from.0 + (to.0 - from.0) * t0, from.1 + (to.1 - from.1) * t0),
+            (from.0 + (to.0 - from.0) * t1, from.1 + (to.1 - from.1) * t1),
+            color,
+        );
+    }
+}
+
+/// Draws the series on a shared time axis, with a value grid on the left and
+/// the legend in the top right corner.
+pub fn render_line_chart(title: &str, series: &[ChartSeries]) -> Result<RgbaImage> {
+    const WIDTH: u32 = 900;
+    const HEIGHT: u32 = 500;
+    const LEFT: f32 = 80.0;
+    const RIGHT: f32 = 30.0;
+    const TOP: f32 = 60.0;
+    const BOTTOM: f32 = 40.0;
+    const Y_TICKS: i32 = 5;
+
+    let all_points = || series.iter().flat_map(|s| s.points.iter());
+    if all_points().next().is_none() {
+        return Err(anyhow::anyhow!("No chart data"));
+    }
+
+    let start_t = all_points().map(|(t, _)| *t).min().unwrap_or_default();
+    let end_t = all_points()
+        .map(|(t, _)| *t)
+        .max()
+        .unwrap_or_default()
+        .max(start_t + 1);
+    let min_v = all_points().map(|(_, v)| *v).min().unwrap_or_default();
+    let max_v = all_points().map(|(_, v)| *v).max().unwrap_or_default();
+    // Bornes arrondies à 50 avec un peu de marge
+    let min_v = ((min_v - 25) / 50 * 50) as f32;
+    let max_v = (((max_v + 75) / 50) * 50) as f32;
+
+    let plot_w = WIDTH as f32 - LEFT - RIGHT;
+    let plot_h = HEIGHT as f32 - TOP - BOTTOM;
+    let x_of = |t: i64| LEFT + (t - start_t) as f32 / (end_t - start_t) as f32 * plot_w;
+    let y_of = |v: i32| TOP + (max_v - v as f32) / (max_v - min_v) * plot_h;
+
+    let mut canvas: RgbaImage = ImageBuffer::from_pixel(WIDTH, HEIGHT, Rgba([32, 34, 37, 255]));
+    let font = banner_font();
+    let white = Rgba([255, 255, 255, 255]);
+    let grey = Rgba([80, 84, 90, 255]);
+    let label_scale = PxScale::from(18.0);
+
+    draw_bold_text_mut(&mut canvas, white, 12, 12, PxScale::from(26.0), font, title);
+
+    // Grille horizontale + valeurs
+    for i in 0..=Y_TICKS {
+        let value = min_v + (max_v - min_v) * i as f32 / Y_TICKS as f32;
+        let y = y_of(value.round() as i32);
+        draw_line_segment_mut(&mut canvas, (LEFT, y), (WIDTH as f32 - RIGHT, y), grey);
+        let label = format!("{}", value.round() as i32);
+        let label_x = LEFT - 10.0 - text_width(font, label_scale, &label);
+        draw_bold_text_mut(
+            &mut canvas,
+            white,
+            label_x as i32,
+            (y - 9.0) as i32,
+            label_scale,
+            font,
+            &label,
+        );
+    }
+
+    // Dates de début / fin sous l'axe
+    let date_label = |t: i64| {
+        chrono::DateTime::from_timestamp(t, 0)
+            .map(|d| d.format("%d/%m").to_string())
+            .unwrap_or_default()
+    };
+    let bottom_y = (HEIGHT as f32 - BOTTOM + 10.0) as i32;
+    draw_bold_text_mut(
+        &mut canvas,
+        white,
+        LEFT as i32,
+        bottom_y,
+        label_scale,
+        font,
+        &date_label(start_t),
+    );
+    let end_label = date_label(end_t);
+    let end_x = WIDTH as f32 - RIGHT - text_width(font, label_scale, &end_label);
+    draw_bold_text_mut(
+        &mut canvas,
+        white,
+        end_x as i32,
+        bottom_y,
+        label_scale,
+        font,
+        &end_label,
+    );
+
+    for s in series {
+        for pair in s.points.windows(2) {
+            let from = (x_of(pair[0].0), y_of(pair[0].1));
+            let to = (x_of(pair[1].0), y_of(pair[1].1));
+            if s.dashed {
+                draw_dashed_segment(&mut canvas, from, to, s.color);
+            } else {
+                draw_thick_segment(&mut canvas, from, to, s.color);
+            }
+        }
+    }
+
+    // Légende en haut à droite
+    let mut legend_x = WIDTH as f32 - RIGHT;
+    for s in series.iter().rev() {
+        let Some(label) = &s.label else {
+            continue;
+        };
+        legend_x -= text_width(font, label_scale, label) + 30.0;
+        draw_filled_rect_mut(
+            &mut canvas,
+            Rect::at(legend_x as i32, 20).of_size(14, 14),
+            s.color,
+        );
+        draw_bold_text_mut(
+            &mut canvas,
+            white,
+            legend_x as i32 + 18,
+            16,
+            label_scale,
+            font,
+            label,
+        );
+    }
+
+    Ok(canvas)
+}
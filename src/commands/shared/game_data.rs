@@ -0,0 +1,595 @@
+//! Catalogue des données de jeu partagé par les commandes : sets de runes,
+//! stats et effets d'artefacts. Les sets ont deux espaces d'ids : celui du
+//! jeu (JSON de compte) et celui de lucksack.
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/* ------------------ Rune sets ------------------ */
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuneSet {
+    Energy,
+    Guard,
+    Swift,
+    Blade,
+    Rage,
+    Focus,
+    Endure,
+    Fatal,
+    Despair,
+    Vampire,
+    Violent,
+    Nemesis,
+    Will,
+    Shield,
+    Revenge,
+    Destroy,
+    Fight,
+    Determination,
+    Enhance,
+    Accuracy,
+    Tolerance,
+    Seal,
+    Intangible,
+}
+
+impl RuneSet {
+    pub const ALL: [RuneSet; 23] = [
+        RuneSet::Energy,
+        RuneSet::Guard,
+        RuneSet::Swift,
+        RuneSet::Blade,
+        RuneSet::Rage,
+        RuneSet::Focus,
+        RuneSet::Endure,
+        RuneSet::Fatal,
+        RuneSet::Despair,
+        RuneSet::Vampire,
+        RuneSet::Violent,
+        RuneSet::Nemesis,
+        RuneSet::Will,
+        RuneSet::Shield,
+        RuneSet::Revenge,
+        RuneSet::Destroy,
+        RuneSet::Fight,
+        RuneSet::Determination,
+        RuneSet::Enhance,
+        RuneSet::Accuracy,
+        RuneSet::Tolerance,
+        RuneSet::Seal,
+        RuneSet::Intangible,
+    ];
+
+    /// `set_id` in the account JSON
+    pub fn game_id(self) -> u32 {
+        match self {
+            RuneSet::Energy => 1,
+            RuneSet::Guard => 2,
+            RuneSet::Swift => 3,
+            RuneSet::Blade => 4,
+            RuneSet::Rage => 5,
+            RuneSet::Focus => 6,
+            RuneSet::Endure => 7,
+            RuneSet::Fatal => 8,
+            RuneSet::Despair => 10,
+            RuneSet::Vampire => 11,
+            RuneSet::Violent => 13,
+            RuneSet::Nemesis => 14,
+            RuneSet::Will => 15,
+            RuneSet::Shield => 16,
+            RuneSet::Revenge => 17,
+            RuneSet::Destroy => 18,
+            RuneSet::Fight => 19,
+            RuneSet::Determination => 20,
+            RuneSet::Enhance => 21,
+            RuneSet::Accuracy => 22,
+            RuneSet::Tolerance => 23,
+            RuneSet::Seal => 24,
+            RuneSet::Intangible => 25,
+        }
+    }
+
+    /// Set id used by the lucksack API
+    pub fn lucksack_id(self) -> i32 {
+        match self {
+            RuneSet::Energy => 1,
+            RuneSet::Fatal => 2,
+            RuneSet::Blade => 3,
+            RuneSet::Rage => 4,
+            RuneSet::Swift => 5,
+            RuneSet::Focus => 6,
+            RuneSet::Guard => 7,
+            RuneSet::Endure => 8,
+            RuneSet::Violent => 9,
+            RuneSet::Will => 10,
+            RuneSet::Nemesis => 11,
+            RuneSet::Shield => 12,
+            RuneSet::Revenge => 13,
+            RuneSet::Despair => 14,
+            RuneSet::Vampire => 15,
+            RuneSet::Destroy => 16,
+            RuneSet::Fight => 17,
+            RuneSet::Determination => 18,
+            RuneSet::Enhance => 19,
+            RuneSet::Accuracy => 20,
+            RuneSet::Tolerance => 21,
+            RuneSet::Intangible => 22,
+            RuneSet::Seal => 23,
+        }
+    }
+
+    pub fn from_game_id(id: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.game_id() == id)
+    }
+
+    pub fn from_lucksack_id(id: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.lucksack_id() == id)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RuneSet::Energy => "Energy",
+            RuneSet::Guard => "Guard",
+            RuneSet::Swift => "Swift",
+            RuneSet::Blade => "Blade",
+            RuneSet::Rage => "Rage",
+            RuneSet::Focus => "Focus",
+            RuneSet::Endure => "Endure",
+            RuneSet::Fatal => "Fatal",
+            RuneSet::Despair => "Despair",
+            RuneSet::Vampire => "Vampire",
+            RuneSet::Violent => "Violent",
+            RuneSet::Nemesis => "Nemesis",
+            RuneSet::Will => "Will",
+            RuneSet::Shield => "Shield",
+            RuneSet::Revenge => "Revenge",
+            RuneSet::Destroy => "Destroy",
+            RuneSet::Fight => "Fight",
+            RuneSet::Determination => "Determination",
+            RuneSet::Enhance => "Enhance",
+            RuneSet::Accuracy => "Accuracy",
+            RuneSet::Tolerance => "Tolerance",
+            RuneSet::Seal => "Seal",
+            RuneSet::Intangible => "Intangible",
+        }
+    }
+
+    /// Runes needed to trigger the set bonus
+    pub fn pieces(self) -> u8 {
+        match self {
+            RuneSet::Swift
+            | RuneSet::Rage
+            | RuneSet::Fatal
+            | RuneSet::Despair
+            | RuneSet::Vampire
+            | RuneSet::Violent => 4,
+            // Intangible n'a pas de bonus propre, il complète un autre set
+            RuneSet::Intangible => 1,
+            _ => 2,
+        }
+    }
+
+    pub fn bonus(self) -> &'static str {
+        match self {
+            RuneSet::Energy => "HP +15%",
+            RuneSet::Guard => "DEF +15%",
+            RuneSet::Swift => "SPD +25%",
+            RuneSet::Blade => "CRIT Rate +12%",
+            RuneSet::Rage => "CRIT DMG +40%",
+            RuneSet::Focus => "Accuracy +20%",
+            RuneSet::Endure => "Resistance +20%",
+            RuneSet::Fatal => "ATK +35%",
+            RuneSet::Despair => "Stun chance +25%",
+            RuneSet::Vampire => "Life Drain +35%",
+            RuneSet::Violent => "Extra turn chance +22%",
+            RuneSet::Nemesis => "ATK Bar +4% per 7% HP lost",
+            RuneSet::Will => "Immunity for 1 turn",
+            RuneSet::Shield => "Team shield (15% HP) for 3 turns",
+            RuneSet::Revenge => "Counterattack chance +15%",
+            RuneSet::Destroy => "Reduces enemy MAX HP by 4% per 30% of damage dealt",
+            RuneSet::Fight => "Team ATK +8%",
+            RuneSet::Determination => "Team DEF +8%",
+            RuneSet::Enhance => "Team HP +8%",
+            RuneSet::Accuracy => "Team Accuracy +10%",
+            RuneSet::Tolerance => "Team Resistance +10%",
+            RuneSet::Seal => "Chance to inflict Seal when attacking",
+            RuneSet::Intangible => "Counts towards any other set",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            RuneSet::Energy => "💚",
+            RuneSet::Guard => "🛡️",
+            RuneSet::Swift => "💨",
+            RuneSet::Blade => "🗡️",
+            RuneSet::Rage => "💢",
+            RuneSet::Focus => "🎯",
+            RuneSet::Endure => "🧱",
+            RuneSet::Fatal => "💀",
+            RuneSet::Despair => "😵",
+            RuneSet::Vampire => "🧛",
+            RuneSet::Violent => "🌀",
+            RuneSet::Nemesis => "⏩",
+            RuneSet::Will => "✨",
+            RuneSet::Shield => "🔰",
+            RuneSet::Revenge => "↩️",
+            RuneSet::Destroy => "💥",
+            RuneSet::Fight => "⚔️",
+            RuneSet::Determination => "🏰",
+            RuneSet::Enhance => "❤️",
+            RuneSet::Accuracy => "🏹",
+            RuneSet::Tolerance => "🧘",
+            RuneSet::Seal => "🔒",
+            RuneSet::Intangible => "👻",
+        }
+    }
+}
+
+impl fmt::Display for RuneSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/* ------------------ Stats ------------------ */
+
+#[derive(Serialize, Deserialize, Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Stat {
+    #[default]
+    None,
+    Hp,
+    HpPct,
+    Atk,
+    AtkPct,
+    Def,
+    DefPct,
+    Spd,
+    CritRatePct,
+    CritDmgPct,
+    ResistPct,
+    AccuracyPct,
+}
+
+impl Stat {
+    pub const ALL: [Stat; 12] = [
+        Stat::None,
+        Stat::Hp,
+        Stat::HpPct,
+        Stat::Atk,
+        Stat::AtkPct,
+        Stat::Def,
+        Stat::DefPct,
+        Stat::Spd,
+        Stat::CritRatePct,
+        Stat::CritDmgPct,
+        Stat::ResistPct,
+        Stat::AccuracyPct,
+    ];
+
+    /// Stat id, identical in the account JSON and on lucksack
+    pub fn id(self) -> u32 {
+        match self {
+            Stat::None => 0,
+            Stat::Hp => 1,
+            Stat::HpPct => 2,
+            Stat::Atk => 3,
+            Stat::AtkPct => 4,
+            Stat::Def => 5,
+            Stat::DefPct => 6,
+            Stat::Spd => 8,
+            Stat::CritRatePct => 9,
+            Stat::CritDmgPct => 10,
+            Stat::ResistPct => 11,
+            Stat::AccuracyPct => 12,
+        }
+    }
+
+    pub fn from_id(id: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.id() == id)
+    }
+
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Stat::None => "-",
+            Stat::Hp => "HP",
+            Stat::HpPct => "HP%",
+            Stat::Atk => "ATK",
+            Stat::AtkPct => "ATK%",
+            Stat::Def => "DEF",
+            Stat::DefPct => "DEF%",
+            Stat::Spd => "SPD",
+            Stat::CritRatePct => "CR",
+            Stat::CritDmgPct => "CD",
+            Stat::ResistPct => "RES",
+            Stat::AccuracyPct => "ACC",
+        }
+    }
+}
+
+/* ------------------ Artifact effects ------------------ */
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArtifactEffect {
+    // Artefacts d'élément (200-226)
+    AtkUpLostHp,
+    DefUpLostHp,
+    SpdUpLostHp,
+    SpdUnderInability,
+    AtkBuffEffect,
+    DefBuffEffect,
+    SpdBuffEffect,
+    CritRateBuffEffect,
+    CounterDmg,
+    CoopAttackDmg,
+    BombDmg,
+    ReflectDmg,
+    CrushingHitDmg,
+    DmgReceivedUnderInability,
+    CritDmgReceived,
+    LifeDrain,
+    HpWhenRevived,
+    AtkBarWhenRevived,
+    AddDmgByHp,
+    AddDmgByAtk,
+    AddDmgByDef,
+    AddDmgBySpd,
+    CritDmgEnemyHpHigh,
+    CritDmgEnemyHpLow,
+    SingleTargetCritDmg,
+    CounterCoopCritDmg,
+    AtkDefBuffEffect,
+    // Artefacts de type : dégâts par élément (300-309)
+    DmgDealtOnFire,
+    DmgDealtOnWater,
+    DmgDealtOnWind,
+    DmgDealtOnLight,
+    DmgDealtOnDark,
+    DmgReceivedFromFire,
+    DmgReceivedFromWater,
+    DmgReceivedFromWind,
+    DmgReceivedFromLight,
+    DmgReceivedFromDark,
+    // Artefacts de type : effets de compétences (400-411)
+    Skill1CritDmg,
+    Skill2CritDmg,
+    Skill3CritDmg,
+    Skill4CritDmg,
+    Skill1Recovery,
+    Skill2Recovery,
+    Skill3Recovery,
+    Skill1Accuracy,
+    Skill2Accuracy,
+    Skill3Accuracy,
+    Skill3Or4CritDmg,
+    FirstAttackCritDmg,
+}
+
+impl ArtifactEffect {
+    pub const ALL: [ArtifactEffect; 49] = [
+        ArtifactEffect::AtkUpLostHp,
+        ArtifactEffect::DefUpLostHp,
+        ArtifactEffect::SpdUpLostHp,
+        ArtifactEffect::SpdUnderInability,
+        ArtifactEffect::AtkBuffEffect,
+        ArtifactEffect::DefBuffEffect,
+        ArtifactEffect::SpdBuffEffect,
+        ArtifactEffect::CritRateBuffEffect,
+        ArtifactEffect::CounterDmg,
+        ArtifactEffect::CoopAttackDmg,
+        ArtifactEffect::BombDmg,
+        ArtifactEffect::ReflectDmg,
+        ArtifactEffect::CrushingHitDmg,
+        ArtifactEffect::DmgReceivedUnderInability,
+        ArtifactEffect::CritDmgReceived,
+        ArtifactEffect::LifeDrain,
+        ArtifactEffect::HpWhenRevived,
+        ArtifactEffect::AtkBarWhenRevived,
+        ArtifactEffect::AddDmgByHp,
+        ArtifactEffect::AddDmgByAtk,
+        ArtifactEffect::AddDmgByDef,
+        ArtifactEffect::AddDmgBySpd,
+        ArtifactEffect::CritDmgEnemyHpHigh,
+        ArtifactEffect::CritDmgEnemyHpLow,
+        ArtifactEffect::SingleTargetCritDmg,
+        ArtifactEffect::CounterCoopCritDmg,
+        ArtifactEffect::AtkDefBuffEffect,
+        ArtifactEffect::DmgDealtOnFire,
+        ArtifactEffect::DmgDealtOnWater,
+        ArtifactEffect::DmgDealtOnWind,
+        ArtifactEffect::DmgDealtOnLight,
+        ArtifactEffect::DmgDealtOnDark,
+        ArtifactEffect::DmgReceivedFromFire,
+        ArtifactEffect::DmgReceivedFromWater,
+        ArtifactEffect::DmgReceivedFromWind,
+        ArtifactEffect::DmgReceivedFromLight,
+        ArtifactEffect::DmgReceivedFromDark,
+        ArtifactEffect::Skill1CritDmg,
+        ArtifactEffect::Skill2CritDmg,
+        ArtifactEffect::Skill3CritDmg,
+        ArtifactEffect::Skill4CritDmg,
+        ArtifactEffect::Skill1Recovery,
+        ArtifactEffect::Skill2Recovery,
+        ArtifactEffect::Skill3Recovery,
+        ArtifactEffect::Skill1Accuracy,
+        ArtifactEffect::Skill2Accuracy,
+        ArtifactEffect::Skill3Accuracy,
+        ArtifactEffect::Skill3Or4CritDmg,
+        ArtifactEffect::FirstAttackCritDmg,
+    ];
+
+    /// Effect id (game and lucksack)
+    pub fn id(self) -> i32 {
+        match self {
+            ArtifactEffect::AtkUpLostHp => 200,
+            ArtifactEffect::DefUpLostHp => 201,
+            ArtifactEffect::SpdUpLostHp => 202,
+            ArtifactEffect::SpdUnderInability => 203,
+            ArtifactEffect::AtkBuffEffect => 204,
+            ArtifactEffect::DefBuffEffect => 205,
+            ArtifactEffect::SpdBuffEffect => 206,
+            ArtifactEffect::CritRateBuffEffect => 207,
+            ArtifactEffect::CounterDmg => 208,
+            ArtifactEffect::CoopAttackDmg => 209,
+            ArtifactEffect::BombDmg => 210,
+            ArtifactEffect::ReflectDmg => 211,
+            ArtifactEffect::CrushingHitDmg => 212,
+            ArtifactEffect::DmgReceivedUnderInability => 213,
+            ArtifactEffect::CritDmgReceived => 214,
+            ArtifactEffect::LifeDrain => 215,
+            ArtifactEffect::HpWhenRevived => 216,
+            ArtifactEffect::AtkBarWhenRevived => 217,
+            ArtifactEffect::AddDmgByHp => 218,
+            ArtifactEffect::AddDmgByAtk => 219,
+            ArtifactEffect::AddDmgByDef => 220,
+            ArtifactEffect::AddDmgBySpd => 221,
+            ArtifactEffect::CritDmgEnemyHpHigh => 222,
+            ArtifactEffect::CritDmgEnemyHpLow => 223,
+            ArtifactEffect::SingleTargetCritDmg => 224,
+            ArtifactEffect::CounterCoopCritDmg => 225,
+            ArtifactEffect::AtkDefBuffEffect => 226,
+            ArtifactEffect::DmgDealtOnFire => 300,
+            ArtifactEffect::DmgDealtOnWater => 301,
+            ArtifactEffect::DmgDealtOnWind => 302,
+            ArtifactEffect::DmgDealtOnLight => 303,
+            ArtifactEffect::DmgDealtOnDark => 304,
+            ArtifactEffect::DmgReceivedFromFire => 305,
+            ArtifactEffect::DmgReceivedFromWater => 306,
+            ArtifactEffect::DmgReceivedFromWind => 307,
+            ArtifactEffect::DmgReceivedFromLight => 308,
+            ArtifactEffect::DmgReceivedFromDark => 309,
+            ArtifactEffect::Skill1CritDmg => 400,
+            ArtifactEffect::Skill2CritDmg => 401,
+            ArtifactEffect::Skill3CritDmg => 402,
+            ArtifactEffect::Skill4CritDmg => 403,
+            ArtifactEffect::Skill1Recovery => 404,
+            ArtifactEffect::Skill2Recovery => 405,
+            ArtifactEffect::Skill3Recovery => 406,
+            ArtifactEffect::Skill1Accuracy => 407,
+            ArtifactEffect::Skill2Accuracy => 408,
+            ArtifactEffect::Skill3Accuracy => 409,
+            ArtifactEffect::Skill3Or4CritDmg => 410,
+            ArtifactEffect::FirstAttackCritDmg => 411,
+        }
+    }
+
+    pub fn from_id(id: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.id() == id)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ArtifactEffect::AtkUpLostHp => "ATK+ Proportional to Lost HP",
+            ArtifactEffect::DefUpLostHp => "DEF+ Proportional to Lost HP",
+            ArtifactEffect::SpdUpLostHp => "SPD+ Proportional to Lost HP",
+            ArtifactEffect::SpdUnderInability => "SPD Under Inability",
+            ArtifactEffect::AtkBuffEffect => "ATK Increasing Effect",
+            ArtifactEffect::DefBuffEffect => "DEF Increasing Effect",
+            ArtifactEffect::SpdBuffEffect => "SPD Increasing Effect",
+            ArtifactEffect::CritRateBuffEffect => "CRIT Rate Increasing Effect",
+            ArtifactEffect::CounterDmg => "Damage by Counterattack",
+            ArtifactEffect::CoopAttackDmg => "Damage by Co-op Attack",
+            ArtifactEffect::BombDmg => "Bomb Damage",
+            ArtifactEffect::ReflectDmg => "Reflect DMG",
+            ArtifactEffect::CrushingHitDmg => "Crushing Hit DMG",
+            ArtifactEffect::DmgReceivedUnderInability => "Damage Received Under Inability",
+            ArtifactEffect::CritDmgReceived => "CRIT DMG Received",
+            ArtifactEffect::LifeDrain => "Life Drain",
+            ArtifactEffect::HpWhenRevived => "HP when Revived",
+            ArtifactEffect::AtkBarWhenRevived => "ATK Bar when Revived",
+            ArtifactEffect::AddDmgByHp => "Additional DMG by HP%",
+            ArtifactEffect::AddDmgByAtk => "Additional DMG by ATK%",
+            ArtifactEffect::AddDmgByDef => "Additional DMG by DEF%",
+            ArtifactEffect::AddDmgBySpd => "Additional DMG by SPD",
+            ArtifactEffect::CritDmgEnemyHpHigh => "CRIT DMG+ (Enemy HP High)",
+            ArtifactEffect::CritDmgEnemyHpLow => "CRIT DMG+ (Enemy HP Low)",
+            ArtifactEffect::SingleTargetCritDmg => "Single-target CRIT DMG",
+            ArtifactEffect::CounterCoopCritDmg => "Counter/Co-op CRIT DMG",
+            ArtifactEffect::AtkDefBuffEffect => "ATK/DEF Increasing Effect",
+            ArtifactEffect::DmgDealtOnFire => "DMG Dealt on Fire",
+            ArtifactEffect::DmgDealtOnWater => "DMG Dealt on Water",
+            ArtifactEffect::DmgDealtOnWind => "DMG Dealt on Wind",
+            ArtifactEffect::DmgDealtOnLight => "DMG Dealt on Light",
+            ArtifactEffect::DmgDealtOnDark => "DMG Dealt on Dark",
+            ArtifactEffect::DmgReceivedFromFire => "DMG Received from Fire",
+            ArtifactEffect::DmgReceivedFromWater => "DMG Received from Water",
+            ArtifactEffect::DmgReceivedFromWind => "DMG Received from Wind",
+            ArtifactEffect::DmgReceivedFromLight => "DMG Received from Light",
+            ArtifactEffect::DmgReceivedFromDark => "DMG Received from Dark",
+            ArtifactEffect::Skill1CritDmg => "[Skill 1] CRIT DMG",
+            ArtifactEffect::Skill2CritDmg => "[Skill 2] CRIT DMG",
+            ArtifactEffect::Skill3CritDmg => "[Skill 3] CRIT DMG",
+            ArtifactEffect::Skill4CritDmg => "[Skill 4] CRIT DMG",
+            ArtifactEffect::Skill1Recovery => "[Skill 1] Recovery",
+            ArtifactEffect::Skill2Recovery => "[Skill 2] Recovery",
+            ArtifactEffect::Skill3Recovery => "[Skill 3] Recovery",
+            ArtifactEffect::Skill1Accuracy => "[Skill 1] Accuracy",
+            ArtifactEffect::Skill2Accuracy => "[Skill 2] Accuracy",
+            ArtifactEffect::Skill3Accuracy => "[Skill 3] Accuracy",
+            ArtifactEffect::Skill3Or4CritDmg => "[Skill 3/4] CRIT DMG",
+            ArtifactEffect::FirstAttackCritDmg => "First Attack CRIT DMG",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            ArtifactEffect::DmgDealtOnFire | ArtifactEffect::DmgReceivedFromFire => "🔥",
+            ArtifactEffect::DmgDealtOnWater | ArtifactEffect::DmgReceivedFromWater => "💧",
+            ArtifactEffect::DmgDealtOnWind | ArtifactEffect::DmgReceivedFromWind => "🌪️",
+            ArtifactEffect::DmgDealtOnLight | ArtifactEffect::DmgReceivedFromLight => "☀️",
+            ArtifactEffect::DmgDealtOnDark | ArtifactEffect::DmgReceivedFromDark => "🌑",
+            ArtifactEffect::Skill1Recovery
+            | ArtifactEffect::Skill2Recovery
+            | ArtifactEffect::Skill3Recovery
+            | ArtifactEffect::LifeDrain
+            | ArtifactEffect::HpWhenRevived => "💚",
+            ArtifactEffect::Skill1Accuracy
+            | ArtifactEffect::Skill2Accuracy
+            | ArtifactEffect::Skill3Accuracy => "🎯",
+            ArtifactEffect::DmgReceivedUnderInability | ArtifactEffect::CritDmgReceived => "🛡️",
+            _ => "⚔️",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn rune_set_ids_round_trip() {
+        for set in RuneSet::ALL {
+            assert_eq!(RuneSet::from_game_id(set.game_id()), Some(set));
+            assert_eq!(RuneSet::from_lucksack_id(set.lucksack_id()), Some(set));
+        }
+    }
+
+    #[test]
+    fn rune_set_ids_are_unique() {
+        let game_ids: HashSet<u32> = RuneSet::ALL.iter().map(|s| s.game_id()).collect();
+        let lucksack_ids: HashSet<i32> = RuneSet::ALL.iter().map(|s| s.lucksack_id()).collect();
+        assert_eq!(game_ids.len(), RuneSet::ALL.len());
+        assert_eq!(lucksack_ids.len(), RuneSet::ALL.len());
+    }
+
+    #[test]
+    fn stat_ids_round_trip() {
+        for stat in Stat::ALL {
+            assert_eq!(Stat::from_id(stat.id()), Some(stat));
+        }
+        let ids: HashSet<u32> = Stat::ALL.iter().map(|s| s.id()).collect();
+        assert_eq!(ids.len(), Stat::ALL.len());
+    }
+
+    #[test]
+    fn artifact_effects_cover_known_ranges() {
+        for id in (200..=226).chain(300..=309).chain(400..=411) {
+            let effect = ArtifactEffect::from_id(id);
+            assert!(effect.is_some(), "missing artifact effect {id}");
+            assert_eq!(effect.map(ArtifactEffect::id), Some(id));
+        }
+        assert_eq!(ArtifactEffect::ALL.len(), 27 + 10 + 12);
+        assert_eq!(ArtifactEffect::from_id(227), None);
+    }
+}
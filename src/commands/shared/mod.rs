@@ -1,7 +1,11 @@
+pub mod chart;
 pub mod clients;
 pub mod coupons;
 pub mod embed_error_handling;
+pub mod game_data;
 pub mod logs;
 pub mod models;
 pub mod player_alias;
+pub mod portraits;
+pub mod render;
 pub mod utils;
@@ -41,6 +41,18 @@ pub enum Mode {
     NoSpeedDetailAndAnonymized,
 }
 
+/// How replay images are sent: one grid, one match per page, or an animation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, poise::ChoiceParameter)]
+pub enum ReplayDisplay {
+    #[default]
+    #[name = "Grid"]
+    Grid,
+    #[name = "Gallery (one match per page)"]
+    Gallery,
+    #[name = "Animated GIF"]
+    Animated,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LoggerDocument {
     pub username: String,
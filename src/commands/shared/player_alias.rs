@@ -1,35 +1,353 @@
+//! Player alias registry.
+//!
+//! Aliases live in the `player-aliases` Mongo collection and are kept in
+//! memory for synchronous lookups. The bundled list below seeds an empty
+//! collection and serves as the registry until Mongo has been loaded.
+use anyhow::{anyhow, Result};
+use futures::stream::TryStreamExt;
 use lazy_static::lazy_static;
+use mongodb::bson::doc;
+use mongodb::Collection;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
+use std::sync::RwLock;
 
-lazy_static! {
-    /// Map utilisée dans `/get_leaderboard` pour afficher (alias)
-    pub static ref PLAYER_ALIAS_MAP: HashMap<i64, String> = {
-        let mut m = HashMap::new();
-        for player in ALIAS_DATA["players"].as_array().unwrap() {
-            let lucksack_id = player["lucksack_id"].as_i64().unwrap();
-            let en_names = player["en_names"].as_array().unwrap();
-            if let Some(first_alias) = en_names.first() {
-                m.insert(lucksack_id, first_alias.as_str().unwrap().to_string());
+use crate::commands::shared::clients::mongo_client;
+
+const MAX_ALIAS_LEN: usize = 64;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerAlias {
+    #[serde(default, alias = "og_names")]
+    pub og_name: Option<String>,
+    pub en_names: Vec<String>,
+    #[serde(default)]
+    pub swarena_id: Option<i64>,
+    pub lucksack_id: i64,
+}
+
+impl PlayerAlias {
+    pub fn validate(&self) -> Result<()> {
+        if self.lucksack_id <= 0 {
+            return Err(anyhow!("Invalid lucksack id: {}", self.lucksack_id));
+        }
+        if self.en_names.is_empty() {
+            return Err(anyhow!("No alias for lucksack id {}", self.lucksack_id));
+        }
+        for name in &self.en_names {
+            validate_alias_name(name)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn validate_alias_name(name: &str) -> Result<()> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err(anyhow!("Alias cannot be empty"));
+    }
+    if trimmed != name {
+        return Err(anyhow!("Alias `{}` has leading or trailing spaces", name));
+    }
+    if name.chars().count() > MAX_ALIAS_LEN {
+        return Err(anyhow!(
+            "Alias `{}` is longer than {} characters",
+            name,
+            MAX_ALIAS_LEN
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct AliasRegistry {
+    entries: Vec<PlayerAlias>,
+    /// lucksack_id -> premier alias (affichage)
+    display: HashMap<i64, String>,
+    /// alias en minuscules -> lucksack_id
+    lookup: HashMap<String, i64>,
+}
+
+impl AliasRegistry {
+    fn from_entries(entries: Vec<PlayerAlias>) -> Self {
+        let mut display = HashMap::new();
+        let mut lookup = HashMap::new();
+
+        for entry in &entries {
+            if let Some(first) = entry.en_names.first() {
+                display.insert(entry.lucksack_id, first.clone());
             }
+            for name in &entry.en_names {
+                lookup.insert(name.to_lowercase(), entry.lucksack_id);
+            }
+        }
+
+        Self {
+            entries,
+            display,
+            lookup,
         }
-        m
+    }
+}
+
+static ALIAS_REGISTRY: Lazy<RwLock<AliasRegistry>> =
+    Lazy::new(|| RwLock::new(AliasRegistry::from_entries(seed_aliases())));
+
+/// Alias affiché à côté du pseudo (`/get_rta_leaderboard`, `/get_player_stats`, ...)
+pub fn player_alias(lucksack_id: i64) -> Option<String> {
+    ALIAS_REGISTRY
+        .read()
+        .ok()
+        .and_then(|r| r.display.get(&lucksack_id).cloned())
+}
+
+/// Exact (case-insensitive) alias lookup, used to resolve a command input.
+pub fn find_player_by_alias(name: &str) -> Option<i64> {
+    let registry = ALIAS_REGISTRY.read().ok()?;
+    registry.lookup.get(&name.trim().to_lowercase()).copied()
+}
+
+/// "Did you mean" hint for an input that matched no alias exactly: the alias
+/// with the same name once spaces and punctuation are removed, or a single
+/// typo on names of at least 5 characters, as long as only one player matches.
+/// Never used to resolve a player by itself.
+pub fn suggest_alias(name: &str) -> Option<String> {
+    let registry = ALIAS_REGISTRY.read().ok()?;
+    let query = normalize_alias(&name.trim().to_lowercase());
+    if query.is_empty() {
+        return None;
+    }
+
+    let candidates: Vec<(i64, &String)> = registry
+        .entries
+        .iter()
+        .flat_map(|e| e.en_names.iter().map(move |n| (e.lucksack_id, n)))
+        .filter(|(_, alias)| {
+            let alias = normalize_alias(&alias.to_lowercase());
+            alias == query || (query.chars().count() >= 5 && levenshtein(&alias, &query) <= 1)
+        })
+        .collect();
+
+    let (first_id, first_name) = candidates.first()?;
+    candidates
+        .iter()
+        .all(|(id, _)| id == first_id)
+        .then(|| (*first_name).clone())
+}
+
+/// Aliases ranked by closeness to `query` (prefix, then substring, then edit
+/// distance). Returns `(alias, lucksack_id)`.
+pub fn search_aliases(query: &str, limit: usize) -> Vec<(String, i64)> {
+    let Ok(registry) = ALIAS_REGISTRY.read() else {
+        return Vec::new();
     };
+    let query = normalize_alias(&query.to_lowercase());
+
+    let mut scored: Vec<(usize, String, i64)> = registry
+        .entries
+        .iter()
+        .flat_map(|e| e.en_names.iter().map(move |n| (n.clone(), e.lucksack_id)))
+        .filter_map(|(name, id)| {
+            let alias = normalize_alias(&name.to_lowercase());
+            let score = if query.is_empty() || alias.starts_with(&query) {
+                0
+            } else if alias.contains(&query) {
+                1
+            } else {
+                let distance = levenshtein(&alias, &query);
+                if distance > 2 {
+                    return None;
+                }
+                1 + distance
+            };
+            Some((score, name, id))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.len().cmp(&b.1.len())));
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, name, id)| (name, id))
+        .collect()
+}
+
+pub fn list_aliases() -> Vec<PlayerAlias> {
+    ALIAS_REGISTRY
+        .read()
+        .map(|r| r.entries.clone())
+        .unwrap_or_default()
+}
+
+fn normalize_alias(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).collect()
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
 
-    /// Map utilisée pour faire la recherche par alias dans `/get_player_stats`
-    pub static ref ALIAS_LOOKUP_MAP: HashMap<String, i64> = {
-        let mut m = HashMap::new();
-        for player in ALIAS_DATA["players"].as_array().unwrap() {
-            let lucksack_id = player["lucksack_id"].as_i64().unwrap();
-            let en_names = player["en_names"].as_array().unwrap();
-            for alias in en_names {
-                m.insert(alias.as_str().unwrap().to_lowercase(), lucksack_id);
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/* ------------------ Mongo storage ------------------ */
+
+fn aliases_collection() -> Result<Collection<PlayerAlias>> {
+    Ok(mongo_client()?
+        .database("bot-swbox-db")
+        .collection::<PlayerAlias>("player-aliases"))
+}
+
+/// Seeds an empty collection with the bundled list, then reloads the
+/// in-memory registry. Invalid documents are skipped.
+pub async fn load_alias_registry() -> Result<usize> {
+    let col = aliases_collection()?;
+
+    if col.estimated_document_count().await? == 0 {
+        let seed = seed_aliases();
+        if !seed.is_empty() {
+            col.insert_many(seed).await?;
+        }
+    }
+
+    reload_alias_registry().await
+}
+
+async fn reload_alias_registry() -> Result<usize> {
+    let col = aliases_collection()?;
+    let raw = col.clone_with_type::<mongodb::bson::Document>();
+    let docs: Vec<mongodb::bson::Document> = raw.find(doc! {}).await?.try_collect().await?;
+
+    let entries: Vec<PlayerAlias> = docs
+        .into_iter()
+        .filter_map(|d| match mongodb::bson::from_document::<PlayerAlias>(d) {
+            Ok(entry) => match entry.validate() {
+                Ok(()) => Some(entry),
+                Err(e) => {
+                    eprintln!("Skipping invalid player alias: {e}");
+                    None
+                }
+            },
+            Err(e) => {
+                eprintln!("Skipping malformed player alias: {e}");
+                None
             }
+        })
+        .collect();
+
+    let count = entries.len();
+    if let Ok(mut registry) = ALIAS_REGISTRY.write() {
+        *registry = AliasRegistry::from_entries(entries);
+    }
+    Ok(count)
+}
+
+/// Adds `name` to the aliases of `lucksack_id` (creating the entry if needed).
+pub async fn add_alias(lucksack_id: i64, name: &str, swarena_id: Option<i64>) -> Result<()> {
+    validate_alias_name(name)?;
+    if lucksack_id <= 0 {
+        return Err(anyhow!("Invalid lucksack id: {}", lucksack_id));
+    }
+
+    if let Some(owner) = ALIAS_REGISTRY
+        .read()
+        .ok()
+        .and_then(|r| r.lookup.get(&name.to_lowercase()).copied())
+    {
+        if owner != lucksack_id {
+            return Err(anyhow!(
+                "`{}` is already the alias of lucksack id {}",
+                name,
+                owner
+            ));
         }
-        m
+    }
+
+    let col = aliases_collection()?;
+    let filter = doc! { "lucksack_id": lucksack_id };
+
+    if col.find_one(filter.clone()).await?.is_some() {
+        let mut update = doc! { "$addToSet": { "en_names": name } };
+        if let Some(swarena_id) = swarena_id {
+            update.insert("$set", doc! { "swarena_id": swarena_id });
+        }
+        col.update_one(filter, update).await?;
+    } else {
+        col.insert_one(PlayerAlias {
+            og_name: None,
+            en_names: vec![name.to_string()],
+            swarena_id,
+            lucksack_id,
+        })
+        .await?;
+    }
+
+    reload_alias_registry().await?;
+    Ok(())
+}
+
+/// Removes `name` (case-insensitive). Entries left without alias are deleted.
+/// Returns the lucksack id the alias belonged to.
+pub async fn remove_alias(name: &str) -> Result<Option<i64>> {
+    let Some(entry) = ALIAS_REGISTRY.read().ok().and_then(|r| {
+        r.entries
+            .iter()
+            .find(|e| e.en_names.iter().any(|n| n.eq_ignore_ascii_case(name)))
+            .cloned()
+    }) else {
+        return Ok(None);
     };
 
-    static ref ALIAS_DATA: serde_json::Value = json!({
+    let remaining: Vec<String> = entry
+        .en_names
+        .iter()
+        .filter(|n| !n.eq_ignore_ascii_case(name))
+        .cloned()
+        .collect();
+
+    let col = aliases_collection()?;
+    let filter = doc! { "lucksack_id": entry.lucksack_id };
+    if remaining.is_empty() {
+        col.delete_one(filter).await?;
+    } else {
+        col.update_one(filter, doc! { "$set": { "en_names": remaining } })
+            .await?;
+    }
+
+    reload_alias_registry().await?;
+    Ok(Some(entry.lucksack_id))
+}
+
+/* ------------------ Bundled seed ------------------ */
+
+fn seed_aliases() -> Vec<PlayerAlias> {
+    SEED_ALIAS_DATA["players"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| {
+            let entry = serde_json::from_value::<PlayerAlias>(v.clone()).ok()?;
+            entry.validate().ok()?;
+            Some(entry)
+        })
+        .collect()
+}
+
+lazy_static! {
+    static ref SEED_ALIAS_DATA: serde_json::Value = json!({
         "players": [
             // Gros joueurs
             {
@@ -143,3 +461,19 @@ lazy_static! {
         ]
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alias_lookup_is_exact_and_fuzzy_matches_are_only_suggested() {
+        assert_eq!(find_player_by_alias("  kelian BAO "), Some(283644));
+        assert_eq!(find_player_by_alias("Kelianbaoo"), None);
+        assert_eq!(find_player_by_alias("sk-it"), None);
+
+        assert_eq!(suggest_alias("Kelianbaoo").as_deref(), Some("Kelianbao"));
+        assert_eq!(suggest_alias("sk-it").as_deref(), Some("skit"));
+        assert_eq!(suggest_alias("Tar"), None);
+    }
+}
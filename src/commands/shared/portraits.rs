@@ -0,0 +1,228 @@
+//! Monster portrait store.
+//!
+//! Portraits live on disk in `assets/monster_images`, keyed by their
+//! `image_filename`. Decoded images are kept in a bounded LRU cache, and a
+//! missing portrait never blocks rendering: a placeholder tile is returned
+//! right away while the file is fetched from swarfarm in the background.
+use ab_glyph::PxScale;
+use anyhow::{anyhow, Context, Result};
+use futures::stream::{self, StreamExt};
+use image::{DynamicImage, ImageBuffer, Rgba, RgbaImage};
+use moka::future::Cache;
+use moka::policy::EvictionPolicy;
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::commands::shared::clients::http_client;
+use crate::commands::shared::render::{banner_font, draw_bold_text_mut, text_width};
+use crate::LUCKSACK_MONSTER_MAP;
+
+pub const PORTRAIT_DIR: &str = "assets/monster_images";
+pub const PORTRAIT_SIZE: u32 = 100;
+const PORTRAIT_BASE_URL: &str = "https://swarfarm.com/static/herders/images/monsters/";
+const SYNC_CONCURRENCY: usize = 8;
+
+/// Décodé + redimensionné à 100x100, ~40 Ko par entrée
+static PORTRAIT_CACHE: Lazy<Cache<String, DynamicImage>> = Lazy::new(|| {
+    Cache::builder()
+        .eviction_policy(EvictionPolicy::lru())
+        .max_capacity(1_500)
+        .build()
+});
+
+/// Portraits déjà demandés : une seule tentative par process, `sync_portraits` rattrape le reste
+static REQUESTED_DOWNLOADS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+static PLACEHOLDER: OnceLock<DynamicImage> = OnceLock::new();
+
+/// Returns the portrait for `filename`, or the placeholder tile if it is not
+/// on disk yet. Never waits on the network.
+pub async fn get_portrait(filename: &str) -> DynamicImage {
+    if let Some(img) = PORTRAIT_CACHE.get(filename).await {
+        return img;
+    }
+
+    match read_portrait_from_disk(filename).await {
+        Ok(img) => {
+            PORTRAIT_CACHE
+                .insert(filename.to_string(), img.clone())
+                .await;
+            img
+        }
+        Err(_) => {
+            schedule_download(filename);
+            placeholder_portrait().clone()
+        }
+    }
+}
+
+/// Puts `img` in the portrait cache, so tests never touch the disk or the network.
+#[cfg(test)]
+pub async fn preload_portrait(filename: &str, img: DynamicImage) {
+    PORTRAIT_CACHE.insert(filename.to_string(), img).await;
+}
+
+/// Whether `filename` is already on disk (`get_portrait` will not fall back
+/// to the placeholder).
+pub fn has_portrait(filename: &str) -> bool {
+    is_safe_filename(filename) && portrait_path(filename).exists()
+}
+
+/// Grey "?" tile used while a portrait is missing.
+pub fn placeholder_portrait() -> &'static DynamicImage {
+    PLACEHOLDER.get_or_init(|| {
+        let mut tile: RgbaImage =
+            ImageBuffer::from_pixel(PORTRAIT_SIZE, PORTRAIT_SIZE, Rgba([60, 60, 60, 255]));
+
+        let font = banner_font();
+        let scale = PxScale::from(64.0);
+        let x = ((PORTRAIT_SIZE as f32 - text_width(font, scale, "?")) / 2.0).round() as i32;
+        draw_bold_text_mut(
+            &mut tile,
+            Rgba([200, 200, 200, 255]),
+            x,
+            12,
+            scale,
+            font,
+            "?",
+        );
+
+        DynamicImage::ImageRgba8(tile)
+    })
+}
+
+/// Downloads every portrait referenced by `monsters_elements.json` and the
+/// lucksack catalog that is not on disk yet. Returns the number of new files.
+pub async fn sync_portraits() -> Result<usize> {
+    tokio::fs::create_dir_all(PORTRAIT_DIR).await?;
+
+    let missing: Vec<String> = known_portrait_filenames()
+        .into_iter()
+        .filter(|f| !portrait_path(f).exists())
+        .collect();
+
+    let downloaded = stream::iter(missing)
+        .map(|filename| async move {
+            match download_portrait(&filename).await {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("Portrait sync failed for {filename}: {e:?}");
+                    false
+                }
+            }
+        })
+        .buffer_unordered(SYNC_CONCURRENCY)
+        .filter(|ok| futures::future::ready(*ok))
+        .count()
+        .await;
+
+    Ok(downloaded)
+}
+
+fn known_portrait_filenames() -> HashSet<String> {
+    let mut filenames = HashSet::new();
+
+    if let Ok(file) = std::fs::read_to_string("monsters_elements.json") {
+        if let Ok(v) = serde_json::from_str::<Value>(&file) {
+            for m in v["monsters"].as_array().into_iter().flatten() {
+                if let Some(f) = m["image_filename"].as_str().filter(|f| !f.is_empty()) {
+                    filenames.insert(f.to_string());
+                }
+            }
+        }
+    }
+
+    for entry in LUCKSACK_MONSTER_MAP.values() {
+        filenames.insert(entry.image.clone());
+        if let Some(collab) = &entry.collab_image {
+            filenames.insert(collab.clone());
+        }
+    }
+
+    filenames.retain(|f| is_safe_filename(f));
+    filenames
+}
+
+fn portrait_path(filename: &str) -> PathBuf {
+    Path::new(PORTRAIT_DIR).join(filename)
+}
+
+fn is_safe_filename(filename: &str) -> bool {
+    !filename.is_empty()
+        && !filename.contains(['/', '\\'])
+        && !filename.starts_with('.')
+        && filename.ends_with(".png")
+}
+
+async fn read_portrait_from_disk(filename: &str) -> Result<DynamicImage> {
+    if !is_safe_filename(filename) {
+        return Err(anyhow!("Invalid portrait filename: {}", filename));
+    }
+
+    let path = portrait_path(filename);
+    let filename = filename.to_string();
+
+    tokio::task::spawn_blocking(move || -> Result<DynamicImage> {
+        let data = std::fs::read(&path)
+            .with_context(|| format!("Failed to read image file: {}", filename))?;
+        let img = image::load_from_memory(&data)
+            .with_context(|| format!("Failed to decode image: {}", filename))?;
+        Ok(img.resize_exact(
+            PORTRAIT_SIZE,
+            PORTRAIT_SIZE,
+            image::imageops::FilterType::Triangle,
+        ))
+    })
+    .await
+    .map_err(|e| anyhow!("Blocking task failed: {}", e))?
+}
+
+fn schedule_download(filename: &str) {
+    if !is_safe_filename(filename) {
+        return;
+    }
+
+    {
+        let Ok(mut requested) = REQUESTED_DOWNLOADS.lock() else {
+            return;
+        };
+        if !requested.insert(filename.to_string()) {
+            return;
+        }
+    }
+
+    let filename = filename.to_string();
+    tokio::spawn(async move {
+        if let Err(e) = download_portrait(&filename).await {
+            eprintln!("Portrait download failed for {filename}: {e:?}");
+        }
+    });
+}
+
+async fn download_portrait(filename: &str) -> Result<()> {
+    let url = format!("{}{}", PORTRAIT_BASE_URL, filename);
+    let bytes = http_client()
+        .get(&url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to download image from: {}", url))?
+        .error_for_status()
+        .with_context(|| format!("Image not available: {}", url))?
+        .bytes()
+        .await
+        .with_context(|| format!("Failed to read downloaded bytes for file: {}", filename))?;
+
+    // On ne garde sur disque que des images valides
+    image::load_from_memory(&bytes)
+        .with_context(|| format!("Failed to decode downloaded image: {}", filename))?;
+
+    tokio::fs::create_dir_all(PORTRAIT_DIR).await?;
+    tokio::fs::write(portrait_path(filename), &bytes)
+        .await
+        .with_context(|| format!("Failed to write portrait: {}", filename))?;
+
+    Ok(())
+}
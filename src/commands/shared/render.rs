@@ -0,0 +1,600 @@
+use ab_glyph::{Font, FontArc, PxScale, ScaleFont};
+use anyhow::{anyhow, Result};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::GenericImage;
+use image::{Delay, Frame, ImageBuffer, Rgba, RgbaImage};
+use imageproc::drawing::draw_text_mut;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::commands::shared::portraits::get_portrait;
+
+/* ------------------ Layout & inputs ------------------ */
+
+/// Options controlling how a replay grid is drawn.
+#[derive(Debug, Clone)]
+pub struct ReplayLayout {
+    /// Number of match sections per row.
+    pub cols: u32,
+    /// Fixed number of rows; `None` sizes the canvas to the sections rendered.
+    pub rows: Option<u32>,
+    /// Draws the "left - date - right" banner above each match.
+    pub show_banner: bool,
+    /// Staggers the 5 monsters following the draft order (1-2-2 / 2-2-1).
+    pub pick_order: bool,
+    /// Overlays a cross on the banned monster.
+    pub ban_cross: bool,
+    /// Draws a golden border around the leader.
+    pub leader_highlight: bool,
+}
+
+impl Default for ReplayLayout {
+    fn default() -> Self {
+        Self {
+            cols: 2,
+            rows: None,
+            show_banner: true,
+            pick_order: true,
+            ban_cross: true,
+            leader_highlight: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchOutcome {
+    Win,
+    Loss,
+    Unknown,
+}
+
+impl MatchOutcome {
+    fn border_color(self) -> Rgba<u8> {
+        match self {
+            MatchOutcome::Win => Rgba([0, 255, 0, 100]),
+            MatchOutcome::Loss => Rgba([255, 0, 0, 100]),
+            MatchOutcome::Unknown => Rgba([0, 0, 0, 100]),
+        }
+    }
+}
+
+/// One side of a draft, monsters listed in pick order.
+#[derive(Debug, Clone)]
+pub struct TeamDraft {
+    pub image_filenames: Vec<String>,
+    pub monster_ids: Vec<u32>,
+    pub ban_id: u32,
+    pub leader_id: u32,
+    pub first_pick: bool,
+}
+
+/// A full match: left team is always the "point of view" player.
+#[derive(Debug, Clone)]
+pub struct MatchSection {
+    pub left: TeamDraft,
+    pub right: TeamDraft,
+    pub left_text: String,
+    pub center_text: String,
+    pub right_text: String,
+    pub outcome: MatchOutcome,
+}
+
+/* ------------------ Static resources ------------------ */
+
+static CROSS_IMAGE_100: OnceLock<RgbaImage> = OnceLock::new();
+static BANNER_FONT: OnceLock<FontArc> = OnceLock::new();
+
+fn cross_image_100() -> &'static RgbaImage {
+    CROSS_IMAGE_100.get_or_init(|| {
+        const CROSS_BYTES: &[u8] = include_bytes!("../player_stats/cross.png");
+        image::load_from_memory(CROSS_BYTES)
+            .expect("Erreur lors du chargement de cross.png")
+            .resize_exact(100, 100, image::imageops::FilterType::Triangle)
+            .to_rgba8()
+    })
+}
+
+pub fn banner_font() -> &'static FontArc {
+    BANNER_FONT.get_or_init(|| {
+        const FONT_BYTES: &[u8] = include_bytes!("../player_stats/NotoSansCJK-Regular.otf");
+        FontArc::try_from_vec(FONT_BYTES.to_vec()).expect("Police invalide")
+    })
+}
+
+/* ------------------ Replay grid ------------------ */
+
+/// Renders every match as a bordered section and lays them out on a grid.
+pub async fn render_replay_grid(
+    matches: &[MatchSection],
+    layout: &ReplayLayout,
+) -> Result<RgbaImage> {
+    let mut sections: Vec<RgbaImage> = Vec::new();
+
+    for m in matches {
+        sections.push(render_match_section(m, layout).await?);
+    }
+
+    if sections.is_empty() && layout.rows.is_none() {
+        return Err(anyhow!("No valid matches to render"));
+    }
+
+    let cols = layout.cols.max(1);
+    let rows = layout
+        .rows
+        .unwrap_or_else(|| (sections.len() as u32).div_ceil(cols))
+        .max(1);
+    let padding = 10u32;
+    let sw = sections.first().map(|img| img.width()).unwrap_or(0);
+    let sh = sections.first().map(|img| img.height()).unwrap_or(0);
+    let full_w = cols * sw + (cols - 1) * padding;
+    let full_h = rows * sh + (rows - 1) * padding;
+
+    let mut canvas = ImageBuffer::new(full_w, full_h);
+    for (i, section) in sections.iter().enumerate().take((cols * rows) as usize) {
+        let col = (i as u32) % cols;
+        let row = (i as u32) / cols;
+        canvas.copy_from(section, col * (sw + padding), row * (sh + padding))?;
+    }
+
+    Ok(canvas)
+}
+
+/// Renders every match as its own bordered section, one frame per match.
+pub async fn render_replay_frames(
+    matches: &[MatchSection],
+    layout: &ReplayLayout,
+) -> Result<Vec<RgbaImage>> {
+    let mut frames = Vec::with_capacity(matches.len());
+    for m in matches {
+        frames.push(render_match_section(m, layout).await?);
+    }
+
+    if frames.is_empty() {
+        return Err(anyhow!("No valid matches to render"));
+    }
+
+    Ok(frames)
+}
+
+/// Renders a single match (banner + both teams) inside its win/loss border.
+pub async fn render_match_section(m: &MatchSection, layout: &ReplayLayout) -> Result<RgbaImage> {
+    let img_left = create_team_collage(&m.left, layout).await?;
+    let img_right = create_team_collage(&m.right, layout).await?;
+
+    let image_width = img_left.width() / 3;
+    let spacing = image_width / 2;
+    let combined_width = img_left.width() + img_right.width() + spacing;
+    let height = img_left.height().max(img_right.height());
+
+    let mut combined = ImageBuffer::new(combined_width, height);
+    combined.copy_from(&img_left, 0, 0)?;
+    combined.copy_from(&img_right, img_left.width() + spacing, 0)?;
+
+    let banner = if layout.show_banner {
+        Some(create_match_banner(
+            &m.left_text,
+            &m.center_text,
+            &m.right_text,
+            combined_width,
+            Rgba([0, 0, 0, 0]),
+        ))
+    } else {
+        None
+    };
+    let banner_height = banner.as_ref().map(|b| b.height()).unwrap_or(0);
+
+    let border = 10u32;
+    let inner_w = combined_width;
+    let inner_h = banner_height + combined.height();
+
+    let mut section = ImageBuffer::from_pixel(
+        inner_w + 2 * border,
+        inner_h + 2 * border,
+        m.outcome.border_color(),
+    );
+    let mut inner = ImageBuffer::from_pixel(inner_w, inner_h, Rgba([0, 0, 0, 0]));
+    if let Some(banner) = &banner {
+        inner.copy_from(banner, 0, 0)?;
+    }
+    inner.copy_from(&combined, 0, banner_height)?;
+    section.copy_from(&inner, border, border)?;
+
+    Ok(section)
+}
+
+/// Builds the 3x2 collage of one team's 5 picks.
+pub async fn create_team_collage(team: &TeamDraft, layout: &ReplayLayout) -> Result<RgbaImage> {
+    let mut images = Vec::new();
+    for filename in &team.image_filenames {
+        images.push(get_portrait(filename).await);
+    }
+
+    let first = images
+        .first()
+        .ok_or_else(|| anyhow!("Cannot build a collage without monsters"))?;
+    let width = first.width();
+    let height = first.height();
+    let mut collage = ImageBuffer::new(width * 3, height * 2);
+
+    let cross = if width == 100 && height == 100 {
+        cross_image_100().clone()
+    } else {
+        image::imageops::resize(
+            cross_image_100(),
+            width,
+            height,
+            image::imageops::FilterType::Triangle,
+        )
+    };
+
+    let grid_slots = team_grid_slots(team.first_pick, layout.pick_order);
+
+    for (i, (img, &monster_id)) in images
+        .iter()
+        .zip(&team.monster_ids)
+        .enumerate()
+        .take(grid_slots.len())
+    {
+        let mut rgba = img.to_rgba8();
+
+        if layout.leader_highlight && monster_id == team.leader_id {
+            draw_border(&mut rgba, Rgba([255, 215, 0, 255]), 5);
+        }
+
+        if layout.ban_cross && monster_id == team.ban_id {
+            image::imageops::overlay(&mut rgba, &cross, 0, 0);
+        }
+
+        let (grid_x, grid_y) = grid_slots[i];
+        let x = grid_x * width;
+        let centered =
+            layout.pick_order && ((team.first_pick && i == 0) || (!team.first_pick && i == 4));
+        let y = if centered {
+            (collage.height() - height) / 2
+        } else {
+            grid_y * height
+        };
+
+        collage.copy_from(&rgba, x, y)?;
+    }
+
+    Ok(collage)
+}
+
+/// Column/row of each pick. With `pick_order`, the first picker's opening
+/// monster (or the second picker's last one) sits alone, vertically centered.
+fn team_grid_slots(first_pick: bool, pick_order: bool) -> [(u32, u32); 5] {
+    match (pick_order, first_pick) {
+        (true, true) => [(0, 1), (1, 0), (1, 1), (2, 0), (2, 1)],
+        (true, false) => [(0, 0), (0, 1), (1, 0), (1, 1), (2, 1)],
+        (false, _) => [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1)],
+    }
+}
+
+fn draw_border(rgba: &mut RgbaImage, color: Rgba<u8>, thickness: u32) {
+    let (width, height) = rgba.dimensions();
+    let thickness = thickness.min(width / 2).min(height / 2);
+    for x in 0..width {
+        for t in 0..thickness {
+            rgba.put_pixel(x, t, color);
+            rgba.put_pixel(x, height - 1 - t, color);
+        }
+    }
+    for y in 0..height {
+        for t in 0..thickness {
+            rgba.put_pixel(t, y, color);
+            rgba.put_pixel(width - 1 - t, y, color);
+        }
+    }
+}
+
+/// Encodes the image as PNG in memory from a blocking task.
+pub async fn encode_png(image: RgbaImage) -> Result<Vec<u8>> {
+    tokio::task::spawn_blocking(move || {
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut bytes, image::ImageFormat::Png)?;
+        Ok::<_, anyhow::Error>(bytes.into_inner())
+    })
+    .await?
+}
+
+/// Writes the image as PNG from a blocking task.
+pub async fn save_png(image: RgbaImage, output_path: PathBuf) -> Result<PathBuf> {
+    tokio::task::spawn_blocking(move || {
+        std::fs::create_dir_all("/tmp")?;
+        image.save(&output_path)?;
+        Ok::<_, anyhow::Error>(output_path)
+    })
+    .await?
+}
+
+/// Writes the frames as an endlessly looping GIF. Frames of different sizes
+/// are anchored top-left on a canvas sized to the largest one.
+pub async fn save_gif(
+    frames: Vec<RgbaImage>,
+    output_path: PathBuf,
+    frame_delay_ms: u32,
+) -> Result<PathBuf> {
+    tokio::task::spawn_blocking(move || {
+        let width = frames.iter().map(|f| f.width()).max().unwrap_or(0);
+        let height = frames.iter().map(|f| f.height()).max().unwrap_or(0);
+        if width == 0 || height == 0 {
+            return Err(anyhow!("No frames to encode"));
+        }
+
+        std::fs::create_dir_all("/tmp")?;
+        let file = std::fs::File::create(&output_path)?;
+        let mut encoder = GifEncoder::new_with_speed(std::io::BufWriter::new(file), 10);
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        for frame in frames {
+            let mut canvas = ImageBuffer::from_pixel(width, height, Rgba([0, 0, 0, 255]));
+            image::imageops::overlay(&mut canvas, &frame, 0, 0);
+            encoder.encode_frame(Frame::from_parts(
+                canvas,
+                0,
+                0,
+                Delay::from_numer_denom_ms(frame_delay_ms, 1),
+            ))?;
+        }
+
+        Ok::<_, anyhow::Error>(output_path)
+    })
+    .await?
+}
+
+/// Keeps at most `max_files` generated replay images (`/tmp/replay-*.png|gif`),
+/// removing the oldest first.
+pub fn prune_tmp_replay_files(max_files: usize) {
+    let Ok(entries) = std::fs::read_dir("/tmp") else {
+        return;
+    };
+
+    let mut files: Vec<(PathBuf, std::time::SystemTime)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_name().and_then(|n| n.to_str())?;
+
+            if !(name.starts_with("replay-") && (name.ends_with(".png") || name.ends_with(".gif")))
+            {
+                return None;
+            }
+
+            let modified = entry
+                .metadata()
+                .ok()
+                .and_then(|m| m.modified().ok())
+                .unwrap_or(std::time::UNIX_EPOCH);
+
+            Some((path, modified))
+        })
+        .collect();
+
+    let overflow = files.len().saturating_sub(max_files);
+    if overflow == 0 {
+        return;
+    }
+
+    // Remove oldest files first to keep most recent replay images.
+    files.sort_by_key(|(_, modified)| *modified);
+    for (path, _) in files.into_iter().take(overflow) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/* ------------------ Text helpers ------------------ */
+
+pub fn create_match_banner(
+    left_text: &str,
+    center_text: &str,
+    right_text: &str,
+    width: u32,
+    color: Rgba<u8>,
+) -> RgbaImage {
+    let height = 40;
+    let mut image = ImageBuffer::from_pixel(width, height, color);
+
+    let font = banner_font();
+
+    let scale = PxScale::from(26.0);
+    let margin = 8.0_f32;
+    let width_f = width as f32;
+    let max_width = width_f / 3.0 - margin * 2.0;
+
+    let left = fit_text_to_width(font, scale, left_text, max_width);
+    let center = fit_text_to_width(font, scale, center_text, max_width);
+    let right = fit_text_to_width(font, scale, right_text, max_width);
+
+    let center_w = text_width(font, scale, &center);
+    let right_w = text_width(font, scale, &right);
+
+    let y = 8;
+    let white = Rgba([255, 255, 255, 255]);
+
+    draw_bold_text_mut(&mut image, white, margin as i32, y, scale, font, &left);
+
+    let center_x: i32 = ((width_f - center_w) / 2.0).round() as i32;
+    draw_bold_text_mut(&mut image, white, center_x, y, scale, font, &center);
+
+    let right_x: i32 = (width_f - right_w - margin).round() as i32;
+    draw_bold_text_mut(&mut image, white, right_x, y, scale, font, &right);
+
+    image
+}
+
+pub fn draw_bold_text_mut(
+    image: &mut RgbaImage,
+    color: Rgba<u8>,
+    x: i32,
+    y: i32,
+    scale: PxScale,
+    font: &FontArc,
+    text: &str,
+) {
+    let offsets = [(0, 0), (1, 0), (0, 1), (1, 1)];
+    for (dx, dy) in offsets {
+        draw_text_mut(image, color, x + dx, y + dy, scale, font, text);
+    }
+}
+
+pub fn text_width(font: &FontArc, scale: PxScale, text: &str) -> f32 {
+    let scaled = font.as_scaled(scale);
+    let mut pen = 0.0f32;
+    let mut prev_id = None;
+
+    for ch in text.chars() {
+        let g = scaled.glyph_id(ch);
+        if let Some(pid) = prev_id {
+            pen += scaled.kern(pid, g);
+        }
+        pen += scaled.h_advance(g);
+        prev_id = Some(g);
+    }
+    pen
+}
+
+pub fn fit_text_to_width(font: &FontArc, scale: PxScale, text: &str, max_width: f32) -> String {
+    if text_width(font, scale, text) <= max_width {
+        return text.to_string();
+    }
+
+    let mut chars: Vec<char> = text.chars().collect();
+    while !chars.is_empty()
+        && text_width(font, scale, &chars.iter().collect::<String>()) > max_width
+    {
+        chars.pop();
+    }
+
+    let mut result: String = chars.iter().collect();
+    if !result.is_empty() {
+        result.push('…');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::shared::portraits::{preload_portrait, PORTRAIT_SIZE};
+    use image::DynamicImage;
+
+    const GOLDEN_GRID: &str = "src/commands/shared/fixtures/replay_grid.png";
+
+    /// Tuiles unies, une couleur par monstre : le rendu ne dépend ni du disque ni de la police
+    async fn preload_test_portraits() {
+        for i in 0..10u8 {
+            let tile = ImageBuffer::from_pixel(
+                PORTRAIT_SIZE,
+                PORTRAIT_SIZE,
+                Rgba([20 * i, 200 - 15 * i, 40 + 20 * i, 255]),
+            );
+            preload_portrait(&format!("test_{i}.png"), DynamicImage::ImageRgba8(tile)).await;
+        }
+    }
+
+    fn team(first: u32, first_pick: bool) -> TeamDraft {
+        TeamDraft {
+            image_filenames: (first..first + 5)
+                .map(|i| format!("test_{i}.png"))
+                .collect(),
+            monster_ids: (first..first + 5).collect(),
+            ban_id: first + 2,
+            leader_id: first,
+            first_pick,
+        }
+    }
+
+    fn test_matches() -> Vec<MatchSection> {
+        [MatchOutcome::Win, MatchOutcome::Loss, MatchOutcome::Unknown]
+            .into_iter()
+            .enumerate()
+            .map(|(i, outcome)| MatchSection {
+                left: team(0, i % 2 == 0),
+                right: team(5, i % 2 == 1),
+                left_text: "Left".to_string(),
+                center_text: "2024-01-01".to_string(),
+                right_text: "Right".to_string(),
+                outcome,
+            })
+            .collect()
+    }
+
+    /// Sans bannière : pas de texte, donc un rendu identique quelle que soit la police
+    fn layout_without_banner() -> ReplayLayout {
+        ReplayLayout {
+            show_banner: false,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn replay_grid_matches_golden_image() {
+        preload_test_portraits().await;
+        let grid = render_replay_grid(&test_matches(), &layout_without_banner())
+            .await
+            .unwrap();
+
+        // UPDATE_GOLDEN=1 cargo test régénère l'image de référence
+        if std::env::var("UPDATE_GOLDEN").is_ok() {
+            grid.save(GOLDEN_GRID).unwrap();
+        }
+
+        let golden = image::open(GOLDEN_GRID)
+            .expect("missing golden image, run with UPDATE_GOLDEN=1")
+            .to_rgba8();
+        assert_eq!(grid.dimensions(), golden.dimensions());
+        assert!(
+            grid.as_raw() == golden.as_raw(),
+            "replay grid differs from {GOLDEN_GRID}"
+        );
+    }
+
+    #[tokio::test]
+    async fn match_section_layout() {
+        preload_test_portraits().await;
+        let m = &test_matches()[0];
+
+        let without_banner = render_match_section(m, &layout_without_banner())
+            .await
+            .unwrap();
+        // 2 collages 3x2 + un demi-portrait d'espace, bordure de 10 px
+        let size = PORTRAIT_SIZE;
+        assert_eq!(
+            without_banner.dimensions(),
+            (6 * size + size / 2 + 20, 2 * size + 20)
+        );
+        assert_eq!(
+            *without_banner.get_pixel(0, 0),
+            MatchOutcome::Win.border_color()
+        );
+
+        let with_banner = render_match_section(m, &ReplayLayout::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            with_banner.dimensions(),
+            (without_banner.width(), without_banner.height() + 40)
+        );
+    }
+
+    #[tokio::test]
+    async fn grid_keeps_fixed_rows() {
+        preload_test_portraits().await;
+        let layout = ReplayLayout {
+            cols: 2,
+            rows: Some(3),
+            ..layout_without_banner()
+        };
+        let section = render_match_section(&test_matches()[0], &layout)
+            .await
+            .unwrap();
+        let grid = render_replay_grid(&test_matches()[..1], &layout)
+            .await
+            .unwrap();
+        assert_eq!(
+            grid.dimensions(),
+            (2 * section.width() + 10, 3 * section.height() + 20)
+        );
+    }
+}
@@ -1,13 +1,12 @@
+use crate::commands::shared::game_data::{RuneSet, Stat};
 use crate::commands::upload_json::rune::Property;
 use crate::commands::upload_json::rune::Rune;
-use crate::commands::upload_json::utils::{
-    get_rune_set_id_by_id, get_rune_stat_id_by_id, get_stars_ammount_by_id,
-};
+use crate::commands::upload_json::utils::get_stars_ammount_by_id;
 use serde_json::Value;
 use std::collections::HashMap;
 
 /// Fonction qui extrait une rune à partir d'un objet JSON
-fn extract_rune(rune: &Value) -> Option<Rune> {
+pub fn extract_rune(rune: &Value) -> Option<Rune> {
     let class = rune.get("class")?.as_u64()? as u32;
     if class < 5 {
         return None;
@@ -17,7 +16,7 @@ fn extract_rune(rune: &Value) -> Option<Rune> {
     let slot_location = rune.get("slot_no")?.as_u64()? as u32;
     let class_enum = get_stars_ammount_by_id(class);
     let antic = class / 10 == 1;
-    let set_id = get_rune_set_id_by_id(rune.get("set_id")?.as_u64()? as u32);
+    let set_id = RuneSet::from_game_id(rune.get("set_id")?.as_u64()? as u32)?;
     let upgrade_limit = rune.get("upgrade_limit")?.as_u64()? as u32;
     let upgrade_current = rune.get("upgrade_curr")?.as_u64()? as u32;
     if upgrade_current < 12 {
@@ -26,7 +25,7 @@ fn extract_rune(rune: &Value) -> Option<Rune> {
 
     let primary_property = if let Some(pri_eff) = rune.get("pri_eff") {
         let pri_eff_array = pri_eff.as_array()?;
-        let stat_id = get_rune_stat_id_by_id(pri_eff_array[0].as_u64()? as u32);
+        let stat_id = Stat::from_id(pri_eff_array[0].as_u64()? as u32)?;
         let value = pri_eff_array[1].as_f64()? as f32;
         Property::new(stat_id, value, None, None)
     } else {
@@ -35,7 +34,7 @@ fn extract_rune(rune: &Value) -> Option<Rune> {
 
     let innate_property = if let Some(prefix_eff) = rune.get("prefix_eff") {
         let prefix_eff_array = prefix_eff.as_array()?;
-        let stat_id = get_rune_stat_id_by_id(prefix_eff_array[0].as_u64()? as u32);
+        let stat_id = Stat::from_id(prefix_eff_array[0].as_u64()? as u32)?;
         let value = prefix_eff_array[1].as_f64()? as f32;
         Property::new(stat_id, value, None, None)
     } else {
@@ -47,7 +46,7 @@ fn extract_rune(rune: &Value) -> Option<Rune> {
         let sec_eff_array = sec_eff.as_array()?;
         for sec_eff in sec_eff_array {
             let sec_eff_array = sec_eff.as_array()?;
-            let stat_id = get_rune_stat_id_by_id(sec_eff_array[0].as_u64()? as u32);
+            let stat_id = Stat::from_id(sec_eff_array[0].as_u64()? as u32)?;
             let value = sec_eff_array[1].as_f64()? as f32;
             let has_been_replaced = sec_eff_array[2].as_u64()? == 1;
             let boost_value = sec_eff_array[3].as_u64()? as f32;
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use std::fmt;
+
+use crate::commands::shared::game_data::{RuneSet, Stat};
 
 use crate::commands::upload_json::utils::{
     calculate_eff_stat_6, get_main_stat_max_value_by_id_5, get_main_stat_max_value_by_id_6,
@@ -11,7 +12,7 @@ pub struct Rune {
     slot_location: u32,
     class: StarsAmmount,
     antic: bool,
-    pub set_id: RuneSetId,
+    pub set_id: RuneSet,
     upgrade_limit: u32,
     upgrade_current: u32,
     primary_property: Property,
@@ -23,7 +24,7 @@ pub struct Rune {
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Property {
-    pub id: RuneStatId,
+    pub id: Stat,
     pub value: f32,
     has_been_replaced: Option<bool>,
     pub boost_value: Option<f32>,
@@ -35,62 +36,12 @@ pub enum StarsAmmount {
     Six,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum RuneSetId {
-    Energy,
-    Guard,
-    Swift,
-    Blade,
-    Rage,
-    Focus,
-    Endure,
-    Fatal,
-    Despair,
-    Vampire,
-    Violent,
-    Nemesis,
-    Will,
-    Shield,
-    Revenge,
-    Destroy,
-    Fight,
-    Determination,
-    Enhance,
-    Accuracy,
-    Tolerance,
-    Seal,
-    Intangible,
-}
-
-impl fmt::Display for RuneSetId {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
-#[derive(Serialize, Deserialize, Default, Debug, Copy, Clone, PartialEq)]
-pub enum RuneStatId {
-    #[default]
-    None,
-    Hp,
-    HpPct,
-    Atk,
-    AtkPtc,
-    Def,
-    DefPtc,
-    Spd,
-    CritRatePct,
-    CritDmgPct,
-    ResistPct,
-    AccuracyPct,
-}
-
 pub struct RuneInput {
     pub id: u32,
     pub slot_location: u32,
     pub class: StarsAmmount,
     pub antic: bool,
-    pub set_id: RuneSetId,
+    pub set_id: RuneSet,
     pub upgrade_limit: u32,
     pub upgrade_current: u32,
     pub primary_property: Property,
@@ -132,7 +83,7 @@ impl Rune {
                     / get_main_stat_max_value_by_id_6(self.primary_property.id);
 
                 // Innate stat efficiency
-                if self.innate_property.id != RuneStatId::None {
+                if self.innate_property.id != Stat::None {
                     eff_innate = calculate_eff_stat_6(&self.innate_property);
                 }
 
@@ -152,7 +103,7 @@ impl Rune {
                     / get_main_stat_max_value_by_id_6(self.primary_property.id);
 
                 // Innate stat efficiency
-                if self.innate_property.id != RuneStatId::None {
+                if self.innate_property.id != Stat::None {
                     eff_innate = calculate_eff_stat_6(&self.innate_property);
                 }
 
@@ -169,7 +120,7 @@ impl Rune {
     fn get_speed_value(&self) -> Option<u32> {
         let mut speed_value = 0;
         for stat in self.secondary_properties.iter() {
-            if stat.id == RuneStatId::Spd {
+            if stat.id == Stat::Spd {
                 speed_value += stat.value as u32;
                 if let Some(boost_value) = stat.boost_value {
                     speed_value += boost_value as u32;
@@ -182,7 +133,7 @@ impl Rune {
 
 impl Property {
     pub fn new(
-        id: RuneStatId,
+        id: Stat,
         value: f32,
         has_been_replaced: Option<bool>,
         boost_value: Option<f32>,
@@ -0,0 +1,262 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::Error;
+
+use crate::commands::player_stats::command::resolve_player_id;
+use crate::commands::player_stats::utils::{
+    get_lucksack_player_summary, get_lucksack_season_numbers,
+};
+use crate::commands::shared::embed_error_handling::{
+    create_embed_error, schedule_message_deletion,
+};
+use crate::commands::shared::logs::{get_server_name, send_log};
+use crate::commands::shared::models::LoggerDocument;
+use crate::commands::watch::models::WatchEntry;
+use crate::commands::watch::utils::{
+    add_watch_entry, create_watchlist_embed, get_watch_channel, list_watch_entries,
+    remove_watch_entry, set_watch_channel, MAX_WATCHED_PLAYERS,
+};
+use crate::Data;
+
+fn db_error(e: anyhow::Error) -> Error {
+    Error::from(std::io::Error::other(format!("DB error: {e}")))
+}
+
+async fn log_watch(
+    ctx: &poise::ApplicationContext<'_, Data, Error>,
+    subcommand: &str,
+    success: bool,
+) -> Result<(), Error> {
+    send_log(LoggerDocument::new(
+        &ctx.author().name,
+        &format!("watch {}", subcommand),
+        &get_server_name(ctx).await?,
+        success,
+        chrono::Utc::now().timestamp(),
+    ))
+    .await
+}
+
+/// Autocomplete sur les joueurs suivis par le serveur
+async fn autocomplete_watched_player<'a>(
+    ctx: poise::ApplicationContext<'a, Data, Error>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    let lower = partial.to_lowercase();
+    let entries = match ctx.guild_id() {
+        Some(guild_id) => list_watch_entries(guild_id.get() as i64)
+            .await
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    entries
+        .into_iter()
+        .map(|e| e.player_name)
+        .filter(move |name| name.to_lowercase().contains(&lower))
+        .take(25)
+}
+
+/// 📂 Manage the server watchlist (rank, score and top 100 notifications)
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD",
+    subcommands("add", "remove", "list", "channel"),
+    subcommand_required
+)]
+pub async fn watch(_ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Add a player to the watchlist
+#[poise::command(slash_command, guild_only)]
+pub async fn add(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Player name, alias or @mention"] player_name: String,
+    #[description = "Notify when the score crosses this value"] score_threshold: Option<i32>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let result: Result<(), Error> = async {
+        let guild_id = ctx.guild_id().map(|g| g.get() as i64).unwrap_or_default();
+
+        let entries = list_watch_entries(guild_id).await.map_err(db_error)?;
+        if entries.len() >= MAX_WATCHED_PLAYERS {
+            let reply = ctx
+                .send(create_embed_error(&format!(
+                    "❌ The watchlist is full ({} players). Remove someone first.",
+                    MAX_WATCHED_PLAYERS
+                )))
+                .await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(());
+        }
+
+        let Some(resolved) = resolve_player_id(&ctx, &player_name, "watch add").await? else {
+            return Ok(());
+        };
+
+        let season = get_lucksack_season_numbers()
+            .await
+            .ok()
+            .and_then(|s| s.first().copied())
+            .ok_or_else(|| Error::from(std::io::Error::other("No valid season number found")))?;
+
+        let summary = get_lucksack_player_summary(resolved.player_id, season)
+            .await
+            .map_err(|e| {
+                Error::from(std::io::Error::other(format!(
+                    "Error retrieving player summary: {}",
+                    e
+                )))
+            })?;
+
+        // L'état actuel sert de référence pour le premier relevé
+        let added = add_watch_entry(WatchEntry {
+            guild_id,
+            player_id: resolved.player_id,
+            player_name: summary.user_info.username.clone(),
+            score_threshold,
+            added_by: ctx.author().name.clone(),
+            added_at: chrono::Utc::now().timestamp(),
+            last_score: Some(summary.summary.current_score),
+            last_rank: Some(summary.summary.current_rank),
+            last_bracket: Some(summary.summary.current_rank_bracket),
+        })
+        .await
+        .map_err(db_error)?;
+
+        let content = if added {
+            let mut msg = format!(
+                "👀 **{}** added to the watchlist.",
+                summary.user_info.username
+            );
+            if get_watch_channel(guild_id)
+                .await
+                .map_err(db_error)?
+                .is_none()
+            {
+                msg.push_str("\n⚠️ No notification channel yet, set one with `/watch channel`.");
+            }
+            msg
+        } else {
+            format!(
+                "ℹ️ **{}** is already in the watchlist.",
+                summary.user_info.username
+            )
+        };
+
+        let reply = CreateReply {
+            content: Some(content),
+            embeds: vec![],
+            components: Some(vec![]),
+            ..Default::default()
+        };
+        match resolved.reply_handle {
+            Some(handle) => handle.edit(poise::Context::Application(ctx), reply).await?,
+            None => {
+                ctx.send(reply).await?;
+            }
+        }
+
+        Ok(())
+    }
+    .await;
+
+    log_watch(&ctx, "add", result.is_ok()).await?;
+    result
+}
+
+/// Remove a player from the watchlist
+#[poise::command(slash_command, guild_only)]
+pub async fn remove(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Watched player"]
+    #[autocomplete = "autocomplete_watched_player"]
+    player_name: String,
+) -> Result<(), Error> {
+    let result: Result<(), Error> = async {
+        let guild_id = ctx.guild_id().map(|g| g.get() as i64).unwrap_or_default();
+        let entries = list_watch_entries(guild_id).await.map_err(db_error)?;
+
+        let Some(entry) = entries
+            .iter()
+            .find(|e| e.player_name.eq_ignore_ascii_case(&player_name))
+        else {
+            let reply = ctx
+                .send(create_embed_error(&format!(
+                    "❌ `{}` is not in the watchlist.",
+                    player_name
+                )))
+                .await?;
+            schedule_message_deletion(reply, ctx).await?;
+            return Ok(());
+        };
+
+        remove_watch_entry(guild_id, entry.player_id)
+            .await
+            .map_err(db_error)?;
+        ctx.say(format!(
+            "🗑️ **{}** removed from the watchlist.",
+            entry.player_name
+        ))
+        .await?;
+
+        Ok(())
+    }
+    .await;
+
+    log_watch(&ctx, "remove", result.is_ok()).await?;
+    result
+}
+
+/// Show the watched players
+#[poise::command(slash_command, guild_only)]
+pub async fn list(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    let result: Result<(), Error> = async {
+        let guild_id = ctx.guild_id().map(|g| g.get() as i64).unwrap_or_default();
+        let entries = list_watch_entries(guild_id).await.map_err(db_error)?;
+        let channel_id = get_watch_channel(guild_id).await.map_err(db_error)?;
+
+        ctx.send(CreateReply {
+            embeds: vec![create_watchlist_embed(&entries, channel_id)],
+            ..Default::default()
+        })
+        .await?;
+
+        Ok(())
+    }
+    .await;
+
+    log_watch(&ctx, "list", result.is_ok()).await?;
+    result
+}
+
+/// Set the channel receiving watchlist notifications
+#[poise::command(slash_command, guild_only)]
+pub async fn channel(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+    #[description = "Notification channel"]
+    #[channel_types("Text")]
+    channel: serenity::GuildChannel,
+) -> Result<(), Error> {
+    let result: Result<(), Error> = async {
+        let guild_id = ctx.guild_id().map(|g| g.get() as i64).unwrap_or_default();
+        set_watch_channel(guild_id, channel.id.get() as i64)
+            .await
+            .map_err(db_error)?;
+
+        ctx.say(format!(
+            "📣 Watchlist notifications will be posted in <#{}>.",
+            channel.id
+        ))
+        .await?;
+
+        Ok(())
+    }
+    .await;
+
+    log_watch(&ctx, "channel", result.is_ok()).await?;
+    result
+}
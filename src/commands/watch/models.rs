@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Joueur suivi par un serveur (collection `watchlist`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchEntry {
+    pub guild_id: i64,
+    pub player_id: i64,
+    pub player_name: String,
+    /// Notifie quand le score passe au-dessus / en dessous de ce seuil
+    pub score_threshold: Option<i32>,
+    pub added_by: String,
+    pub added_at: i64,
+    /// Dernier état connu, `None` tant que le joueur n'a pas été relevé
+    pub last_score: Option<i32>,
+    pub last_rank: Option<i64>,
+    pub last_bracket: Option<i32>,
+}
+
+/// Salon de notification d'un serveur (collection `watch-channels`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchChannel {
+    pub guild_id: i64,
+    pub channel_id: i64,
+}
+
+/// Changement détecté entre deux relevés
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchEvent {
+    BracketChanged { from: i32, to: i32 },
+    ThresholdCrossed { threshold: i32, upward: bool },
+    EnteredTop100 { rank: i64 },
+}
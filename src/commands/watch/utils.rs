@@ -0,0 +1,314 @@
+use anyhow::Result;
+use futures::stream::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::Collection;
+use poise::serenity_prelude as serenity;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter, CreateMessage};
+use serenity::Context as SerenityContext;
+use std::collections::HashMap;
+
+use crate::commands::player_stats::utils::{
+    get_lucksack_player_summary, get_lucksack_season_numbers, get_rank_emojis_for_bracket,
+    LucksackPlayerSummary,
+};
+use crate::commands::shared::clients::mongo_client;
+use crate::commands::watch::models::{WatchChannel, WatchEntry, WatchEvent};
+
+/// Nombre max de joueurs suivis par serveur
+pub const MAX_WATCHED_PLAYERS: usize = 25;
+
+/// Délai entre deux appels lucksack pendant le relevé
+const POLL_DELAY_MS: u64 = 1500;
+
+fn watchlist_collection() -> Result<Collection<WatchEntry>> {
+    Ok(mongo_client()?
+        .database("bot-swbox-db")
+        .collection::<WatchEntry>("watchlist"))
+}
+
+fn watch_channels_collection() -> Result<Collection<WatchChannel>> {
+    Ok(mongo_client()?
+        .database("bot-swbox-db")
+        .collection::<WatchChannel>("watch-channels"))
+}
+
+/* ------------------ Watchlist storage ------------------ */
+
+pub async fn list_watch_entries(guild_id: i64) -> Result<Vec<WatchEntry>> {
+    let col = watchlist_collection()?;
+    let entries: Vec<WatchEntry> = col
+        .find(doc! { "guild_id": guild_id })
+        .await?
+        .try_collect()
+        .await?;
+    Ok(entries)
+}
+
+/// Returns `false` if the player is already watched by this guild.
+pub async fn add_watch_entry(entry: WatchEntry) -> Result<bool> {
+    let col = watchlist_collection()?;
+    let filter = doc! { "guild_id": entry.guild_id, "player_id": entry.player_id };
+
+    if col.find_one(filter).await?.is_some() {
+        return Ok(false);
+    }
+
+    col.insert_one(entry).await?;
+    Ok(true)
+}
+
+pub async fn remove_watch_entry(guild_id: i64, player_id: i64) -> Result<u64> {
+    let col = watchlist_collection()?;
+    let res = col
+        .delete_one(doc! { "guild_id": guild_id, "player_id": player_id })
+        .await?;
+    Ok(res.deleted_count)
+}
+
+pub async fn set_watch_channel(guild_id: i64, channel_id: i64) -> Result<()> {
+    let col = watch_channels_collection()?;
+    col.delete_many(doc! { "guild_id": guild_id }).await?;
+    col.insert_one(WatchChannel {
+        guild_id,
+        channel_id,
+    })
+    .await?;
+    Ok(())
+}
+
+pub async fn get_watch_channel(guild_id: i64) -> Result<Option<i64>> {
+    let col = watch_channels_collection()?;
+    Ok(col
+        .find_one(doc! { "guild_id": guild_id })
+        .await?
+        .map(|c| c.channel_id))
+}
+
+async fn update_last_state(entry: &WatchEntry, summary: &LucksackPlayerSummary) -> Result<()> {
+    let col = watchlist_collection()?;
+    col.update_one(
+        doc! { "guild_id": entry.guild_id, "player_id": entry.player_id },
+        doc! { "$set": {
+            "player_name": &summary.user_info.username,
+            "last_score": summary.summary.current_score,
+            "last_rank": summary.summary.current_rank,
+            "last_bracket": summary.summary.current_rank_bracket,
+        }},
+    )
+    .await?;
+    Ok(())
+}
+
+/* ------------------ Change detection ------------------ */
+
+fn is_top_100(rank: i64) -> bool {
+    (1..=100).contains(&rank)
+}
+
+/// Compares the stored state of `entry` with a fresh summary. Nothing is
+/// reported on the first poll, which only records the baseline.
+pub fn detect_watch_events(entry: &WatchEntry, summary: &LucksackPlayerSummary) -> Vec<WatchEvent> {
+    let mut events = Vec::new();
+    let current = &summary.summary;
+
+    if let Some(from) = entry.last_bracket {
+        if from != current.current_rank_bracket {
+            events.push(WatchEvent::BracketChanged {
+                from,
+                to: current.current_rank_bracket,
+            });
+        }
+    }
+
+    if let (Some(threshold), Some(last_score)) = (entry.score_threshold, entry.last_score) {
+        if last_score < threshold && current.current_score >= threshold {
+            events.push(WatchEvent::ThresholdCrossed {
+                threshold,
+                upward: true,
+            });
+        } else if last_score >= threshold && current.current_score < threshold {
+            events.push(WatchEvent::ThresholdCrossed {
+                threshold,
+                upward: false,
+            });
+        }
+    }
+
+    if let Some(last_rank) = entry.last_rank {
+        if !is_top_100(last_rank) && is_top_100(current.current_rank) {
+            events.push(WatchEvent::EnteredTop100 {
+                rank: current.current_rank,
+            });
+        }
+    }
+
+    events
+}
+
+fn format_watch_event(event: &WatchEvent) -> String {
+    match event {
+        WatchEvent::BracketChanged { from, to } => {
+            let arrow = if to > from { "📈" } else { "📉" };
+            format!(
+                "{} Rank bracket: {} → {}",
+                arrow,
+                get_rank_emojis_for_bracket(*from),
+                get_rank_emojis_for_bracket(*to)
+            )
+        }
+        WatchEvent::ThresholdCrossed { threshold, upward } => {
+            if *upward {
+                format!("⬆️ Crossed **{}** points", threshold)
+            } else {
+                format!("⬇️ Dropped below **{}** points", threshold)
+            }
+        }
+        WatchEvent::EnteredTop100 { rank } => {
+            format!("🏆 Entered the top 100 (#{})", rank)
+        }
+    }
+}
+
+pub fn create_watch_notification_embed(
+    summary: &LucksackPlayerSummary,
+    events: &[WatchEvent],
+) -> CreateEmbed {
+    let info = &summary.user_info;
+    let lines: Vec<String> = events.iter().map(format_watch_event).collect();
+
+    CreateEmbed::default()
+        .title(format!(
+            ":flag_{}: {} - Watchlist update",
+            info.country.to_lowercase(),
+            info.username
+        ))
+        .url(format!("https://lucksack.gg/player/{}", info.player_id))
+        .thumbnail(info.image.clone())
+        .color(serenity::Colour::from_rgb(255, 215, 0))
+        .description(lines.join("\n"))
+        .field(
+            "Current",
+            format!(
+                "Elo **{}** • Rank #{} {}",
+                summary.summary.current_score,
+                summary.summary.current_rank,
+                get_rank_emojis_for_bracket(summary.summary.current_rank_bracket)
+            ),
+            false,
+        )
+        .footer(CreateEmbedFooter::new("Data is gathered from lucksack.gg"))
+}
+
+pub fn create_watchlist_embed(entries: &[WatchEntry], channel_id: Option<i64>) -> CreateEmbed {
+    let description = if entries.is_empty() {
+        "No player is watched yet. Use `/watch add <player>`.".to_string()
+    } else {
+        entries
+            .iter()
+            .map(|e| {
+                let score = e
+                    .last_score
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "N/A".to_string());
+                let rank = e
+                    .last_rank
+                    .map(|r| format!("#{}", r))
+                    .unwrap_or_else(|| "N/A".to_string());
+                let threshold = e
+                    .score_threshold
+                    .map(|t| format!(" • threshold {}", t))
+                    .unwrap_or_default();
+                format!(
+                    "- **{}** : Elo {} • {}{}",
+                    e.player_name, score, rank, threshold
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let channel = match channel_id {
+        Some(id) => format!("<#{}>", id),
+        None => "Not set, use `/watch channel`".to_string(),
+    };
+
+    CreateEmbed::default()
+        .title(format!(
+            "👀 Watchlist ({}/{})",
+            entries.len(),
+            MAX_WATCHED_PLAYERS
+        ))
+        .color(serenity::Colour::from_rgb(0, 180, 255))
+        .description(description)
+        .field("Notification channel", channel, false)
+}
+
+/* ------------------ Background polling ------------------ */
+
+/// Polls every watched player once and posts the detected changes to the
+/// notification channel of each guild watching them.
+pub async fn poll_watchlist(serenity_ctx: &SerenityContext) -> Result<()> {
+    let col = watchlist_collection()?;
+    let entries: Vec<WatchEntry> = col.find(doc! {}).await?.try_collect().await?;
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let season = get_lucksack_season_numbers()
+        .await?
+        .first()
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("No lucksack season found"))?;
+
+    // Un seul appel par joueur, même s'il est suivi par plusieurs serveurs
+    let mut by_player: HashMap<i64, Vec<WatchEntry>> = HashMap::new();
+    for entry in entries {
+        by_player.entry(entry.player_id).or_default().push(entry);
+    }
+
+    let mut channels: HashMap<i64, Option<i64>> = HashMap::new();
+
+    for (player_id, entries) in by_player {
+        let summary = match get_lucksack_player_summary(player_id, season).await {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Watchlist: failed to fetch player {player_id}: {e:?}");
+                continue;
+            }
+        };
+
+        for entry in &entries {
+            let events = detect_watch_events(entry, &summary);
+
+            if !events.is_empty() {
+                let channel_id = match channels.get(&entry.guild_id) {
+                    Some(c) => *c,
+                    None => {
+                        let c = get_watch_channel(entry.guild_id).await.unwrap_or(None);
+                        channels.insert(entry.guild_id, c);
+                        c
+                    }
+                };
+
+                if let Some(channel_id) = channel_id {
+                    // Pas de panic si le bot n'a pas les droits dans le salon
+                    let _ = serenity::ChannelId::new(channel_id as u64)
+                        .send_message(
+                            &serenity_ctx.http,
+                            CreateMessage::new()
+                                .embed(create_watch_notification_embed(&summary, &events)),
+                        )
+                        .await;
+                }
+            }
+
+            if let Err(e) = update_last_state(entry, &summary).await {
+                eprintln!("Watchlist: failed to update player {player_id}: {e:?}");
+            }
+        }
+
+        tokio::time::sleep(std::time::Duration::from_millis(POLL_DELAY_MS)).await;
+    }
+
+    Ok(())
+}
@@ -19,17 +19,31 @@ use serde::Deserialize;
 use std::{collections::HashMap, fs};
 
 // use crate::commands::duo_stats::get_duo_stats::get_duo_stats;
+use crate::commands::alias::command::alias;
 use crate::commands::best_pve_teams::command::best_pve_teams;
+use crate::commands::guild_ranking::cache::refresh_roster;
+use crate::commands::guild_ranking::command::guild_ranking;
+use crate::commands::h2h::command::h2h;
 use crate::commands::help::command::help;
 use crate::commands::how_to_build::command::how_to_build;
 use crate::commands::leaderboard::command::get_rta_leaderboard;
+use crate::commands::leaderboard::snapshots::take_daily_snapshot;
 use crate::commands::meta::command::get_meta;
 use crate::commands::mob_stats::command::get_mob_stats;
+use crate::commands::my_target::command::my_target;
+use crate::commands::my_target::utils::check_target_reminders;
 use crate::commands::mystats::command::mystats;
+use crate::commands::player_analysis::command::get_player_analysis;
+use crate::commands::player_box::command::player_box;
+use crate::commands::player_box::utils::purge_expired_boxes;
 use crate::commands::player_names::command::track_player_names;
+use crate::commands::player_names::snapshots::ensure_name_snapshot_indexes;
 use crate::commands::player_stats::command::get_player_stats;
 use crate::commands::ranks::command::get_ranks;
+use crate::commands::ranks::history::record_cutoffs;
+use crate::commands::ranks::prediction::set_prediction_enabled;
 use crate::commands::register::command::register;
+use crate::commands::register::utils::record_user_guild;
 use crate::commands::replays::command::get_replays;
 use crate::commands::rta_core::command::get_rta_core;
 use crate::commands::services::command::services;
@@ -37,10 +51,14 @@ use crate::commands::shared::clients::{http_client, init_mongo_client};
 use crate::commands::shared::coupons::{
     apply_coupons_to_all_users, notify_new_coupons, update_coupon_list,
 };
+use crate::commands::shared::player_alias::load_alias_registry;
+use crate::commands::shared::portraits::sync_portraits;
 use crate::commands::suggestion::command::send_suggestion;
 use crate::commands::support::command::support;
 use crate::commands::unregister::command::unregister;
 use crate::commands::upload_json::command::upload_json;
+use crate::commands::watch::command::watch;
+use crate::commands::watch::utils::poll_watchlist;
 
 lazy_static! {
     static ref LOG_CHANNEL_ID: Arc<Mutex<u64>> = Arc::new(Mutex::new(0));
@@ -251,11 +269,25 @@ async fn main() -> Result<()> {
     let mongo_uri = env_required("MONGO_URI")?;
     init_mongo_client(&mongo_uri).await?;
 
+    // Aliases: the bundled list stays in use if Mongo cannot be read
+    match load_alias_registry().await {
+        Ok(count) => println!("{count} player aliases loaded"),
+        Err(e) => eprintln!("Failed to load player aliases, using bundled list: {e:?}"),
+    }
+    if let Err(e) = ensure_name_snapshot_indexes().await {
+        eprintln!("Failed to create the player name snapshot index: {e:?}");
+    }
+
     *GUARDIAN_EMOJI_ID.lock().unwrap() = guardian_emoji_id;
     *PUNISHER_EMOJI_ID.lock().unwrap() = punisher_emoji_id;
     *CONQUEROR_EMOJI_ID.lock().unwrap() = conqueror_emoji_id;
     *LOG_CHANNEL_ID.lock().unwrap() = log_channel_id;
 
+    // Prédictions de seuils : désactivées sauf ENABLE_PREDICTION=true
+    set_prediction_enabled(
+        env::var("ENABLE_PREDICTION").is_ok_and(|v| v.eq_ignore_ascii_case("true")),
+    );
+
     let username = env_required("USERNAME")?;
     let password = env_required("PASSWORD")?;
 
@@ -313,6 +345,81 @@ async fn main() -> Result<()> {
         }
     });
 
+    // Watchlist loop
+    tokio::spawn(async move {
+        while SERENITY_CTX.get().is_none() {
+            sleep(Duration::from_secs(1)).await;
+        }
+        loop {
+            if let Some(ctx) = SERENITY_CTX.get() {
+                if let Err(e) = poll_watchlist(ctx).await {
+                    eprintln!("Failed to poll watchlist: {e:?}");
+                }
+            }
+            sleep(Duration::from_secs(900)).await;
+        }
+    });
+
+    // Rank target reminders loop
+    tokio::spawn(async move {
+        while SERENITY_CTX.get().is_none() {
+            sleep(Duration::from_secs(1)).await;
+        }
+        loop {
+            if let Some(ctx) = SERENITY_CTX.get() {
+                if let Err(e) = check_target_reminders(ctx).await {
+                    eprintln!("Failed to check rank target reminders: {e:?}");
+                }
+            }
+            sleep(Duration::from_secs(3600)).await;
+        }
+    });
+
+    // Guild ranking roster loop
+    tokio::spawn(async move {
+        loop {
+            match refresh_roster().await {
+                Ok(count) => println!("Guild ranking roster refreshed ({count} players)"),
+                Err(e) => eprintln!("Failed to refresh guild ranking roster: {e:?}"),
+            }
+            sleep(Duration::from_secs(1800)).await;
+        }
+    });
+
+    // Leaderboard snapshot loop (one snapshot per UTC day, checked hourly)
+    tokio::spawn(async move {
+        loop {
+            match take_daily_snapshot().await {
+                Ok(true) => println!("Leaderboard snapshot saved"),
+                Ok(false) => {}
+                Err(e) => eprintln!("Failed to snapshot leaderboard: {e:?}"),
+            }
+            sleep(Duration::from_secs(3600)).await;
+        }
+    });
+
+    // Rank cutoff history loop
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = record_cutoffs().await {
+                eprintln!("Failed to record rank cutoffs: {e:?}");
+            }
+            sleep(Duration::from_secs(3600)).await;
+        }
+    });
+
+    // Saved box expiry loop
+    tokio::spawn(async move {
+        loop {
+            match purge_expired_boxes().await {
+                Ok(0) => {}
+                Ok(count) => println!("{count} expired player boxes deleted"),
+                Err(e) => eprintln!("Failed to purge expired player boxes: {e:?}"),
+            }
+            sleep(Duration::from_secs(3600 * 6)).await;
+        }
+    });
+
     // Download monsters json
     let monsters_url =
         "https://raw.githubusercontent.com/B4tiste/BP-data/refs/heads/main/data/monsters_elements.json";
@@ -335,6 +442,14 @@ async fn main() -> Result<()> {
     tokio::fs::write("monsters_catalog.json", &lucksack_catalog_content).await?;
     println!("monsters_catalog.json downloaded");
 
+    // Sync monster portraits to disk (missing ones render as placeholders meanwhile)
+    tokio::spawn(async move {
+        match sync_portraits().await {
+            Ok(count) => println!("{count} monster portraits downloaded"),
+            Err(e) => eprintln!("Failed to sync monster portraits: {e:?}"),
+        }
+    });
+
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
             commands: vec![
@@ -356,7 +471,27 @@ async fn main() -> Result<()> {
                 register(),
                 unregister(),
                 mystats(),
+                get_player_analysis(),
+                h2h(),
+                watch(),
+                guild_ranking(),
+                alias(),
+                my_target(),
+                player_box(),
             ],
+            pre_command: |ctx| {
+                Box::pin(async move {
+                    // Serveur du compte lié, pour /guild_ranking
+                    if let Some(guild_id) = ctx.guild_id() {
+                        let user_id = ctx.author().id.get();
+                        tokio::spawn(async move {
+                            if let Err(e) = record_user_guild(user_id, guild_id.get()).await {
+                                eprintln!("Failed to record the guild of {user_id}: {e:?}");
+                            }
+                        });
+                    }
+                })
+            },
             ..Default::default()
         })
         .setup(|ctx, _ready, framework| {